@@ -0,0 +1,67 @@
+//! Compute unit benchmarks for every AMM instruction
+//!
+//! Needs the program built with `cargo build-sbf` first, then run `cargo bench --bench compute_units`.
+//!
+//! The consumed CUs are checked against `benches/compute_units.baseline`, see
+//! `program_utils::bench` for the report and the environment variables.
+#[allow(dead_code)]
+#[path = "../tests/helpers/mod.rs"]
+mod helpers;
+
+use amm::ID;
+use helpers::{
+    get_mollusk,
+    ix_configs::{
+        init_bonding_curve_configs::get_init_bonding_curve_configs,
+        init_global_configs::get_init_global_configs,
+        route_swap_configs::get_route_swap_configs,
+        swap_configs::{get_swap_configs, SWAP_SLOT},
+    },
+    ReturnVal,
+};
+use program_utils::bench::check_compute_units;
+use solana_sdk::{instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+use std::process::exit;
+
+fn main() {
+    let program_id = Pubkey::new_from_array(ID);
+    let mut mollusk = get_mollusk(&program_id);
+
+    let init_global = get_init_global_configs(&program_id);
+    let init_curve = get_init_bonding_curve_configs(&mollusk, &program_id);
+
+    mollusk.warp_to_slot(SWAP_SLOT);
+    let buy = get_swap_configs(&mollusk, &program_id, 0, LAMPORTS_PER_SOL, 1, 0);
+    let sell = get_swap_configs(&mollusk, &program_id, 1, 1_000_000_000, 1, LAMPORTS_PER_SOL);
+    let route = get_route_swap_configs(&mollusk, &program_id, 1_000_000_000, 1);
+
+    let cases = [
+        ("init_global", init_global),
+        ("process_init_bonding_curve", init_curve),
+        ("swap_buy", buy),
+        ("swap_sell", sell),
+        ("route_swap", route),
+    ];
+
+    let mut results = Vec::with_capacity(cases.len());
+
+    for (name, config) in cases {
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = config;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+        let result = mollusk.process_instruction(&ix, &account_infos);
+
+        if result.program_result.is_err() {
+            eprintln!("{name} failed: {:?}", result.program_result);
+            exit(1);
+        }
+
+        results.push((name, result.compute_units_consumed));
+    }
+
+    check_compute_units(&results);
+}
@@ -1,7 +1,4 @@
-use {
-    pinocchio::program_error::{ProgramError, ToStr},
-    thiserror_no_std::Error,
-};
+use {pinocchio::program_error::ToStr, thiserror_no_std::Error};
 
 #[derive(Clone, Debug, Eq, PartialEq, Error)]
 pub enum AmmError {
@@ -23,13 +20,53 @@ pub enum AmmError {
     SlippageExceeded,
     #[error("Invariant Failed")]
     InvariantFailed,
+    #[error("Unsupported account layout version")]
+    UnsupportedLayoutVersion,
+    #[error("Curve Not complete yet")]
+    CurveNotComplete,
+    #[error("Curve Not graduated yet")]
+    CurveNotGraduated,
+    #[error("Curve reserves not empty")]
+    ReservesNotEmpty,
+    #[error("Nothing to claim")]
+    NothingToClaim,
+    #[error("Curve paused")]
+    CurvePaused,
+    #[error("Invalid curve status transition")]
+    InvalidStatusTransition,
+    #[error("Max wallet exceeded")]
+    MaxWalletExceeded,
+    #[error("Not allowlisted")]
+    NotAllowlisted,
+    #[error("Allowlist limit exceeded")]
+    AllowlistLimitExceeded,
+    #[error("Not a multisig signer")]
+    NotMultisigSigner,
+    #[error("Proposal already approved")]
+    AlreadyApproved,
+    #[error("Approval threshold not met")]
+    ThresholdNotMet,
+    #[error("Proposal already executed")]
+    ProposalExecuted,
+    #[error("Crowdfund failed")]
+    CrowdfundFailed,
+    #[error("Crowdfund not failed")]
+    CrowdfundNotFailed,
+    #[error("Price impact exceeded")]
+    PriceImpactExceeded,
+    #[error("Deadline exceeded")]
+    DeadlineExceeded,
+    #[error("Sell cooldown active")]
+    SellCooldownActive,
+    #[error("Crowdfund not settled")]
+    CrowdfundNotSettled,
+    #[error("Transfer hook not allowed")]
+    TransferHookNotAllowed,
+    #[error("Price limit exceeded")]
+    PriceLimitExceeded,
 }
 
-impl From<AmmError> for ProgramError {
-    fn from(e: AmmError) -> Self {
-        ProgramError::Custom(e as u32)
-    }
-}
+program_utils::impl_program_error!(AmmError);
 
 impl ToStr for AmmError {
     fn to_str<E>(&self) -> &'static str {
@@ -43,6 +80,46 @@ impl ToStr for AmmError {
             AmmError::CouldNotBuy => "Swap Error: Couldn't buy tokens",
             AmmError::CouldNotSell => "Swap Error: Couldn't sell tokens",
             AmmError::InvariantFailed => "Swap Error: Invariants failed",
+            AmmError::UnsupportedLayoutVersion => {
+                "Load Error: Account layout version not supported"
+            }
+            AmmError::CurveNotComplete => "Validation Error: Curve Not complete yet",
+            AmmError::CurveNotGraduated => "Validation Error: Curve Not graduated yet",
+            AmmError::ReservesNotEmpty => "Validation Error: Curve still holds reserves",
+            AmmError::NothingToClaim => "Vesting Error: Nothing unlocked to claim",
+            AmmError::CurvePaused => "Validation Error: Curve paused",
+            AmmError::InvalidStatusTransition => {
+                "Validation Error: Curve status can't change that way"
+            }
+            AmmError::MaxWalletExceeded => "Swap Error: Buy exceeds the max wallet holding",
+            AmmError::NotAllowlisted => "Validation Error: Buyer not on the curve allowlist",
+            AmmError::AllowlistLimitExceeded => {
+                "Swap Error: Buy exceeds the allowlist limit of the buyer"
+            }
+            AmmError::NotMultisigSigner => "Multisig Error: Signer not part of the multisig",
+            AmmError::AlreadyApproved => "Multisig Error: Signer already approved the proposal",
+            AmmError::ThresholdNotMet => "Multisig Error: Proposal lacks approvals",
+            AmmError::ProposalExecuted => "Multisig Error: Proposal already executed",
+            AmmError::CrowdfundFailed => {
+                "Validation Error: Crowdfund missed its target, only refunds are open"
+            }
+            AmmError::CrowdfundNotFailed => {
+                "Validation Error: Curve isn't a crowdfund that missed its target"
+            }
+            AmmError::PriceImpactExceeded => {
+                "Swap Error: Trade moves the price beyond the config limit"
+            }
+            AmmError::DeadlineExceeded => "Swap Error: Swap landed after its deadline slot",
+            AmmError::SellCooldownActive => {
+                "Swap Error: Sell before the cooldown since the wallet's last buy is over"
+            }
+            AmmError::CrowdfundNotSettled => {
+                "Vesting Error: Crowdfund hasn't reached its target, the allocation stays locked"
+            }
+            AmmError::TransferHookNotAllowed => {
+                "Validation Error: Transfer hook program isn't allowed by the config"
+            }
+            AmmError::PriceLimitExceeded => "Swap Error: Swap price worse than its limit price",
         }
     }
 }
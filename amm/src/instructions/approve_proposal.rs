@@ -0,0 +1,53 @@
+use crate::{
+    load, load_read_only, require, require_signer, require_writable,
+    states::multisig::{Multisig, Proposal},
+};
+use pinocchio::{
+    account_info::AccountInfo, log::sol_log, program_error::ProgramError, pubkey::pubkey_eq,
+    ProgramResult,
+};
+
+/// Accounts of `ApproveProposal`
+///
+/// - `signer`: multisig signer approving the proposal
+/// - `proposal`: pending proposal of `multisig`
+pub struct ApproveProposalAccounts<'a> {
+    pub signer: &'a AccountInfo,
+    pub multisig: &'a AccountInfo,
+    pub proposal: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ApproveProposalAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [signer, multisig, proposal] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        require_signer(signer)?;
+        require_writable(proposal)?;
+
+        Ok(Self {
+            signer,
+            multisig,
+            proposal,
+        })
+    }
+}
+
+/// Records the approval of a multisig signer on a proposal that hasn't run yet
+pub fn process_approve_proposal(accounts: &[AccountInfo]) -> ProgramResult {
+    sol_log("AMM Instruction: Approve Proposal");
+    let ctx = ApproveProposalAccounts::try_from(accounts)?;
+
+    let multisig_data = load_read_only::<Multisig>(ctx.multisig, &crate::ID)?;
+    let proposal_data = load::<Proposal>(ctx.proposal, &crate::ID)?;
+
+    require(
+        pubkey_eq(&proposal_data.multisig, ctx.multisig.key()),
+        ProgramError::InvalidAccountData,
+    )?;
+
+    proposal_data.approve(multisig_data.signer_index(ctx.signer.key())?)
+}
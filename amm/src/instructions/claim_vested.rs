@@ -0,0 +1,142 @@
+use crate::{
+    checked_add, load, load_read_only, require, require_owner, require_writable,
+    states::{bonding_curve::BondingCurve, vesting::Vesting},
+    token::TransferChecked,
+    AmmError,
+};
+use {
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::Signer,
+        log::sol_log,
+        program_error::ProgramError,
+        pubkey::pubkey_eq,
+        sysvars::{clock::Clock, Sysvar},
+        ProgramResult,
+    },
+    pinocchio_token_2022::{
+        state::{Mint, TokenAccount},
+        ID as TOKEN_PROGRAM_ID,
+    },
+};
+
+/// Accounts of `ClaimVested`, anyone can crank a claim
+///
+/// - `vesting`, `vesting_vault`: vesting PDA of `mint` and its ATA
+/// - `curve`: curve of `mint`, a crowdfund curve only releases its allocation once the crowdfund
+///   can't fail anymore
+/// - `beneficiary_token_account`: `mint` token account owned by the vesting beneficiary
+/// - `transfer_hook_accounts`: remaining accounts, forwarded to the token transfer for the transfer
+///   hook of `mint`
+pub struct ClaimVestedAccounts<'a> {
+    pub vesting: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub curve: &'a AccountInfo,
+    pub vesting_vault: &'a AccountInfo,
+    pub beneficiary_token_account: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub transfer_hook_accounts: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClaimVestedAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [vesting, mint, curve, vesting_vault, beneficiary_token_account, token_program, transfer_hook_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        for account in [vesting, vesting_vault, beneficiary_token_account] {
+            require_writable(account)?;
+        }
+
+        Vesting::check_id(vesting, mint.key())?;
+        require_owner(vesting, &crate::ID)?;
+        BondingCurve::check_id(curve, *mint.key())?;
+        require_owner(curve, &crate::ID)?;
+
+        require(
+            pubkey_eq(token_program.key(), &TOKEN_PROGRAM_ID),
+            ProgramError::IncorrectProgramId,
+        )?;
+
+        let beneficiary = load_read_only::<Vesting>(vesting, &crate::ID)?.beneficiary;
+
+        {
+            let vault_info = TokenAccount::from_account_info(vesting_vault)
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+            let beneficiary_info = TokenAccount::from_account_info(beneficiary_token_account)
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+
+            require(
+                pubkey_eq(vault_info.mint(), mint.key())
+                    && pubkey_eq(beneficiary_info.mint(), mint.key()),
+                AmmError::InvalidMint.into(),
+            )?;
+
+            require(
+                pubkey_eq(vault_info.owner(), vesting.key())
+                    && pubkey_eq(beneficiary_info.owner(), &beneficiary),
+                ProgramError::IncorrectAuthority,
+            )?;
+        }
+
+        Ok(Self {
+            vesting,
+            mint,
+            curve,
+            vesting_vault,
+            beneficiary_token_account,
+            token_program,
+            transfer_hook_accounts,
+        })
+    }
+}
+
+/// Sends everything unlocked and not yet claimed from the vesting vault to the beneficiary
+///
+/// Nothing is released from a crowdfund curve before it settles, refunds pay out to every holder
+/// pro-rata and the allocation must not take a share.
+pub fn process_claim_vested(accounts: &[AccountInfo]) -> ProgramResult {
+    sol_log("AMM Instruction: Claim Vested");
+    let ctx = ClaimVestedAccounts::try_from(accounts)?;
+    let slot = (Clock::get()?).slot;
+
+    require(
+        load_read_only::<BondingCurve>(ctx.curve, &crate::ID)?.crowdfund_settled(slot)?,
+        AmmError::CrowdfundNotSettled.into(),
+    )?;
+
+    let vesting_data = load::<Vesting>(ctx.vesting, &crate::ID)?;
+
+    let claimable = vesting_data
+        .claimable_amount(slot)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    require(claimable > 0, AmmError::NothingToClaim.into())?;
+
+    let decimals = Mint::from_account_info(ctx.mint)
+        .map_err(|_| ProgramError::InvalidAccountData)?
+        .decimals();
+
+    let vesting_bump = [vesting_data.bump];
+    let vesting_seeds = Vesting::get_signer_seeds(ctx.mint.key(), &vesting_bump);
+
+    TransferChecked {
+        token_program: &TOKEN_PROGRAM_ID,
+        amount: claimable,
+        authority: ctx.vesting,
+        decimals,
+        from: ctx.vesting_vault,
+        to: ctx.beneficiary_token_account,
+        mint: ctx.mint,
+        hook_accounts: ctx.transfer_hook_accounts,
+    }
+    .invoke_signed(&[Signer::from(&vesting_seeds)])?;
+
+    vesting_data.claimed_amount = checked_add(vesting_data.claimed_amount, claimable)?;
+
+    Ok(())
+}
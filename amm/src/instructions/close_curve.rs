@@ -0,0 +1,197 @@
+use crate::{
+    close_account, load, load_read_only, require, require_owner, require_signer, require_writable,
+    states::{
+        bonding_curve::{BondingCurve, CurveStatus},
+        global_config::GlobalConfig,
+    },
+    transfer_fee::{HarvestWithheldTokensToMint, TransferFee},
+    AmmError,
+};
+use {
+    pinocchio::{
+        account_info::AccountInfo, instruction::Signer, log::sol_log, program_error::ProgramError,
+        pubkey::pubkey_eq, ProgramResult,
+    },
+    pinocchio_system::instructions::Transfer as SendSol,
+    pinocchio_token_2022::{
+        instructions::{Burn, CloseAccount, ThawAccount},
+        state::TokenAccount,
+        ID as TOKEN_PROGRAM_ID,
+    },
+};
+
+/// Accounts of `CloseCurve`
+///
+/// - `authority`: either the curve creator or the global config admin
+/// - `curve`, `curve_mint_ata`, `curve_sol_escrow`: the graduated curve of `mint` and its reserve
+///   accounts, all of them are closed
+/// - `mint`: writable when it charges a transfer fee or the curve ATA still holds tokens, the fees
+///   withheld in the curve ATA are harvested to it and the tokens are burned
+/// - `destination`: receives the lamports, the creator when the creator signs, the treasury when
+///   the admin signs
+pub struct CloseCurveAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub curve: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub curve_mint_ata: &'a AccountInfo,
+    pub curve_sol_escrow: &'a AccountInfo,
+    pub destination: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub sol_escrow_bump: u8,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseCurveAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, curve, mint, curve_mint_ata, curve_sol_escrow, destination, system_program, token_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        require_signer(authority)?;
+
+        for account in [curve, curve_mint_ata, curve_sol_escrow, destination] {
+            require_writable(account)?;
+        }
+
+        GlobalConfig::check_id(config)?;
+        BondingCurve::check_id(curve, *mint.key())?;
+        require_owner(curve, &crate::ID)?;
+        let sol_escrow_bump = BondingCurve::check_sol_escrow_id(curve_sol_escrow, mint.key())?;
+
+        require(
+            pubkey_eq(token_program.key(), &TOKEN_PROGRAM_ID),
+            ProgramError::IncorrectProgramId,
+        )?;
+
+        let global_config = load_read_only::<GlobalConfig>(config, &crate::ID)?;
+        let curve_data = load_read_only::<BondingCurve>(curve, &crate::ID)?;
+
+        if pubkey_eq(authority.key(), &curve_data.creator) {
+            require(
+                pubkey_eq(destination.key(), &curve_data.creator),
+                ProgramError::InvalidArgument,
+            )?;
+        } else if pubkey_eq(authority.key(), &global_config.admin) {
+            require(
+                pubkey_eq(destination.key(), &global_config.fee_receiver),
+                ProgramError::InvalidArgument,
+            )?;
+        } else {
+            return Err(ProgramError::IncorrectAuthority);
+        }
+
+        require(
+            pubkey_eq(
+                TokenAccount::from_account_info(curve_mint_ata)
+                    .map_err(|_| ProgramError::InvalidAccountData)?
+                    .owner(),
+                curve.key(),
+            ),
+            ProgramError::IncorrectAuthority,
+        )?;
+
+        Ok(Self {
+            authority,
+            config,
+            curve,
+            mint,
+            curve_mint_ata,
+            curve_sol_escrow,
+            destination,
+            system_program,
+            token_program,
+            sol_escrow_bump,
+        })
+    }
+}
+
+/// Closes the curve PDA, the curve ATA and the sol escrow of a graduated curve
+///
+/// Refuses while the curve still accounts for any reserves. Tokens the curve ATA holds on top of
+/// them, sent to it directly, are burned.
+pub fn process_close_curve(accounts: &[AccountInfo]) -> ProgramResult {
+    sol_log("AMM Instruction: Close Curve");
+    let ctx = CloseCurveAccounts::try_from(accounts)?;
+
+    let curve_data = load::<BondingCurve>(ctx.curve, &crate::ID)?;
+
+    curve_data.transition(CurveStatus::Closed)?;
+
+    require(
+        curve_data.real_sol_reserves == 0 && curve_data.real_token_reserves == 0,
+        AmmError::ReservesNotEmpty.into(),
+    )?;
+
+    let (leftover_tokens, ata_frozen) = {
+        let curve_mint_info = TokenAccount::from_account_info(ctx.curve_mint_ata)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        (curve_mint_info.amount(), curve_mint_info.is_frozen())
+    };
+
+    let curve_bump = [curve_data.bump];
+    let curve_seeds = BondingCurve::get_signer_seeds(ctx.mint.key(), &curve_bump);
+    let curve_signer = Signer::from(&curve_seeds);
+
+    if ata_frozen {
+        ThawAccount {
+            account: ctx.curve_mint_ata,
+            freeze_authority: ctx.curve,
+            mint: ctx.mint,
+            token_program: &TOKEN_PROGRAM_ID,
+        }
+        .invoke_signed(core::slice::from_ref(&curve_signer))?;
+    }
+
+    if leftover_tokens > 0 {
+        Burn {
+            account: ctx.curve_mint_ata,
+            mint: ctx.mint,
+            authority: ctx.curve,
+            amount: leftover_tokens,
+            token_program: &TOKEN_PROGRAM_ID,
+        }
+        .invoke_signed(core::slice::from_ref(&curve_signer))?;
+    }
+
+    // Token-2022 won't close an account still holding withheld transfer fees
+    if TransferFee::from_mint(ctx.mint)?.basis_points > 0 {
+        HarvestWithheldTokensToMint {
+            mint: ctx.mint,
+            sources: core::slice::from_ref(ctx.curve_mint_ata),
+        }
+        .invoke()?;
+    }
+
+    CloseAccount {
+        account: ctx.curve_mint_ata,
+        destination: ctx.destination,
+        authority: ctx.curve,
+        token_program: &TOKEN_PROGRAM_ID,
+    }
+    .invoke_signed(&[curve_signer])?;
+
+    let escrow_lamports = ctx.curve_sol_escrow.lamports();
+
+    if escrow_lamports > 0 {
+        let escrow_bump = [ctx.sol_escrow_bump];
+        let escrow_seeds = BondingCurve::get_sol_escrow_signer_seeds(ctx.mint.key(), &escrow_bump);
+
+        SendSol {
+            from: ctx.curve_sol_escrow,
+            lamports: escrow_lamports,
+            to: ctx.destination,
+        }
+        .invoke_signed(&[Signer::from(&escrow_seeds)])?;
+    }
+
+    close_account(ctx.curve, ctx.destination)?;
+
+    sol_log("Curve closed");
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use crate::{
+    require, require_empty, require_signer, require_writable,
+    states::multisig::{Multisig, MAX_MULTISIG_SIGNERS},
+};
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Signer,
+    log::sol_log,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct CreateMultisigParams {
+    pub threshold: u8,
+    pub signer_count: u8,
+    /// The first `signer_count` entries are the signers, the rest is ignored
+    pub signers: [Pubkey; MAX_MULTISIG_SIGNERS],
+}
+
+impl CreateMultisigParams {
+    pub const SIZE: usize = core::mem::size_of::<CreateMultisigParams>();
+}
+
+impl TryFrom<&[u8]> for CreateMultisigParams {
+    type Error = ProgramError;
+
+    fn try_from(ix_data: &[u8]) -> Result<Self, Self::Error> {
+        require(
+            ix_data.len() == CreateMultisigParams::SIZE,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        let params = bytemuck::try_pod_read_unaligned::<CreateMultisigParams>(ix_data)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        require(
+            params.signer_count as usize <= MAX_MULTISIG_SIGNERS,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        Multisig::validate_signers(
+            &params.signers[..params.signer_count as usize],
+            params.threshold,
+        )?;
+
+        Ok(params)
+    }
+}
+
+/// Accounts of `CreateMultisig`
+///
+/// - `payer`: signer paying for the multisig
+/// - `create_key`: signer the multisig address derives from, so nobody else can take it
+/// - `multisig`: empty multisig PDA of `create_key`
+/// - `vault`: vault PDA of `multisig`, signing the instructions of its executed proposals
+pub struct CreateMultisigAccounts<'a> {
+    pub payer: &'a AccountInfo,
+    pub create_key: &'a AccountInfo,
+    pub multisig: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub multisig_bump: u8,
+    pub vault_bump: u8,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CreateMultisigAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [payer, create_key, multisig, vault, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        require_signer(payer)?;
+        require_signer(create_key)?;
+        require_writable(payer)?;
+        require_writable(multisig)?;
+        require_empty(multisig)?;
+
+        let multisig_bump = Multisig::check_id(multisig, create_key.key())?;
+        let vault_bump = Multisig::check_vault_id(vault, multisig.key())?;
+
+        Ok(Self {
+            payer,
+            create_key,
+            multisig,
+            vault,
+            system_program,
+            multisig_bump,
+            vault_bump,
+        })
+    }
+}
+
+/// Creates an M-of-N multisig, its vault needs no account and holds whatever lamports it receives
+pub fn process_create_multisig(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResult {
+    sol_log("AMM Instruction: Create Multisig");
+    let ctx = CreateMultisigAccounts::try_from(accounts)?;
+    let params = CreateMultisigParams::try_from(ix_data)?;
+
+    let bump = [ctx.multisig_bump];
+    let seeds = Multisig::get_signer_seeds(ctx.create_key.key(), &bump);
+    let signer = Signer::from(&seeds);
+
+    CreateAccount {
+        from: ctx.payer,
+        to: ctx.multisig,
+        lamports: (Rent::get()?).minimum_balance(Multisig::SIZE),
+        space: Multisig::SIZE as u64,
+        owner: &crate::ID,
+    }
+    .invoke_signed(&[signer])?;
+
+    Multisig::init(
+        ctx.multisig,
+        ctx.multisig_bump,
+        ctx.vault_bump,
+        ctx.create_key.key(),
+        &params.signers[..params.signer_count as usize],
+        params.threshold,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use crate::{
+    checked_add, load, require, require_empty, require_signer, require_writable,
+    states::multisig::{
+        Multisig, Proposal, ProposalAccount, MAX_PROPOSAL_ACCOUNTS, MAX_PROPOSAL_DATA_LEN,
+    },
+};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Signer,
+    log::sol_log,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+/// Marks an account of the proposed instruction as a signer
+pub const PROPOSAL_ACCOUNT_SIGNER: u8 = 1 << 0;
+/// Marks an account of the proposed instruction as writable
+pub const PROPOSAL_ACCOUNT_WRITABLE: u8 = 1 << 1;
+
+/// Instruction a proposal executes, parsed from the `CreateProposal` data
+///
+/// Data layout:
+/// -  [0..32]: program id of the instruction
+/// -  [32..32 + n]: flags of each of the `n` instruction accounts
+/// -  [32 + n..]: instruction data, at most `MAX_PROPOSAL_DATA_LEN` bytes
+pub struct ProposedInstruction<'a> {
+    pub program_id: Pubkey,
+    pub account_flags: &'a [u8],
+    pub data: &'a [u8],
+}
+
+impl<'a> ProposedInstruction<'a> {
+    pub fn parse(ix_data: &'a [u8], account_count: usize) -> Result<Self, ProgramError> {
+        require(
+            account_count <= MAX_PROPOSAL_ACCOUNTS && ix_data.len() >= 32 + account_count,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        let (program_id, rest) = ix_data.split_at(32);
+        let (account_flags, data) = rest.split_at(account_count);
+
+        require(
+            data.len() <= MAX_PROPOSAL_DATA_LEN
+                && account_flags.iter().all(|flags| {
+                    flags & !(PROPOSAL_ACCOUNT_SIGNER | PROPOSAL_ACCOUNT_WRITABLE) == 0
+                }),
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        Ok(Self {
+            program_id: program_id
+                .try_into()
+                .map_err(|_| ProgramError::InvalidInstructionData)?,
+            account_flags,
+            data,
+        })
+    }
+}
+
+/// Accounts of `CreateProposal`
+///
+/// - `proposer`: multisig signer paying for the proposal
+/// - `proposal`: empty PDA of the next proposal of `multisig`
+/// - `instruction_accounts`: remaining accounts, the accounts of the proposed instruction in order
+pub struct CreateProposalAccounts<'a> {
+    pub proposer: &'a AccountInfo,
+    pub multisig: &'a AccountInfo,
+    pub proposal: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub instruction_accounts: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CreateProposalAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [proposer, multisig, proposal, system_program, instruction_accounts @ ..] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        require_signer(proposer)?;
+
+        for account in [proposer, multisig, proposal] {
+            require_writable(account)?;
+        }
+
+        require_empty(proposal)?;
+
+        Ok(Self {
+            proposer,
+            multisig,
+            proposal,
+            system_program,
+            instruction_accounts,
+        })
+    }
+}
+
+/// Stores an instruction for the multisig vault to sign once enough signers approve it
+pub fn process_create_proposal(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResult {
+    sol_log("AMM Instruction: Create Proposal");
+    let ctx = CreateProposalAccounts::try_from(accounts)?;
+    let instruction = ProposedInstruction::parse(ix_data, ctx.instruction_accounts.len())?;
+
+    let multisig_data = load::<Multisig>(ctx.multisig, &crate::ID)?;
+    multisig_data.signer_index(ctx.proposer.key())?;
+
+    let index = multisig_data.proposal_count;
+    let proposal_bump = Proposal::check_id(ctx.proposal, ctx.multisig.key(), index)?;
+
+    let index_bytes = index.to_le_bytes();
+    let bump = [proposal_bump];
+    let proposal_seeds = Proposal::get_signer_seeds(ctx.multisig.key(), &index_bytes, &bump);
+
+    CreateAccount {
+        from: ctx.proposer,
+        to: ctx.proposal,
+        lamports: (Rent::get()?).minimum_balance(Proposal::SIZE),
+        space: Proposal::SIZE as u64,
+        owner: &crate::ID,
+    }
+    .invoke_signed(&[Signer::from(&proposal_seeds)])?;
+
+    let mut proposal_accounts = [ProposalAccount::default(); MAX_PROPOSAL_ACCOUNTS];
+
+    for ((proposal_account, account), flags) in proposal_accounts
+        .iter_mut()
+        .zip(ctx.instruction_accounts)
+        .zip(instruction.account_flags)
+    {
+        *proposal_account = ProposalAccount {
+            pubkey: *account.key(),
+            is_signer: flags & PROPOSAL_ACCOUNT_SIGNER,
+            is_writable: (flags & PROPOSAL_ACCOUNT_WRITABLE) >> 1,
+        };
+    }
+
+    Proposal::init(
+        ctx.proposal,
+        proposal_bump,
+        ctx.multisig.key(),
+        index,
+        &instruction.program_id,
+        &proposal_accounts[..ctx.instruction_accounts.len()],
+        instruction.data,
+    )?;
+
+    multisig_data.proposal_count = checked_add(index, 1)?;
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use crate::{
+    load, load_read_only, require, require_signer, require_writable,
+    states::{
+        global_config::GlobalConfig,
+        treasury::{Treasury, TreasuryRecipient, MAX_TREASURY_RECIPIENTS},
+    },
+};
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Signer,
+    log::sol_log,
+    program_error::ProgramError,
+    pubkey::pubkey_eq,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::{Allocate, Assign, CreateAccount, Transfer as SendSol};
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct TreasuryRecipientsParams {
+    pub recipient_count: u8,
+    pub padding: [u8; 7],
+    /// The first `recipient_count` entries are the recipients, the rest is ignored
+    pub recipients: [TreasuryRecipient; MAX_TREASURY_RECIPIENTS],
+}
+
+impl TreasuryRecipientsParams {
+    pub const SIZE: usize = core::mem::size_of::<TreasuryRecipientsParams>();
+
+    pub fn recipients(&self) -> &[TreasuryRecipient] {
+        &self.recipients[..self.recipient_count as usize]
+    }
+}
+
+impl TryFrom<&[u8]> for TreasuryRecipientsParams {
+    type Error = ProgramError;
+
+    fn try_from(ix_data: &[u8]) -> Result<Self, Self::Error> {
+        require(
+            ix_data.len() == TreasuryRecipientsParams::SIZE,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        let params = bytemuck::try_pod_read_unaligned::<TreasuryRecipientsParams>(ix_data)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        require(
+            params.recipient_count as usize <= MAX_TREASURY_RECIPIENTS,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        Treasury::validate_recipients(params.recipients())?;
+
+        Ok(params)
+    }
+}
+
+/// Accounts of `CreateTreasury`
+///
+/// - `admin`: global config admin paying for the treasury
+/// - `treasury`: uninitialized treasury PDA, it may already hold lamports
+pub struct CreateTreasuryAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub treasury: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub treasury_bump: u8,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CreateTreasuryAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config, treasury, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        require_signer(admin)?;
+        require_writable(admin)?;
+        require_writable(treasury)?;
+
+        require(
+            treasury.data_is_empty(),
+            ProgramError::AccountAlreadyInitialized,
+        )?;
+
+        GlobalConfig::check_id(config)?;
+        let treasury_bump = Treasury::check_id(treasury)?;
+
+        let global_config = load_read_only::<GlobalConfig>(config, &crate::ID)?;
+
+        require(
+            pubkey_eq(admin.key(), &global_config.admin),
+            ProgramError::IncorrectAuthority,
+        )?;
+
+        Ok(Self {
+            admin,
+            config,
+            treasury,
+            system_program,
+            treasury_bump,
+        })
+    }
+}
+
+/// Creates the treasury PDA with its withdrawal recipients
+///
+/// Fees sent to the treasury address before it exists stay in it, the account is allocated in
+/// place instead of created when it already holds lamports.
+pub fn process_create_treasury(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResult {
+    sol_log("AMM Instruction: Create Treasury");
+    let ctx = CreateTreasuryAccounts::try_from(accounts)?;
+    let params = TreasuryRecipientsParams::try_from(ix_data)?;
+
+    let bump = [ctx.treasury_bump];
+    let treasury_seeds = Treasury::get_signer_seeds(&bump);
+    let rent_exempt_lamports = (Rent::get()?).minimum_balance(Treasury::SIZE);
+    let current_lamports = ctx.treasury.lamports();
+
+    if current_lamports == 0 {
+        CreateAccount {
+            from: ctx.admin,
+            to: ctx.treasury,
+            lamports: rent_exempt_lamports,
+            space: Treasury::SIZE as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[Signer::from(&treasury_seeds)])?;
+    } else {
+        if current_lamports < rent_exempt_lamports {
+            SendSol {
+                from: ctx.admin,
+                to: ctx.treasury,
+                lamports: rent_exempt_lamports - current_lamports,
+            }
+            .invoke()?;
+        }
+
+        Allocate {
+            account: ctx.treasury,
+            space: Treasury::SIZE as u64,
+        }
+        .invoke_signed(&[Signer::from(&treasury_seeds)])?;
+
+        Assign {
+            account: ctx.treasury,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[Signer::from(&treasury_seeds)])?;
+    }
+
+    Treasury::init(ctx.treasury, ctx.treasury_bump)?;
+
+    load::<Treasury>(ctx.treasury, &crate::ID)?.set_recipients(params.recipients())
+}
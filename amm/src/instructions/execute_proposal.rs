@@ -0,0 +1,122 @@
+use crate::{
+    load, load_read_only, require, require_pda_with_bump, require_signer, require_writable,
+    states::multisig::{Multisig, Proposal, MAX_PROPOSAL_ACCOUNTS},
+};
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::slice_invoke_signed,
+    instruction::{AccountMeta, Instruction, Signer},
+    log::sol_log,
+    program_error::ProgramError,
+    pubkey::pubkey_eq,
+    ProgramResult,
+};
+
+/// Accounts of `ExecuteProposal`
+///
+/// - `signer`: multisig signer running the proposal
+/// - `proposal`: approved proposal of `multisig`
+/// - `vault`: vault PDA of `multisig`, signs the instruction
+/// - `instruction_accounts`: remaining accounts, the accounts the proposal stored in the same
+///   order, followed by the program of the instruction when it isn't this one
+pub struct ExecuteProposalAccounts<'a> {
+    pub signer: &'a AccountInfo,
+    pub multisig: &'a AccountInfo,
+    pub proposal: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub instruction_accounts: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ExecuteProposalAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [signer, multisig, proposal, vault, instruction_accounts @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        require_signer(signer)?;
+        require_writable(proposal)?;
+
+        Ok(Self {
+            signer,
+            multisig,
+            proposal,
+            vault,
+            instruction_accounts,
+        })
+    }
+}
+
+/// Runs a proposal that reached the threshold of its multisig, with the vault signing
+///
+/// The proposal is marked executed before the instruction runs, so the instruction can't run it
+/// again.
+pub fn process_execute_proposal(accounts: &[AccountInfo]) -> ProgramResult {
+    sol_log("AMM Instruction: Execute Proposal");
+    let ctx = ExecuteProposalAccounts::try_from(accounts)?;
+
+    let multisig_data = load_read_only::<Multisig>(ctx.multisig, &crate::ID)?;
+    multisig_data.signer_index(ctx.signer.key())?;
+
+    require_pda_with_bump(
+        ctx.vault,
+        &[Multisig::VAULT_SEED_PREFIX, ctx.multisig.key().as_ref()],
+        multisig_data.vault_bump,
+        &crate::ID,
+    )?;
+
+    let proposal_data = load::<Proposal>(ctx.proposal, &crate::ID)?;
+
+    require(
+        pubkey_eq(&proposal_data.multisig, ctx.multisig.key()),
+        ProgramError::InvalidAccountData,
+    )?;
+
+    proposal_data.execute(multisig_data.threshold)?;
+
+    let proposal = *proposal_data;
+    let accounts_len = proposal.account_count as usize;
+
+    require(
+        ctx.instruction_accounts.len() >= accounts_len,
+        ProgramError::NotEnoughAccountKeys,
+    )?;
+
+    for (account, stored) in ctx
+        .instruction_accounts
+        .iter()
+        .zip(&proposal.accounts[..accounts_len])
+    {
+        require(
+            pubkey_eq(account.key(), &stored.pubkey),
+            ProgramError::InvalidArgument,
+        )?;
+    }
+
+    let account_metas: [AccountMeta; MAX_PROPOSAL_ACCOUNTS] = core::array::from_fn(|index| {
+        let stored = &proposal.accounts[index];
+        AccountMeta::new(
+            &stored.pubkey,
+            stored.is_writable == 1,
+            stored.is_signer == 1,
+        )
+    });
+    let account_infos: [&AccountInfo; MAX_PROPOSAL_ACCOUNTS] =
+        core::array::from_fn(|index| ctx.instruction_accounts.get(index).unwrap_or(ctx.proposal));
+
+    let instruction = Instruction {
+        program_id: &proposal.program_id,
+        accounts: &account_metas[..accounts_len],
+        data: &proposal.data[..proposal.data_len as usize],
+    };
+
+    let bump = [multisig_data.vault_bump];
+    let vault_seeds = Multisig::get_vault_signer_seeds(ctx.multisig.key(), &bump);
+
+    slice_invoke_signed(
+        &instruction,
+        &account_infos[..accounts_len],
+        &[Signer::from(&vault_seeds)],
+    )
+}
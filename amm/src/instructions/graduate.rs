@@ -0,0 +1,187 @@
+use crate::{
+    load, load_read_only, require, require_owner, require_signer, require_writable,
+    states::{
+        bonding_curve::{BondingCurve, CurveStatus},
+        global_config::GlobalConfig,
+        treasury::Treasury,
+    },
+    token::TransferChecked,
+    AmmError,
+};
+use {
+    pinocchio::{
+        account_info::AccountInfo, instruction::Signer, log::sol_log, program_error::ProgramError,
+        pubkey::pubkey_eq, ProgramResult,
+    },
+    pinocchio_system::instructions::Transfer as SendSol,
+    pinocchio_token_2022::{
+        instructions::ThawAccount,
+        state::{Mint, TokenAccount},
+        ID as TOKEN_PROGRAM_ID,
+    },
+};
+
+/// Accounts of `Graduate`
+///
+/// - `admin`: global config admin
+/// - `curve`, `curve_mint_ata`, `curve_sol_escrow`: the complete curve of `mint` and its reserves
+/// - `token_destination`: `mint` token account receiving every token left in the curve ATA
+/// - `treasury`: treasury PDA, receives the SOL reserves
+/// - `transfer_hook_accounts`: remaining accounts, forwarded to the token transfer for the transfer
+///   hook of `mint`
+pub struct GraduateAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub curve: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub curve_mint_ata: &'a AccountInfo,
+    pub curve_sol_escrow: &'a AccountInfo,
+    pub token_destination: &'a AccountInfo,
+    pub treasury: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub transfer_hook_accounts: &'a [AccountInfo],
+    pub sol_escrow_bump: u8,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GraduateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config, curve, mint, curve_mint_ata, curve_sol_escrow, token_destination, treasury, system_program, token_program, transfer_hook_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        require_signer(admin)?;
+
+        for account in [
+            curve,
+            curve_mint_ata,
+            curve_sol_escrow,
+            token_destination,
+            treasury,
+        ] {
+            require_writable(account)?;
+        }
+
+        GlobalConfig::check_id(config)?;
+        BondingCurve::check_id(curve, *mint.key())?;
+        require_owner(curve, &crate::ID)?;
+        let sol_escrow_bump = BondingCurve::check_sol_escrow_id(curve_sol_escrow, mint.key())?;
+        Treasury::check_id(treasury)?;
+
+        require(
+            pubkey_eq(token_program.key(), &TOKEN_PROGRAM_ID),
+            ProgramError::IncorrectProgramId,
+        )?;
+
+        let global_config = load_read_only::<GlobalConfig>(config, &crate::ID)?;
+
+        require(
+            pubkey_eq(admin.key(), &global_config.admin),
+            ProgramError::IncorrectAuthority,
+        )?;
+
+        {
+            let curve_mint_info = TokenAccount::from_account_info(curve_mint_ata)
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+            let destination_info = TokenAccount::from_account_info(token_destination)
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+
+            require(
+                pubkey_eq(curve_mint_info.mint(), mint.key())
+                    && pubkey_eq(destination_info.mint(), mint.key()),
+                AmmError::InvalidMint.into(),
+            )?;
+
+            require(
+                pubkey_eq(curve_mint_info.owner(), curve.key()),
+                ProgramError::IncorrectAuthority,
+            )?;
+        }
+
+        Ok(Self {
+            admin,
+            config,
+            curve,
+            mint,
+            curve_mint_ata,
+            curve_sol_escrow,
+            token_destination,
+            treasury,
+            system_program,
+            token_program,
+            transfer_hook_accounts,
+            sol_escrow_bump,
+        })
+    }
+}
+
+/// Hands the liquidity of a complete curve over so it can seed the post-curve market
+///
+/// Every token left in the curve ATA goes to `token_destination` and the SOL reserves go to the
+/// treasury, only `WithdrawTreasury` moves them further. The curve ATA is left thawed and the curve
+/// is marked as graduated.
+pub fn process_graduate(accounts: &[AccountInfo]) -> ProgramResult {
+    sol_log("AMM Instruction: Graduate");
+    let ctx = GraduateAccounts::try_from(accounts)?;
+
+    let curve_data = load::<BondingCurve>(ctx.curve, &crate::ID)?;
+
+    curve_data.transition(CurveStatus::Graduated)?;
+
+    let curve_bump = [curve_data.bump];
+    let curve_seeds = BondingCurve::get_signer_seeds(ctx.mint.key(), &curve_bump);
+    let curve_signer = Signer::from(&curve_seeds);
+
+    let (token_amount, ata_frozen) = {
+        let curve_mint_info = TokenAccount::from_account_info(ctx.curve_mint_ata)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        (curve_mint_info.amount(), curve_mint_info.is_frozen())
+    };
+
+    // Unfrozen vault curves never froze their ATA
+    if ata_frozen {
+        ThawAccount {
+            account: ctx.curve_mint_ata,
+            freeze_authority: ctx.curve,
+            mint: ctx.mint,
+            token_program: &TOKEN_PROGRAM_ID,
+        }
+        .invoke_signed(core::slice::from_ref(&curve_signer))?;
+    }
+
+    let decimals = Mint::from_account_info(ctx.mint)
+        .map_err(|_| ProgramError::InvalidAccountData)?
+        .decimals();
+
+    TransferChecked {
+        token_program: &TOKEN_PROGRAM_ID,
+        amount: token_amount,
+        authority: ctx.curve,
+        decimals,
+        from: ctx.curve_mint_ata,
+        to: ctx.token_destination,
+        mint: ctx.mint,
+        hook_accounts: ctx.transfer_hook_accounts,
+    }
+    .invoke_signed(&[curve_signer])?;
+
+    let escrow_bump = [ctx.sol_escrow_bump];
+    let escrow_seeds = BondingCurve::get_sol_escrow_signer_seeds(ctx.mint.key(), &escrow_bump);
+
+    SendSol {
+        from: ctx.curve_sol_escrow,
+        lamports: curve_data.real_sol_reserves,
+        to: ctx.treasury,
+    }
+    .invoke_signed(&[Signer::from(&escrow_seeds)])?;
+
+    curve_data.real_sol_reserves = 0;
+
+    sol_log("Curve graduated");
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use crate::{
+    load_read_only, require, require_owner, require_writable,
+    states::bonding_curve::BondingCurve,
+    transfer_fee::{HarvestWithheldTokensToMint, TransferFee, WithdrawWithheldTokensFromMint},
+    AmmError,
+};
+use {
+    pinocchio::{
+        account_info::AccountInfo, instruction::Signer, log::sol_log, program_error::ProgramError,
+        pubkey::pubkey_eq, ProgramResult,
+    },
+    pinocchio_token_2022::{state::TokenAccount, ID as TOKEN_PROGRAM_ID},
+};
+
+/// Accounts of `HarvestTransferFees`
+///
+/// - `mint`: launched mint charging a transfer fee
+/// - `curve`: curve PDA of `mint`, withdraw authority of the withheld fees
+/// - `creator_token_account`: `mint` token account of the curve creator receiving the fees
+/// - `sources`: token accounts of `mint` to harvest the withheld fees from, may be empty to only
+///   withdraw what the mint already holds
+pub struct HarvestTransferFeesAccounts<'a> {
+    pub mint: &'a AccountInfo,
+    pub curve: &'a AccountInfo,
+    pub creator_token_account: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub sources: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for HarvestTransferFeesAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [mint, curve, creator_token_account, token_program, sources @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        for account in [mint, creator_token_account] {
+            require_writable(account)?;
+        }
+
+        BondingCurve::check_id(curve, *mint.key())?;
+        require_owner(curve, &crate::ID)?;
+
+        require(
+            pubkey_eq(token_program.key(), &TOKEN_PROGRAM_ID),
+            ProgramError::IncorrectProgramId,
+        )?;
+
+        let curve_data = load_read_only::<BondingCurve>(curve, &crate::ID)?;
+
+        {
+            let creator_token_info = TokenAccount::from_account_info(creator_token_account)
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+
+            require(
+                pubkey_eq(creator_token_info.mint(), mint.key()),
+                AmmError::InvalidMint.into(),
+            )?;
+
+            require(
+                pubkey_eq(creator_token_info.owner(), &curve_data.creator),
+                ProgramError::IncorrectAuthority,
+            )?;
+        }
+
+        Ok(Self {
+            mint,
+            curve,
+            creator_token_account,
+            token_program,
+            sources,
+        })
+    }
+}
+
+/// Collects the transfer fees withheld on a launched mint and pays them to the curve creator
+///
+/// Anyone can call it, the fees only ever go to the creator. Works as long as the curve account
+/// exists, whatever its status.
+pub fn process_harvest_transfer_fees(accounts: &[AccountInfo]) -> ProgramResult {
+    sol_log("AMM Instruction: Harvest Transfer Fees");
+    let ctx = HarvestTransferFeesAccounts::try_from(accounts)?;
+
+    require(
+        TransferFee::from_mint(ctx.mint)?.basis_points > 0,
+        AmmError::InvalidMint.into(),
+    )?;
+
+    if !ctx.sources.is_empty() {
+        HarvestWithheldTokensToMint {
+            mint: ctx.mint,
+            sources: ctx.sources,
+        }
+        .invoke()?;
+    }
+
+    let curve_bump = [load_read_only::<BondingCurve>(ctx.curve, &crate::ID)?.bump];
+    let curve_seeds = BondingCurve::get_signer_seeds(ctx.mint.key(), &curve_bump);
+
+    WithdrawWithheldTokensFromMint {
+        mint: ctx.mint,
+        destination: ctx.creator_token_account,
+        authority: ctx.curve,
+    }
+    .invoke_signed(&[Signer::from(&curve_seeds)])?;
+
+    sol_log("Transfer fees harvested");
+    Ok(())
+}
@@ -1,162 +1,448 @@
 use crate::{
-    load, require,
-    states::{bonding_curve::BondingCurve, global_config::GlobalConfig},
+    checked_sub,
+    instructions::swap::{execute_swap, SwapAccounts},
+    load, require, require_empty, require_owner, require_signer, require_writable,
+    states::{
+        allowlist::Allowlist, bonding_curve::BondingCurve, crowdfund::Crowdfund,
+        global_config::GlobalConfig, vesting::Vesting,
+    },
+    transfer_fee::{
+        InitializeTransferFeeConfig, TransferFee, EXTENSIONS_OFFSET, TRANSFER_FEE_CONFIG_TLV_LEN,
+    },
+    transfer_hook::{InitializeTransferHook, TRANSFER_HOOK_TLV_LEN},
+    AmmError,
 };
+use bytemuck::{Pod, Zeroable};
 use {
     pinocchio::{
         account_info::AccountInfo,
         instruction::Signer,
         log::sol_log as msg,
         program_error::ProgramError,
-        pubkey::{find_program_address, pubkey_eq, Pubkey},
+        pubkey::{pubkey_eq, Pubkey},
         sysvars::{rent::Rent, Sysvar},
         ProgramResult,
     },
-    pinocchio_associated_token_account::instructions::Create as CreateAta,
-    pinocchio_system::instructions::CreateAccount,
+    pinocchio_associated_token_account::instructions::{Create as CreateAta, CreateIdempotent},
+    pinocchio_system::instructions::{CreateAccount, Transfer as SendSol},
     pinocchio_token_2022::{
         instructions::{
             AuthorityType, FreezeAccount, InitializeMint2, MintToChecked, SetAuthority,
         },
         state::Mint,
+        ID as TOKEN_PROGRAM_ID,
     },
 };
 
-pub fn process_init_bonding_curve(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-    msg("AMM Instruction: Init Bonding Curve");
-    validate(program_id, accounts)?;
-    msg("Validation completed");
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct CreateBondingCurveParams {
+    /// `BondingCurve::FROZEN_VAULT` or `BondingCurve::UNFROZEN_VAULT`
+    pub vault_mode: u8,
+    pub padding: [u8; 3],
+    /// Most a wallet may hold after a curve buy, in bps of the total supply, 0 for no cap. Only
+    /// curve buys are checked, the cap lifts once the curve completes
+    pub max_wallet_bps: u16,
+    /// Token-2022 transfer fee of the mint, at most the config's `max_transfer_fee_bps`, 0 launches
+    /// the mint without the extension
+    pub transfer_fee_bps: u16,
+    /// Lamports the creator spends on the first buy of the curve, 0 skips the buy
+    pub dev_buy_lamports: u64,
+    /// Slot trading opens at, the curve starts `Scheduled` when it is in the future and 0 opens it
+    /// right away. A scheduled curve can't take a dev buy.
+    pub starting_slot: u64,
+    /// Cap on the transfer fee of a single transfer, in base units of the mint, at most the
+    /// config's `max_transfer_fee`
+    pub max_transfer_fee: u64,
+    /// Program the Token-2022 `TransferHook` extension of the mint invokes on every transfer, one
+    /// of the config's `allowed_transfer_hooks` or zeroed to launch the mint without a hook. Curve
+    /// trades run it too, a hook rejecting transfers can block sells
+    pub transfer_hook_program: Pubkey,
+    /// Merkle root of the wallets allowed to buy before `allowlist.end_slot`, zeroed along with
+    /// the end slot to open the curve to everyone. The dev buy isn't gated
+    pub allowlist: Allowlist,
+    /// SOL the curve has to raise by `crowdfund.deadline_slot`, after its starting slot, or it
+    /// fails and refunds its holders. Zeroed along with the deadline for a regular curve
+    pub crowdfund: Crowdfund,
+}
 
-    if let [creator, config_pda, curve_pda, mint, curve_mint_ata, _curve_sol_escrow, system_program, token2022_program, _associated_token_program] =
-        accounts
-    {
-        let seeds: &[&[u8]] = &[GlobalConfig::GLOBAL_PEFIX];
+impl CreateBondingCurveParams {
+    pub const SIZE: usize = core::mem::size_of::<CreateBondingCurveParams>();
+}
 
-        let (global_config_pda, _) = find_program_address(seeds, program_id);
+impl TryFrom<&[u8]> for CreateBondingCurveParams {
+    type Error = ProgramError;
+
+    fn try_from(ix_data: &[u8]) -> Result<Self, Self::Error> {
+        // Clients predating these params send none and get a frozen vault without a dev buy
+        if ix_data.is_empty() {
+            return Ok(CreateBondingCurveParams {
+                vault_mode: BondingCurve::FROZEN_VAULT,
+                padding: [0; 3],
+                max_wallet_bps: 0,
+                transfer_fee_bps: 0,
+                dev_buy_lamports: 0,
+                starting_slot: 0,
+                max_transfer_fee: 0,
+                transfer_hook_program: [0; 32],
+                allowlist: Allowlist::default(),
+                crowdfund: Crowdfund::default(),
+            });
+        }
 
         require(
-            pubkey_eq(&global_config_pda, config_pda.key()),
-            ProgramError::IncorrectProgramId,
+            ix_data.len() == CreateBondingCurveParams::SIZE,
+            ProgramError::InvalidInstructionData,
         )?;
 
+        let params = bytemuck::try_pod_read_unaligned::<CreateBondingCurveParams>(ix_data)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
         require(
-            !config_pda.data_is_empty(),
-            ProgramError::UninitializedAccount,
+            params.vault_mode == BondingCurve::FROZEN_VAULT
+                || params.vault_mode == BondingCurve::UNFROZEN_VAULT,
+            ProgramError::InvalidInstructionData,
         )?;
 
-        let config_data = load::<GlobalConfig>(config_pda)?;
-
         require(
-            config_data.inittialized.eq(&1),
-            ProgramError::UninitializedAccount,
+            params.max_wallet_bps <= 10_000,
+            ProgramError::InvalidInstructionData,
         )?;
 
-        let curve_seeds: &[&[u8]] = &[BondingCurve::SEED_PREFIX, mint.key().as_ref()];
+        params.allowlist.validate()?;
+        params.crowdfund.validate()?;
 
-        let (expected_curve_pda, curve_bump) = find_program_address(curve_seeds, program_id);
+        Ok(params)
+    }
+}
 
-        require(
-            pubkey_eq(curve_pda.key(), &expected_curve_pda),
-            ProgramError::IncorrectProgramId,
-        )?;
+/// Accounts of `CreateBondingCurve`
+///
+/// - `creator`: signer paying for every new account
+/// - `config`: initialized global config PDA
+/// - `curve`, `mint`, `curve_mint_ata`, `curve_sol_escrow`: empty accounts created by the
+///   instruction, `mint` is a fresh keypair and has to sign. The sol escrow is a system account
+///   holding the curve's SOL, signed for with its own seeds
+/// - `fee_receiver`: the config's fee receiver, takes the creation fee and the dev buy fee
+/// - `vesting`, `vesting_vault`: empty vesting PDA of `mint` and its ATA, created by the
+///   instruction to lock the allocation minted outside the curve reserves
+/// - `creator_mint_ata`: only passed along with a dev buy, the creator's ATA for `mint`, created if
+///   missing
+/// - `transfer_hook_accounts`: remaining accounts after the dev buy ones, forwarded to the dev buy
+///   transfer for the transfer hook of `mint`
+pub struct InitBondingCurveAccounts<'a> {
+    pub creator: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub curve: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub curve_mint_ata: &'a AccountInfo,
+    pub curve_sol_escrow: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub associated_token_program: &'a AccountInfo,
+    pub fee_receiver: &'a AccountInfo,
+    pub vesting: &'a AccountInfo,
+    pub vesting_vault: &'a AccountInfo,
+    pub creator_mint_ata: Option<&'a AccountInfo>,
+    pub transfer_hook_accounts: &'a [AccountInfo],
+    pub curve_bump: u8,
+    pub sol_escrow_bump: u8,
+    pub vesting_bump: u8,
+}
 
-        CreateAccount {
-            from: creator,
-            to: curve_pda,
-            lamports: (Rent::get()?).minimum_balance(BondingCurve::CURVE_SIZE),
-            space: BondingCurve::CURVE_SIZE as u64,
-            owner: program_id,
-        }
-        .invoke()?;
+impl<'a> TryFrom<&'a [AccountInfo]> for InitBondingCurveAccounts<'a> {
+    type Error = ProgramError;
 
-        BondingCurve::init(
-            curve_bump,
-            *config_data,
-            curve_pda,
-            creator.key(),
-            mint.key(),
-        )?;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [creator, config, curve, mint, curve_mint_ata, curve_sol_escrow, system_program, token_program, associated_token_program, fee_receiver, vesting, vesting_vault, dev_buy_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
 
-        init_mint(
-            creator,
+        let (creator_mint_ata, transfer_hook_accounts) = match dev_buy_accounts {
+            [] => (None, dev_buy_accounts),
+            [creator_mint_ata, transfer_hook_accounts @ ..] => {
+                (Some(creator_mint_ata), transfer_hook_accounts)
+            }
+        };
+
+        require_signer(creator)?;
+        require_signer(mint)?;
+
+        for account in [
+            curve,
             mint,
-            token2022_program,
-            curve_pda,
-            config_data.mint_decimals,
-        )?;
+            curve_mint_ata,
+            curve_sol_escrow,
+            vesting,
+            vesting_vault,
+        ] {
+            require_writable(account)?;
+            require_empty(account)?;
+        }
+
+        GlobalConfig::check_id(config)?;
+        require_owner(config, &crate::ID)?;
+
+        let curve_bump = BondingCurve::check_id(curve, *mint.key())?;
+        let sol_escrow_bump = BondingCurve::check_sol_escrow_id(curve_sol_escrow, mint.key())?;
+        let vesting_bump = Vesting::check_id(vesting, mint.key())?;
 
-        let accounts: &[AccountInfo] = &[
-            *creator,
-            *curve_mint_ata,
-            *curve_pda,
-            *mint,
-            *system_program,
-            *token2022_program,
-        ];
-
-        mint_and_revoke_authorities(
-            accounts,
-            config_data.token_total_supply,
-            config_data.mint_decimals,
+        require(
+            pubkey_eq(token_program.key(), &TOKEN_PROGRAM_ID),
+            ProgramError::IncorrectProgramId,
         )?;
 
-        Ok(())
-    } else {
-        Err(ProgramError::NotEnoughAccountKeys)
+        Ok(Self {
+            creator,
+            config,
+            curve,
+            mint,
+            curve_mint_ata,
+            curve_sol_escrow,
+            system_program,
+            token_program,
+            associated_token_program,
+            fee_receiver,
+            vesting,
+            vesting_vault,
+            creator_mint_ata,
+            transfer_hook_accounts,
+            curve_bump,
+            sol_escrow_bump,
+            vesting_bump,
+        })
     }
 }
 
-pub fn validate(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-    if let [creator, _, curve_pda, mint, curve_mint_ata, curve_sol_escrow, _, _, _] = accounts {
-        require(creator.is_signer(), ProgramError::MissingRequiredSignature)?;
-        require(mint.is_writable(), ProgramError::MissingRequiredSignature)?;
-        require(
-            curve_pda.is_signer(),
-            ProgramError::MissingRequiredSignature,
-        )?;
+pub fn process_init_bonding_curve(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    ix_data: &[u8],
+) -> ProgramResult {
+    msg("AMM Instruction: Init Bonding Curve");
+    let ctx = InitBondingCurveAccounts::try_from(accounts)?;
+    let CreateBondingCurveParams {
+        vault_mode,
+        padding: _,
+        max_wallet_bps,
+        transfer_fee_bps,
+        dev_buy_lamports,
+        starting_slot,
+        max_transfer_fee,
+        transfer_hook_program,
+        allowlist,
+        crowdfund,
+    } = CreateBondingCurveParams::try_from(ix_data)?;
+
+    let config_data = load::<GlobalConfig>(ctx.config, &crate::ID)?;
+
+    require(
+        config_data.inittialized.eq(&1),
+        ProgramError::UninitializedAccount,
+    )?;
+
+    require(
+        transfer_fee_bps <= config_data.max_transfer_fee_bps
+            && max_transfer_fee <= config_data.max_transfer_fee,
+        ProgramError::InvalidInstructionData,
+    )?;
+
+    require(
+        pubkey_eq(ctx.fee_receiver.key(), &config_data.fee_receiver),
+        ProgramError::IncorrectProgramId,
+    )?;
+
+    // An arbitrary hook could let buys through and reject every sell
+    require(
+        transfer_hook_program == [0; 32]
+            || config_data.is_transfer_hook_allowed(&transfer_hook_program),
+        AmmError::TransferHookNotAllowed.into(),
+    )?;
+    msg("Validation completed");
 
-        require(
-            curve_pda.data_is_empty()
-                || mint.data_is_empty()
-                || curve_mint_ata.data_is_empty()
-                || curve_sol_escrow.data_is_empty(),
-            ProgramError::AccountAlreadyInitialized,
-        )?;
+    if config_data.creation_fee_lamports > 0 {
+        require_writable(ctx.fee_receiver)?;
 
-        let sol_escrow_seeds: &[&[u8]] =
-            &[BondingCurve::SOL_ESCROW_SEED_PREFIX, mint.key().as_ref()];
+        SendSol {
+            from: ctx.creator,
+            to: ctx.fee_receiver,
+            lamports: config_data.creation_fee_lamports,
+        }
+        .invoke()?;
+    }
 
-        let (expected_sol_escrow, _) = find_program_address(sol_escrow_seeds, program_id);
+    let curve_bump = [ctx.curve_bump];
+    let curve_seeds = BondingCurve::get_signer_seeds(ctx.mint.key(), &curve_bump);
 
-        require(
-            pubkey_eq(&expected_sol_escrow, curve_sol_escrow.key()),
-            ProgramError::IncorrectProgramId,
-        )?;
+    CreateAccount {
+        from: ctx.creator,
+        to: ctx.curve,
+        lamports: (Rent::get()?).minimum_balance(BondingCurve::CURVE_SIZE),
+        space: BondingCurve::CURVE_SIZE as u64,
+        owner: program_id,
+    }
+    .invoke_signed(&[Signer::from(&curve_seeds)])?;
+
+    BondingCurve::init(
+        ctx.curve_bump,
+        *config_data,
+        ctx.curve,
+        ctx.creator.key(),
+        ctx.mint.key(),
+        vault_mode,
+        starting_slot,
+        max_wallet_bps,
+        allowlist,
+        crowdfund,
+    )?;
+
+    // Rent-exempt on its own, sells can pay out every lamport of the reserves
+    let escrow_bump = [ctx.sol_escrow_bump];
+    let escrow_seeds = BondingCurve::get_sol_escrow_signer_seeds(ctx.mint.key(), &escrow_bump);
 
-        Ok(())
-    } else {
-        Err(ProgramError::NotEnoughAccountKeys)
+    CreateAccount {
+        from: ctx.creator,
+        to: ctx.curve_sol_escrow,
+        lamports: (Rent::get()?).minimum_balance(0),
+        space: 0,
+        owner: &pinocchio_system::ID,
+    }
+    .invoke_signed(&[Signer::from(&escrow_seeds)])?;
+
+    let transfer_fee = TransferFee {
+        basis_points: transfer_fee_bps,
+        maximum_fee: max_transfer_fee,
+    };
+
+    init_mint(
+        ctx.creator,
+        ctx.mint,
+        ctx.token_program,
+        ctx.curve,
+        config_data.mint_decimals,
+        transfer_fee,
+        (transfer_hook_program != [0; 32]).then_some(&transfer_hook_program),
+    )?;
+
+    init_vesting(&ctx, config_data)?;
+
+    mint_and_revoke_authorities(
+        &ctx,
+        config_data.initial_real_token_reserves,
+        config_data.token_total_supply,
+        config_data.mint_decimals,
+        vault_mode,
+    )?;
+
+    if dev_buy_lamports > 0 {
+        dev_buy(&ctx, dev_buy_lamports, config_data.creator_fee_bps)?;
     }
+
+    Ok(())
 }
 
+/// Runs the first buy of the curve for the creator, in the same transaction as the launch so it
+/// can't be front-run
+///
+/// The buy charges `creator_fee_bps` when the config sets it and the launch-phase fee otherwise.
+pub fn dev_buy(
+    ctx: &InitBondingCurveAccounts,
+    sol_amount: u64,
+    creator_fee_bps: u16,
+) -> ProgramResult {
+    let Some(creator_mint_ata) = ctx.creator_mint_ata else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    CreateIdempotent {
+        funding_account: ctx.creator,
+        account: creator_mint_ata,
+        wallet: ctx.creator,
+        mint: ctx.mint,
+        system_program: ctx.system_program,
+        token_program: ctx.token_program,
+    }
+    .invoke()?;
+
+    // Same layout as the accounts of `Swap`, the unused `mint_a` slot takes the mint
+    let swap_accounts = [
+        *ctx.creator,
+        *creator_mint_ata,
+        *ctx.mint,
+        *ctx.mint,
+        *ctx.config,
+        *ctx.curve,
+        *ctx.curve_sol_escrow,
+        *ctx.curve_mint_ata,
+        *ctx.fee_receiver,
+        *ctx.system_program,
+        *ctx.token_program,
+    ];
+    let mut swap_ctx = SwapAccounts::try_from(&swap_accounts[..])?;
+    swap_ctx.transfer_hook_accounts = ctx.transfer_hook_accounts;
+
+    let fee_bps = (creator_fee_bps > 0).then_some(creator_fee_bps);
+
+    execute_swap(&swap_ctx, 0, sol_amount, 0, 0, fee_bps)?;
+
+    Ok(())
+}
+
+/// Creates the mint, with the `TransferFeeConfig` extension when `transfer_fee` charges anything
+/// and the `TransferHook` extension when `transfer_hook_program` is set
+///
+/// The curve PDA is the withdraw authority of the withheld fees. Nobody can change the fee or the
+/// hook program.
 pub fn init_mint(
     creator: &AccountInfo,
     mint: &AccountInfo,
     token2022_program: &AccountInfo,
     curve_pda: &AccountInfo,
     decimals: u8,
+    transfer_fee: TransferFee,
+    transfer_hook_program: Option<&Pubkey>,
 ) -> Result<(), ProgramError> {
     let rent = Rent::get()?;
+    let with_transfer_fee = transfer_fee.basis_points > 0;
+
+    let mut space = EXTENSIONS_OFFSET;
+    if with_transfer_fee {
+        space += TRANSFER_FEE_CONFIG_TLV_LEN;
+    }
+    if transfer_hook_program.is_some() {
+        space += TRANSFER_HOOK_TLV_LEN;
+    }
+    if space == EXTENSIONS_OFFSET {
+        space = Mint::BASE_LEN;
+    }
+
     // Create the account for the Mint
     CreateAccount {
         from: creator,
         to: mint,
         owner: token2022_program.key(),
-        lamports: rent.minimum_balance(Mint::BASE_LEN),
-        space: Mint::BASE_LEN as u64,
+        lamports: rent.minimum_balance(space),
+        space: space as u64,
     }
     .invoke()?;
 
+    // Extensions have to be initialized before the mint itself
+    if with_transfer_fee {
+        InitializeTransferFeeConfig {
+            mint,
+            withdraw_withheld_authority: curve_pda.key(),
+            transfer_fee_basis_points: transfer_fee.basis_points,
+            maximum_fee: transfer_fee.maximum_fee,
+        }
+        .invoke()?;
+    }
+
+    if let Some(program_id) = transfer_hook_program {
+        InitializeTransferHook { mint, program_id }.invoke()?;
+    }
+
     InitializeMint2 {
         decimals,
         freeze_authority: Some(curve_pda.key()),
@@ -169,57 +455,122 @@ pub fn init_mint(
     Ok(())
 }
 
+/// Creates the vesting PDA of the curve and its vault, on the schedule and for the beneficiary the
+/// config sets
+///
+/// The allocation is minted straight into the vault, so it never sits in the curve ATA and no
+/// transfer fee is taken from it.
+pub fn init_vesting(ctx: &InitBondingCurveAccounts, config: &GlobalConfig) -> ProgramResult {
+    let beneficiary = if config.vesting_beneficiary == Vesting::CREATOR_BENEFICIARY {
+        ctx.creator.key()
+    } else {
+        &config.fee_receiver
+    };
+
+    let allocation = checked_sub(
+        config.token_total_supply,
+        config.initial_real_token_reserves,
+    )?;
+
+    let vesting_bump = [ctx.vesting_bump];
+    let vesting_seeds = Vesting::get_signer_seeds(ctx.mint.key(), &vesting_bump);
+
+    CreateAccount {
+        from: ctx.creator,
+        to: ctx.vesting,
+        lamports: (Rent::get()?).minimum_balance(Vesting::SIZE),
+        space: Vesting::SIZE as u64,
+        owner: &crate::ID,
+    }
+    .invoke_signed(&[Signer::from(&vesting_seeds)])?;
+
+    Vesting::init(
+        ctx.vesting,
+        ctx.vesting_bump,
+        ctx.mint.key(),
+        beneficiary,
+        allocation,
+        config.vesting_cliff_slots,
+        config.vesting_duration_slots,
+    )?;
+
+    CreateAta {
+        funding_account: ctx.creator,
+        account: ctx.vesting_vault,
+        wallet: ctx.vesting,
+        mint: ctx.mint,
+        system_program: ctx.system_program,
+        token_program: ctx.token_program,
+    }
+    .invoke()?;
+
+    Ok(())
+}
+
+/// Mints `real_token_reserves` to the curve ATA and the rest of `total_supply` to the vesting
+/// vault, then revokes the mint authority
 pub fn mint_and_revoke_authorities(
-    accounts: &[AccountInfo],
+    ctx: &InitBondingCurveAccounts,
+    real_token_reserves: u64,
     total_supply: u64,
     mint_decimals: u8,
+    vault_mode: u8,
 ) -> Result<(), ProgramError> {
-    if let [creator, curve_mint_ata, curve_pda, mint, system_program, token_program] = accounts {
-        CreateAta {
-            funding_account: creator,
-            account: curve_mint_ata,
-            wallet: curve_pda,
-            mint,
-            system_program,
-            token_program,
-        }
-        .invoke()?;
+    CreateAta {
+        funding_account: ctx.creator,
+        account: ctx.curve_mint_ata,
+        wallet: ctx.curve,
+        mint: ctx.mint,
+        system_program: ctx.system_program,
+        token_program: ctx.token_program,
+    }
+    .invoke()?;
 
-        let seeds = BondingCurve::get_signer_seeds(mint.key());
+    let bump = [ctx.curve_bump];
+    let seeds = BondingCurve::get_signer_seeds(ctx.mint.key(), &bump);
 
-        let signer_seeds = Signer::from(&seeds);
+    let signer_seeds = Signer::from(&seeds);
 
-        MintToChecked {
-            mint,
-            account: curve_mint_ata,
-            amount: total_supply,
-            decimals: mint_decimals,
-            mint_authority: curve_pda,
-            token_program: token_program.key(),
-        }
-        .invoke_signed(&[signer_seeds.clone()])?;
+    MintToChecked {
+        mint: ctx.mint,
+        account: ctx.curve_mint_ata,
+        amount: real_token_reserves,
+        decimals: mint_decimals,
+        mint_authority: ctx.curve,
+        token_program: ctx.token_program.key(),
+    }
+    .invoke_signed(core::slice::from_ref(&signer_seeds))?;
+
+    MintToChecked {
+        mint: ctx.mint,
+        account: ctx.vesting_vault,
+        amount: checked_sub(total_supply, real_token_reserves)?,
+        decimals: mint_decimals,
+        mint_authority: ctx.curve,
+        token_program: ctx.token_program.key(),
+    }
+    .invoke_signed(core::slice::from_ref(&signer_seeds))?;
 
-        // Setting mint authroity to none to avoid rug-pulls
+    // Setting mint authroity to none to avoid rug-pulls
 
-        SetAuthority {
-            account: mint,
-            authority: curve_pda,
-            authority_type: AuthorityType::MintTokens,
-            new_authority: None,
-            token_program: token_program.key(),
-        }
-        .invoke_signed(&[signer_seeds.clone()])?;
+    SetAuthority {
+        account: ctx.mint,
+        authority: ctx.curve,
+        authority_type: AuthorityType::MintTokens,
+        new_authority: None,
+        token_program: ctx.token_program.key(),
+    }
+    .invoke_signed(core::slice::from_ref(&signer_seeds))?;
 
+    if vault_mode == BondingCurve::FROZEN_VAULT {
         FreezeAccount {
-            account: curve_mint_ata,
-            freeze_authority: curve_pda,
-            mint,
-            token_program: token_program.key(),
+            account: ctx.curve_mint_ata,
+            freeze_authority: ctx.curve,
+            mint: ctx.mint,
+            token_program: ctx.token_program.key(),
         }
         .invoke_signed(&[signer_seeds])?;
-
-        Ok(())
-    } else {
-        Err(ProgramError::NotEnoughAccountKeys)
     }
+
+    Ok(())
 }
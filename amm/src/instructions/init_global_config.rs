@@ -1,65 +1,87 @@
 use crate::{
-    require,
+    init_account, require, require_empty, require_signer, require_writable,
     states::global_config::{GlobalConfig, GlobalSettingsInput},
 };
 use {
     pinocchio::{
         account_info::AccountInfo,
+        instruction::Signer,
         log::sol_log,
         program_error::ProgramError,
-        pubkey::{find_program_address, pubkey_eq, Pubkey},
+        pubkey::Pubkey,
         sysvars::{rent::Rent, Sysvar},
         ProgramResult,
     },
     pinocchio_system::instructions::CreateAccount,
 };
 
+/// Accounts of `CreateGlobal`
+///
+/// - `admin`: signer paying for the config
+/// - `global_config`: empty global config PDA
+pub struct InitGlobalAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub global_config: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub global_bump: u8,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for InitGlobalAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, global_config, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        require_signer(admin)?;
+        require_writable(global_config)?;
+        require_empty(global_config)?;
+        let global_bump = GlobalConfig::check_id(global_config)?;
+
+        Ok(Self {
+            admin,
+            global_config,
+            system_program,
+            global_bump,
+        })
+    }
+}
+
 pub fn init_global(program_id: &Pubkey, accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResult {
     sol_log("AMM Instruction: INIT_GLOBAL");
-    if let [admin, global_config, _] = accounts {
-        require(admin.is_signer(), ProgramError::MissingRequiredSignature)?;
-
-        require(
-            global_config.data_is_empty(),
-            ProgramError::AccountAlreadyInitialized,
-        )?;
+    let ctx = InitGlobalAccounts::try_from(accounts)?;
 
-        let seeds: &[&[u8]] = &[GlobalConfig::GLOBAL_PEFIX];
+    require(
+        ix_data.len() == GlobalSettingsInput::SIZE,
+        ProgramError::InvalidInstructionData,
+    )?;
 
-        let (global_config_pda, _) = find_program_address(seeds, program_id);
+    sol_log("Validation successful");
 
-        require(
-            pubkey_eq(&global_config_pda, global_config.key()),
-            ProgramError::IncorrectProgramId,
-        )?;
-        require(
-            ix_data.len() == GlobalConfig::SIZE,
-            ProgramError::InvalidInstructionData,
-        )?;
+    let bump = [ctx.global_bump];
+    let global_seeds = GlobalConfig::get_signer_seeds(&bump);
 
-        sol_log("Validation successful");
+    CreateAccount {
+        from: ctx.admin,
+        lamports: (Rent::get()?).minimum_balance(GlobalConfig::SIZE),
+        space: GlobalConfig::SIZE as u64,
+        owner: program_id,
+        to: ctx.global_config,
+    }
+    .invoke_signed(&[Signer::from(&global_seeds)])?;
 
-        CreateAccount {
-            from: admin,
-            lamports: (Rent::get()?).minimum_balance(GlobalConfig::SIZE),
-            space: GlobalConfig::SIZE as u64,
-            owner: program_id,
-            to: global_config,
-        }
-        .invoke()?;
+    let mut aligned_ix_buf = [0u8; GlobalSettingsInput::SIZE]; // putting raw ix_data will fail since it started at index 1 of the original instruction_data, so this new allocation is required
 
-        let mut aligned_ix_buf = [0u8; GlobalConfig::SIZE]; // putting raw ix_data will fail since it started at index 1 of the original instruction_data, so this new allocation is required
+    aligned_ix_buf.copy_from_slice(ix_data);
 
-        aligned_ix_buf.copy_from_slice(ix_data);
+    let params = bytemuck::try_from_bytes::<GlobalSettingsInput>(&aligned_ix_buf)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
 
-        let params = bytemuck::try_from_bytes::<GlobalSettingsInput>(&aligned_ix_buf)
-            .map_err(|_| ProgramError::InvalidInstructionData)?;
+    GlobalConfig::validate_settings(params)?;
 
-        GlobalConfig::validate_settings(params)?;
+    init_account::<GlobalConfig>(ctx.global_config)?;
+    GlobalConfig::update_global(*params, ctx.global_config)?;
 
-        GlobalConfig::update_global(*params, global_config)?;
-    } else {
-        return Err(ProgramError::NotEnoughAccountKeys);
-    }
     Ok(())
 }
@@ -1,13 +1,44 @@
 use pinocchio::program_error::ProgramError;
+pub mod approve_proposal;
+pub mod claim_vested;
+pub mod close_curve;
+pub mod create_multisig;
+pub mod create_proposal;
+pub mod create_treasury;
+pub mod execute_proposal;
+pub mod graduate;
+pub mod harvest_transfer_fees;
 pub mod init_bonding_curve;
 pub mod init_global_config;
+pub mod quote;
+pub mod refund;
+pub mod route_swap;
+pub mod set_curve_paused;
 pub mod swap;
+pub mod update_global;
+pub mod update_treasury;
+pub mod withdraw_treasury;
 #[repr(u8)]
 pub enum AmmInstructions {
     CreateGlobal,
     UpdateGlobal,
     CreateBondingCurve,
     Swap,
+    Graduate,
+    CloseCurve,
+    RouteSwap,
+    Quote,
+    ClaimVested,
+    SetCurvePaused,
+    HarvestTransferFees,
+    CreateMultisig,
+    CreateProposal,
+    ApproveProposal,
+    ExecuteProposal,
+    CreateTreasury,
+    UpdateTreasury,
+    WithdrawTreasury,
+    Refund,
 }
 
 impl TryFrom<&u8> for AmmInstructions {
@@ -19,6 +50,21 @@ impl TryFrom<&u8> for AmmInstructions {
             1 => Ok(AmmInstructions::UpdateGlobal),
             2 => Ok(AmmInstructions::CreateBondingCurve),
             3 => Ok(AmmInstructions::Swap),
+            4 => Ok(AmmInstructions::Graduate),
+            5 => Ok(AmmInstructions::CloseCurve),
+            6 => Ok(AmmInstructions::RouteSwap),
+            7 => Ok(AmmInstructions::Quote),
+            8 => Ok(AmmInstructions::ClaimVested),
+            9 => Ok(AmmInstructions::SetCurvePaused),
+            10 => Ok(AmmInstructions::HarvestTransferFees),
+            11 => Ok(AmmInstructions::CreateMultisig),
+            12 => Ok(AmmInstructions::CreateProposal),
+            13 => Ok(AmmInstructions::ApproveProposal),
+            14 => Ok(AmmInstructions::ExecuteProposal),
+            15 => Ok(AmmInstructions::CreateTreasury),
+            16 => Ok(AmmInstructions::UpdateTreasury),
+            17 => Ok(AmmInstructions::WithdrawTreasury),
+            18 => Ok(AmmInstructions::Refund),
             _ => Err(ProgramError::InvalidInstructionData),
         }
     }
@@ -0,0 +1,209 @@
+use crate::{
+    checked_add, load_read_only, require, require_owner,
+    states::{
+        bonding_curve::{BondingCurve, BuyResult, CurveStatus, SellResult},
+        global_config::GlobalConfig,
+    },
+    transfer_fee::TransferFee,
+};
+use bytemuck::{Pod, Zeroable};
+use {
+    pinocchio::{
+        account_info::AccountInfo,
+        cpi::set_return_data,
+        msg,
+        program_error::ProgramError,
+        sysvars::{clock::Clock, Sysvar},
+        ProgramResult,
+    },
+    pinocchio_token_2022::state::Mint,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct QuoteParams {
+    pub base_in: u8,
+    pub padding: [u8; 7],
+    pub exact_in_amount: u64,
+}
+
+impl QuoteParams {
+    pub const SIZE: usize = core::mem::size_of::<QuoteParams>();
+}
+
+impl TryFrom<&[u8]> for QuoteParams {
+    type Error = ProgramError;
+
+    fn try_from(ix_data: &[u8]) -> Result<Self, Self::Error> {
+        require(
+            ix_data.len() == QuoteParams::SIZE,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        let params = bytemuck::try_pod_read_unaligned::<QuoteParams>(ix_data)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        require(
+            params.exact_in_amount > 0,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        require(
+            params.base_in == 1 || params.base_in == 0,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        Ok(params)
+    }
+}
+
+/// Outcome of a swap against the current curve state, written as the instruction's return data
+///
+/// - `amount_in`: lamports spent on a buy fee included, or tokens sold transfer fee included
+/// - `amount_out`: tokens received after the transfer fee, or lamports paid out by the curve before
+///   the fee
+/// - `fee_lamports`: fee sent to the fee receiver
+/// - `price_impact_bps`: how much worse than the spot price the trade executes
+/// - `completes_curve`: 1 when the buy takes the last tokens of the curve
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+pub struct SwapQuote {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee_lamports: u64,
+    pub price_impact_bps: u64,
+    pub completes_curve: u8,
+    pub _padding: [u8; 7],
+}
+
+impl SwapQuote {
+    pub const SIZE: usize = core::mem::size_of::<SwapQuote>();
+}
+
+/// Accounts of `Quote`
+///
+/// - `curve`: curve PDA of `mint`, only read
+/// - `config`: global config, its price impact limit applies as in `Swap`
+pub struct QuoteAccounts<'a> {
+    pub curve: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for QuoteAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [curve, mint, config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        BondingCurve::check_id(curve, *mint.key())?;
+        require_owner(curve, &crate::ID)?;
+        GlobalConfig::check_id(config)?;
+
+        Ok(Self {
+            curve,
+            mint,
+            config,
+        })
+    }
+}
+
+/// Quotes a swap without executing it, usable from simulation or CPI
+pub fn process_quote(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResult {
+    msg!("AMM INSTRUCTION: QUOTE");
+    let ctx = QuoteAccounts::try_from(accounts)?;
+    let QuoteParams {
+        base_in,
+        padding: _,
+        exact_in_amount,
+    } = QuoteParams::try_from(ix_data)?;
+
+    // A scheduled curve past its starting slot quotes as active, the copy is never written back
+    let mut curve_data = *load_read_only::<BondingCurve>(ctx.curve, &crate::ID)?;
+
+    curve_data.require_tradable((Clock::get()?).slot)?;
+
+    let decimals = Mint::from_account_info(ctx.mint)
+        .map_err(|_| ProgramError::InvalidAccountData)?
+        .decimals();
+
+    let transfer_fee = TransferFee::from_mint(ctx.mint)?;
+    let max_price_impact_bps =
+        load_read_only::<GlobalConfig>(ctx.config, &crate::ID)?.max_price_impact_bps;
+
+    let quote = quote_swap(
+        &curve_data,
+        base_in,
+        exact_in_amount,
+        decimals,
+        transfer_fee,
+        max_price_impact_bps,
+    )?;
+
+    set_return_data(bytemuck::bytes_of(&quote));
+
+    Ok(())
+}
+
+/// Runs the swap math of `process_swap` on a copy of the curve
+///
+/// A trade moving the spot price beyond `max_price_impact_bps`, 0 for no limit, fails as it would
+/// in `Swap`. `price_impact_bps` tells how far the trade executes from the spot price.
+pub fn quote_swap(
+    curve_data: &BondingCurve,
+    base_in: u8,
+    exact_in_amount: u64,
+    decimals: u8,
+    transfer_fee: TransferFee,
+    max_price_impact_bps: u16,
+) -> Result<SwapQuote, ProgramError> {
+    let mut curve = *curve_data;
+
+    if base_in == 1 {
+        let received = transfer_fee
+            .net_amount(exact_in_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let SellResult {
+            token_amount,
+            sol_amount,
+        } = curve.apply_sell(received, decimals, max_price_impact_bps)?;
+
+        Ok(SwapQuote {
+            amount_in: exact_in_amount,
+            amount_out: sol_amount,
+            fee_lamports: curve_data.calculate_fee(sol_amount)?,
+            price_impact_bps: curve_data
+                .price_impact_bps(sol_amount, token_amount, false)
+                .ok_or(ProgramError::ArithmeticOverflow)?,
+            completes_curve: 0,
+            _padding: [0; 7],
+        })
+    } else {
+        let fee_lamports = curve_data.calculate_fee(exact_in_amount)?;
+
+        let BuyResult {
+            token_amount,
+            sol_amount,
+        } = curve.apply_buy(
+            exact_in_amount - fee_lamports,
+            decimals,
+            max_price_impact_bps,
+        )?;
+
+        Ok(SwapQuote {
+            amount_in: checked_add(sol_amount, fee_lamports)?,
+            amount_out: transfer_fee
+                .net_amount(token_amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?,
+            fee_lamports,
+            price_impact_bps: curve_data
+                .price_impact_bps(sol_amount, token_amount, true)
+                .ok_or(ProgramError::ArithmeticOverflow)?,
+            completes_curve: (curve.status == CurveStatus::Completed as u8) as u8,
+            _padding: [0; 7],
+        })
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{
+    load, require, require_owner, require_signer, require_writable,
+    states::bonding_curve::BondingCurve, token::TransferChecked, transfer_fee::TransferFee,
+    AmmError,
+};
+use bytemuck::{Pod, Zeroable};
+use {
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::Signer,
+        log::sol_log,
+        program_error::ProgramError,
+        pubkey::pubkey_eq,
+        sysvars::{clock::Clock, Sysvar},
+        ProgramResult,
+    },
+    pinocchio_system::instructions::Transfer as SendSol,
+    pinocchio_token_2022::{
+        instructions::{FreezeAccount, ThawAccount},
+        state::{Mint, TokenAccount},
+        ID as TOKEN_PROGRAM_ID,
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct RefundParams {
+    /// Tokens the holder returns to the curve, transfer fee included
+    pub token_amount: u64,
+}
+
+impl RefundParams {
+    pub const SIZE: usize = core::mem::size_of::<RefundParams>();
+}
+
+impl TryFrom<&[u8]> for RefundParams {
+    type Error = ProgramError;
+
+    fn try_from(ix_data: &[u8]) -> Result<Self, Self::Error> {
+        require(
+            ix_data.len() == RefundParams::SIZE,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        let params = bytemuck::try_pod_read_unaligned::<RefundParams>(ix_data)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        require(
+            params.token_amount > 0,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        Ok(params)
+    }
+}
+
+/// Accounts of `Refund`
+///
+/// - `holder`: signer returning tokens of a failed crowdfund curve
+/// - `holder_mint_ata`: holder's token account for `mint`
+/// - `curve`, `curve_sol_escrow`, `curve_mint_ata`: the curve PDA of `mint` and the accounts
+///   holding its reserves
+/// - `transfer_hook_accounts`: remaining accounts, forwarded to the token transfer for the transfer
+///   hook of `mint`
+pub struct RefundAccounts<'a> {
+    pub holder: &'a AccountInfo,
+    pub holder_mint_ata: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub curve: &'a AccountInfo,
+    pub curve_sol_escrow: &'a AccountInfo,
+    pub curve_mint_ata: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub transfer_hook_accounts: &'a [AccountInfo],
+    pub sol_escrow_bump: u8,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RefundAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [holder, holder_mint_ata, mint, curve, curve_sol_escrow, curve_mint_ata, system_program, token_program, transfer_hook_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        require_signer(holder)?;
+
+        for account in [
+            holder,
+            holder_mint_ata,
+            curve,
+            curve_sol_escrow,
+            curve_mint_ata,
+        ] {
+            require_writable(account)?;
+        }
+
+        BondingCurve::check_id(curve, *mint.key())?;
+        require_owner(curve, &crate::ID)?;
+        let sol_escrow_bump = BondingCurve::check_sol_escrow_id(curve_sol_escrow, mint.key())?;
+
+        require(
+            pubkey_eq(token_program.key(), &TOKEN_PROGRAM_ID),
+            ProgramError::IncorrectProgramId,
+        )?;
+
+        {
+            let holder_mint_info = TokenAccount::from_account_info(holder_mint_ata)
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+            let curve_mint_info = TokenAccount::from_account_info(curve_mint_ata)
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+
+            require(
+                pubkey_eq(holder_mint_info.mint(), mint.key())
+                    && pubkey_eq(curve_mint_info.mint(), mint.key()),
+                AmmError::InvalidMint.into(),
+            )?;
+
+            require(
+                pubkey_eq(holder_mint_info.owner(), holder.key())
+                    && pubkey_eq(curve_mint_info.owner(), curve.key()),
+                ProgramError::IncorrectAuthority,
+            )?;
+        }
+
+        Ok(Self {
+            holder,
+            holder_mint_ata,
+            mint,
+            curve,
+            curve_sol_escrow,
+            curve_mint_ata,
+            system_program,
+            token_program,
+            transfer_hook_accounts,
+            sol_escrow_bump,
+        })
+    }
+}
+
+/// Returns tokens of a crowdfund curve that missed its target for their pro-rata share of
+/// `real_sol_reserves`
+///
+/// The first refund after the deadline marks the curve `Failed`. The curve is credited with the
+/// tokens reaching its ATA, the transfer fee withheld on the way isn't refunded.
+pub fn process_refund(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResult {
+    sol_log("AMM Instruction: Refund");
+    let ctx = RefundAccounts::try_from(accounts)?;
+    let RefundParams { token_amount } = RefundParams::try_from(ix_data)?;
+
+    let curve_data = load::<BondingCurve>(ctx.curve, &crate::ID)?;
+
+    curve_data.fail_crowdfund((Clock::get()?).slot)?;
+
+    let decimals = Mint::from_account_info(ctx.mint)
+        .map_err(|_| ProgramError::InvalidAccountData)?
+        .decimals();
+    let token_fee = TransferFee::from_mint(ctx.mint)?
+        .calculate(token_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let sol_amount = curve_data
+        .apply_refund(token_amount - token_fee)
+        .ok_or(AmmError::InvalidBalance)?;
+
+    let curve_bump = [curve_data.bump];
+    let curve_seeds = BondingCurve::get_signer_seeds(ctx.mint.key(), &curve_bump);
+    let signer = Signer::from(&curve_seeds);
+    let frozen_vault = curve_data.is_frozen_vault();
+
+    if frozen_vault {
+        ThawAccount {
+            account: ctx.curve_mint_ata,
+            freeze_authority: ctx.curve,
+            mint: ctx.mint,
+            token_program: &TOKEN_PROGRAM_ID,
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+    }
+
+    TransferChecked {
+        token_program: &TOKEN_PROGRAM_ID,
+        amount: token_amount,
+        authority: ctx.holder,
+        decimals,
+        from: ctx.holder_mint_ata,
+        to: ctx.curve_mint_ata,
+        mint: ctx.mint,
+        hook_accounts: ctx.transfer_hook_accounts,
+    }
+    .invoke()?;
+
+    if frozen_vault {
+        FreezeAccount {
+            account: ctx.curve_mint_ata,
+            freeze_authority: ctx.curve,
+            mint: ctx.mint,
+            token_program: &TOKEN_PROGRAM_ID,
+        }
+        .invoke_signed(&[signer])?;
+    }
+
+    let escrow_bump = [ctx.sol_escrow_bump];
+    let escrow_seeds = BondingCurve::get_sol_escrow_signer_seeds(ctx.mint.key(), &escrow_bump);
+
+    SendSol {
+        from: ctx.curve_sol_escrow,
+        to: ctx.holder,
+        lamports: sol_amount,
+    }
+    .invoke_signed(&[Signer::from(&escrow_seeds)])?;
+
+    curve_data.invariant(&[*ctx.curve_mint_ata, *ctx.curve_sol_escrow])?;
+
+    Ok(())
+}
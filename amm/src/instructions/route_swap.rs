@@ -0,0 +1,175 @@
+use crate::{
+    checked_sub,
+    instructions::swap::{check_sell_cooldown, execute_swap, record_cooldown_buy, SwapAccounts},
+    load_read_only, log_value, require,
+    states::{bonding_curve::BondingCurve, cooldown::TradeCooldown, global_config::GlobalConfig},
+    AmmError,
+};
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::set_return_data,
+    msg,
+    program_error::ProgramError,
+    pubkey::pubkey_eq,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct RouteSwapParams {
+    /// Tokens of the first curve to sell
+    pub exact_in_amount: u64,
+    /// Minimum tokens of the second curve to receive
+    pub min_out_amount: u64,
+}
+
+impl RouteSwapParams {
+    pub const SIZE: usize = core::mem::size_of::<RouteSwapParams>();
+}
+
+impl TryFrom<&[u8]> for RouteSwapParams {
+    type Error = ProgramError;
+
+    fn try_from(ix_data: &[u8]) -> Result<Self, Self::Error> {
+        require(
+            ix_data.len() == RouteSwapParams::SIZE,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        let params = bytemuck::try_pod_read_unaligned::<RouteSwapParams>(ix_data)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        require(
+            params.exact_in_amount > 0,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        Ok(params)
+    }
+}
+
+/// Accounts of `RouteSwap`, the accounts of a `Swap` for each leg one after the other followed by
+/// the cooldown PDAs and the transfer hook accounts of both mints
+///
+/// - `sell_leg`: swap accounts of the curve the tokens are sold to
+/// - `buy_leg`: swap accounts of the curve the tokens are bought from, same buyer, config and fee
+///   receiver as `sell_leg`
+/// - `sell_cooldown`, `buy_cooldown`: the buyer's `TradeCooldown` PDAs of the sell and the buy leg,
+///   only passed when the config has a sell cooldown
+pub struct RouteSwapAccounts<'a> {
+    pub sell_leg: SwapAccounts<'a>,
+    pub buy_leg: SwapAccounts<'a>,
+    pub sell_cooldown: Option<&'a AccountInfo>,
+    pub buy_cooldown: Option<&'a AccountInfo>,
+    pub sell_cooldown_slots: u32,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RouteSwapAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        require(
+            accounts.len() >= 2 * SwapAccounts::LEN,
+            ProgramError::NotEnoughAccountKeys,
+        )?;
+
+        let (sell_accounts, accounts) = accounts.split_at(SwapAccounts::LEN);
+        let (buy_accounts, remaining_accounts) = accounts.split_at(SwapAccounts::LEN);
+
+        let mut sell_leg = SwapAccounts::try_from(sell_accounts)?;
+        let mut buy_leg = SwapAccounts::try_from(buy_accounts)?;
+
+        require(
+            pubkey_eq(sell_leg.buyer.key(), buy_leg.buyer.key())
+                && pubkey_eq(sell_leg.config.key(), buy_leg.config.key())
+                && pubkey_eq(sell_leg.fee_receiver.key(), buy_leg.fee_receiver.key()),
+            ProgramError::InvalidArgument,
+        )?;
+
+        require(
+            !pubkey_eq(sell_leg.mint_b.key(), buy_leg.mint_b.key()),
+            AmmError::InvalidMint.into(),
+        )?;
+
+        // Both legs share the config, so they take a cooldown PDA each or none
+        let sell_cooldown_slots =
+            load_read_only::<GlobalConfig>(sell_leg.config, &crate::ID)?.sell_cooldown_slots;
+
+        let (sell_cooldown, buy_cooldown, transfer_hook_accounts) = if sell_cooldown_slots > 0 {
+            let [sell_cooldown, buy_cooldown, transfer_hook_accounts @ ..] = remaining_accounts
+            else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            TradeCooldown::check_id(sell_cooldown, sell_leg.mint_b.key(), sell_leg.buyer.key())?;
+            TradeCooldown::check_id(buy_cooldown, buy_leg.mint_b.key(), buy_leg.buyer.key())?;
+
+            (
+                Some(sell_cooldown),
+                Some(buy_cooldown),
+                transfer_hook_accounts,
+            )
+        } else {
+            (None, None, remaining_accounts)
+        };
+
+        // Token-2022 only picks the accounts of the mint's own hook, both legs get all of them
+        sell_leg.transfer_hook_accounts = transfer_hook_accounts;
+        buy_leg.transfer_hook_accounts = transfer_hook_accounts;
+
+        Ok(Self {
+            sell_leg,
+            buy_leg,
+            sell_cooldown,
+            buy_cooldown,
+            sell_cooldown_slots,
+        })
+    }
+}
+
+/// Sells tokens of one curve and spends the SOL it pays out on the tokens of another curve
+///
+/// Each leg charges the fee of its own curve, the slippage bound only applies to the tokens
+/// received on the buy leg. The `SwapResult` of the buy leg is written as return data. It carries
+/// no allowlist proof, so the second curve can't be in its allowlist phase.
+pub fn process_route_swap(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResult {
+    msg!("AMM INSTRUCTION: ROUTE SWAP");
+    let ctx = RouteSwapAccounts::try_from(accounts)?;
+    let RouteSwapParams {
+        exact_in_amount,
+        min_out_amount,
+    } = RouteSwapParams::try_from(ix_data)?;
+
+    let buy_allowlist = load_read_only::<BondingCurve>(ctx.buy_leg.curve, &crate::ID)?.allowlist;
+
+    require(
+        !buy_allowlist.is_active((Clock::get()?).slot),
+        AmmError::NotAllowlisted.into(),
+    )?;
+
+    if let Some(sell_cooldown) = ctx.sell_cooldown {
+        check_sell_cooldown(sell_cooldown, ctx.sell_cooldown_slots)?;
+    }
+
+    let sell_result = execute_swap(&ctx.sell_leg, 1, exact_in_amount, 0, 0, None)?;
+
+    // Only what the seller kept after the sell fee is spent on the second curve
+    let sol_in = checked_sub(sell_result.amount_out, sell_result.fee_lamports)?;
+
+    log_value("RouteSwap: sol_in:", sol_in.into());
+
+    require(sol_in > 0, AmmError::CouldNotBuy.into())?;
+
+    let buy_result = execute_swap(&ctx.buy_leg, 0, sol_in, min_out_amount, 0, None)?;
+
+    if let Some(buy_cooldown) = ctx.buy_cooldown {
+        record_cooldown_buy(&ctx.buy_leg, buy_cooldown)?;
+    }
+
+    // Same return data as a `Swap`, describing the buy leg
+    set_return_data(bytemuck::bytes_of(&buy_result));
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use crate::{
+    load, load_read_only, require, require_owner, require_signer, require_writable,
+    states::{
+        bonding_curve::{BondingCurve, CurveStatus},
+        global_config::GlobalConfig,
+    },
+};
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    log::sol_log,
+    program_error::ProgramError,
+    pubkey::pubkey_eq,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct SetCurvePausedParams {
+    /// 1 pauses the curve, 0 resumes it
+    pub paused: u8,
+}
+
+impl SetCurvePausedParams {
+    pub const SIZE: usize = core::mem::size_of::<SetCurvePausedParams>();
+}
+
+impl TryFrom<&[u8]> for SetCurvePausedParams {
+    type Error = ProgramError;
+
+    fn try_from(ix_data: &[u8]) -> Result<Self, Self::Error> {
+        require(
+            ix_data.len() == SetCurvePausedParams::SIZE,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        let params = bytemuck::try_pod_read_unaligned::<SetCurvePausedParams>(ix_data)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        require(params.paused <= 1, ProgramError::InvalidInstructionData)?;
+
+        Ok(params)
+    }
+}
+
+/// Accounts of `SetCurvePaused`
+///
+/// - `admin`: global config admin
+/// - `curve`: curve PDA of `mint`
+pub struct SetCurvePausedAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub curve: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetCurvePausedAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config, curve, mint] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        require_signer(admin)?;
+        require_writable(curve)?;
+
+        GlobalConfig::check_id(config)?;
+        BondingCurve::check_id(curve, *mint.key())?;
+        require_owner(curve, &crate::ID)?;
+
+        let global_config = load_read_only::<GlobalConfig>(config, &crate::ID)?;
+
+        require(
+            pubkey_eq(admin.key(), &global_config.admin),
+            ProgramError::IncorrectAuthority,
+        )?;
+
+        Ok(Self {
+            admin,
+            config,
+            curve,
+            mint,
+        })
+    }
+}
+
+/// Halts or resumes trading on a curve
+///
+/// Only scheduled and active curves can be paused. A resumed curve goes back to `Scheduled` when
+/// its starting slot is still ahead and to `Active` otherwise.
+pub fn process_set_curve_paused(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResult {
+    sol_log("AMM Instruction: Set Curve Paused");
+    let ctx = SetCurvePausedAccounts::try_from(accounts)?;
+    let SetCurvePausedParams { paused } = SetCurvePausedParams::try_from(ix_data)?;
+
+    let curve_data = load::<BondingCurve>(ctx.curve, &crate::ID)?;
+
+    let next = if paused == 1 {
+        CurveStatus::Paused
+    } else if (Clock::get()?).slot < curve_data.starting_slot {
+        CurveStatus::Scheduled
+    } else {
+        CurveStatus::Active
+    };
+
+    curve_data.transition(next)?;
+
+    Ok(())
+}
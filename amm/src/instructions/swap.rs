@@ -1,25 +1,32 @@
 use crate::{
-    load, load_read_only, log_value, require,
+    checked_add, checked_sub, load, load_read_only, log_value, require, require_owner, require_pda,
+    require_signer, require_writable,
     states::{
+        allowlist::AllowlistClaim,
         bonding_curve::{BondingCurve, BuyResult, SellResult},
+        cooldown::TradeCooldown,
         global_config::GlobalConfig,
     },
+    token::TransferChecked,
+    transfer_fee::TransferFee,
     AmmError,
 };
 use bytemuck::{Pod, Zeroable};
 use {
     pinocchio::{
         account_info::AccountInfo,
+        cpi::set_return_data,
         instruction::Signer,
         msg,
         program_error::ProgramError,
         pubkey::{pubkey_eq, Pubkey},
-        sysvars::{rent::Rent, Sysvar},
+        sysvars::{clock::Clock, rent::Rent, Sysvar},
         ProgramResult,
     },
-    pinocchio_system::instructions::Transfer as SendSol,
+    pinocchio_associated_token_account::ID as ASSOCIATED_TOKEN_PROGRAM_ID,
+    pinocchio_system::instructions::{CreateAccount, Transfer as SendSol},
     pinocchio_token_2022::{
-        instructions::{FreezeAccount, ThawAccount, TransferChecked},
+        instructions::{FreezeAccount, ThawAccount},
         state::{AccountState, Mint, TokenAccount},
         ID as TOKEN_PROGRMA_ID,
     },
@@ -32,309 +39,715 @@ pub struct SwapParams {
     pub padding: [u8; 7],
     pub exact_in_amount: u64,
     pub min_out_amount: u64,
+    /// Last slot the swap may land in, 0 for no deadline
+    pub deadline_slot: u64,
+    /// Worst price accepted, in lamports per whole token fees included. The most paid on buys and
+    /// the least received on sells, 0 for no limit
+    pub limit_price_lamports: u64,
 }
 
-pub fn process_swap(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResult {
-    msg!("AMM INSTRUCTION: SWAP");
-    let (mut curve_data, swap_params) = validate(accounts, ix_data)?;
+impl SwapParams {
+    pub const SIZE: usize = core::mem::size_of::<SwapParams>();
+    /// Size of the params before `deadline_slot` and `limit_price_lamports`, both read as 0
+    pub const LEGACY_SIZE: usize = 24;
 
-    let SwapParams {
-        base_in,
-        padding: _,
-        exact_in_amount,
-        min_out_amount,
-    } = swap_params;
-
-    if let [buyer, buyer_mint_ata, _mint_a, mint_b, _config, curve_pda, curve_sol_escrow, curve_mint_ata, fee_receiver, _system_program, _token_program] =
-        accounts
-    {
-        let signer_seeds = BondingCurve::get_signer_seeds(mint_b.key());
-        let signer = Signer::from(&signer_seeds);
-        ThawAccount {
-            account: curve_mint_ata,
-            freeze_authority: curve_pda,
-            mint: mint_b,
-            token_program: &TOKEN_PROGRMA_ID,
+    /// Length of the params at the start of the `Swap` instruction data
+    ///
+    /// An allowlist proof, 8 bytes and 32 per node, may follow either layout. The lengths of the
+    /// two layouts never match modulo 32, with or without a proof.
+    pub fn len_in(ix_data: &[u8]) -> usize {
+        let len = ix_data.len();
+
+        if len == Self::LEGACY_SIZE || len % 32 == (Self::LEGACY_SIZE + 8) % 32 {
+            Self::LEGACY_SIZE
+        } else {
+            Self::SIZE
+        }
+    }
+}
+
+impl TryFrom<&[u8]> for SwapParams {
+    type Error = ProgramError;
+
+    fn try_from(ix_data: &[u8]) -> Result<Self, Self::Error> {
+        let params = match ix_data.len() {
+            // ix_data starts at index 1 of the original instruction_data, so it can't be cast in
+            // place
+            SwapParams::SIZE => bytemuck::try_pod_read_unaligned::<SwapParams>(ix_data)
+                .map_err(|_| ProgramError::InvalidInstructionData)?,
+            SwapParams::LEGACY_SIZE => {
+                let mut params = SwapParams::zeroed();
+                bytemuck::bytes_of_mut(&mut params)[..SwapParams::LEGACY_SIZE]
+                    .copy_from_slice(ix_data);
+                params
+            }
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        require(
+            params.exact_in_amount > 0,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        require(
+            params.base_in == 1 || params.base_in == 0,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        Ok(params)
+    }
+}
+
+/// Allowlist entry of the buyer, appended to the `Swap` params of buys while the curve allowlist
+/// is active
+///
+/// Layout: `max_buy_lamports` (u64) followed by the merkle proof, 32 bytes per node.
+pub struct AllowlistProof<'a> {
+    /// Lamports the buyer may spend during the allowlist phase, 0 for no limit
+    pub max_buy_lamports: u64,
+    pub proof: &'a [[u8; 32]],
+}
+
+impl<'a> TryFrom<&'a [u8]> for AllowlistProof<'a> {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let Some((max_buy_lamports, proof)) = data.split_first_chunk::<8>() else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        let proof = bytemuck::try_cast_slice::<u8, [u8; 32]>(proof)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        Ok(Self {
+            max_buy_lamports: u64::from_le_bytes(*max_buy_lamports),
+            proof,
+        })
+    }
+}
+
+/// Accounts of `Swap`
+///
+/// - `buyer`: signer trading against the curve
+/// - `buyer_mint_ata`: buyer's token account for `mint_b`, has to be the buyer's ATA when a buy
+///   is subject to the curve's max wallet cap
+/// - `config`: global config PDA, `fee_receiver` has to match the one it stores
+/// - `curve`, `curve_sol_escrow`, `curve_mint_ata`: the curve PDA of `mint_b` and the accounts
+///   holding its reserves
+/// - `transfer_hook_accounts`: remaining accounts, forwarded to the token transfer for the transfer
+///   hook of `mint_b`. A buy with a limited allowlist entry takes the buyer's `AllowlistClaim` PDA
+///   first, then the buyer's `TradeCooldown` PDA comes when the config has a sell cooldown
+pub struct SwapAccounts<'a> {
+    pub buyer: &'a AccountInfo,
+    pub buyer_mint_ata: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub mint_b: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub curve: &'a AccountInfo,
+    pub curve_sol_escrow: &'a AccountInfo,
+    pub curve_mint_ata: &'a AccountInfo,
+    pub fee_receiver: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub transfer_hook_accounts: &'a [AccountInfo],
+    pub sol_escrow_bump: u8,
+}
+
+impl SwapAccounts<'_> {
+    /// Number of accounts a swap takes before the transfer hook accounts
+    pub const LEN: usize = 11;
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SwapAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [buyer, buyer_mint_ata, mint_a, mint_b, config, curve, curve_sol_escrow, curve_mint_ata, fee_receiver, system_program, token_program, transfer_hook_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        require_signer(buyer)?;
+
+        for account in [
+            buyer_mint_ata,
+            curve,
+            curve_sol_escrow,
+            curve_mint_ata,
+            fee_receiver,
+        ] {
+            require_writable(account)?;
         }
-        .invoke_signed(&[signer.clone()])?;
 
-        let sol_amount: u64;
-        let fee_lamports: u64;
+        GlobalConfig::check_id(config)?;
+        require_owner(config, &crate::ID)?;
+        BondingCurve::check_id(curve, *mint_b.key())?;
+        require_owner(curve, &crate::ID)?;
+        let sol_escrow_bump = BondingCurve::check_sol_escrow_id(curve_sol_escrow, mint_b.key())?;
+
+        require(
+            pubkey_eq(token_program.key(), &TOKEN_PROGRMA_ID),
+            ProgramError::IncorrectProgramId,
+        )?;
+
+        let global_config = load_read_only::<GlobalConfig>(config, &crate::ID)?;
 
-        let mint_info =
-            Mint::from_account_info(mint_b).map_err(|_| ProgramError::InvalidAccountData)?;
-        if base_in == 1 {
-            // Sell Tokens
+        require(
+            pubkey_eq(fee_receiver.key(), &global_config.fee_receiver),
+            ProgramError::IncorrectProgramId,
+        )?;
+
+        {
             let buyer_mint_info = TokenAccount::from_account_info(buyer_mint_ata)
                 .map_err(|_| ProgramError::InvalidAccountData)?;
+            let curve_mint_info = TokenAccount::from_account_info(curve_mint_ata)
+                .map_err(|_| ProgramError::InvalidAccountData)?;
 
             require(
-                buyer_mint_info.state() == AccountState::Initialized,
-                ProgramError::UninitializedAccount,
+                pubkey_eq(buyer_mint_info.mint(), mint_b.key())
+                    && pubkey_eq(curve_mint_info.mint(), mint_b.key()),
+                ProgramError::IncorrectProgramId,
             )?;
 
             require(
-                buyer_mint_info.amount() >= swap_params.exact_in_amount,
-                ProgramError::InsufficientFunds,
-            )?;
-
-            let curve_account_data = load::<BondingCurve>(curve_pda)?;
-
-            let sell_result = curve_account_data
-                .apply_sell(exact_in_amount, mint_info.decimals())
-                .ok_or(AmmError::CouldNotSell)?;
-
-            sol_amount = sell_result.sol_amount;
-            fee_lamports = curve_account_data.calculate_fee(sol_amount)?;
-
-            log_value("Fee in SOL:", fee_lamports.into());
-
-            let sell_accounts = &[
-                *buyer,
-                *buyer_mint_ata,
-                *curve_pda,
-                *curve_mint_ata,
-                *curve_sol_escrow,
-                *mint_b,
-                *fee_receiver,
-            ];
-            complete_sell(
-                sell_accounts,
-                sell_result,
-                min_out_amount,
-                fee_lamports,
-                mint_info.decimals(),
-                signer,
-            )?;
-        } else {
-            // Buy tokens
-            let fee_lamports = curve_data.calculate_fee(swap_params.exact_in_amount)?;
-            let buy_amount_applied = swap_params.exact_in_amount - fee_lamports;
-
-            let buy_result = curve_data
-                .apply_buy(buy_amount_applied, mint_info.decimals())
-                .ok_or(AmmError::CouldNotBuy)?;
-
-            let buy_acconts = &[
-                *buyer,
-                *buyer_mint_ata,
-                *curve_pda,
-                *curve_mint_ata,
-                *curve_sol_escrow,
-                *mint_b,
-                *fee_receiver,
-            ];
-
-            complete_buy(
-                buy_acconts,
-                buy_result,
-                min_out_amount,
-                fee_lamports,
-                mint_info.decimals(),
-                signer,
+                pubkey_eq(buyer_mint_info.owner(), buyer.key())
+                    && pubkey_eq(curve_mint_info.owner(), curve.key()),
+                ProgramError::IncorrectAuthority,
             )?;
         }
 
-        let invariant_accounts = &[*curve_mint_ata, *curve_sol_escrow];
-        curve_data.invariant(invariant_accounts)?;
+        // TODO: check if mint_a is wsol address
 
-        Ok(())
-    } else {
-        Err(ProgramError::NotEnoughAccountKeys)
+        Ok(Self {
+            buyer,
+            buyer_mint_ata,
+            mint_a,
+            mint_b,
+            config,
+            curve,
+            curve_sol_escrow,
+            curve_mint_ata,
+            fee_receiver,
+            system_program,
+            token_program,
+            transfer_hook_accounts,
+            sol_escrow_bump,
+        })
     }
 }
 
-pub fn complete_sell(
-    accounts: &[AccountInfo],
-    sell_result: SellResult,
-    min_out_amount: u64,
-    fee_lamports: u64,
-    decimals: u8,
-    seeds: Signer,
-) -> ProgramResult {
-    if let [buyer, buyer_mint_ata, curve_pda, curve_mint_ata, curve_sol_ata, mint, fee_receiver] =
-        accounts
-    {
+/// Amounts moved by a swap against one curve and the curve reserves it left behind, written as
+/// the return data of `Swap`
+///
+/// - `amount_in`: tokens sold transfer fee included, or lamports spent on a buy fee included
+/// - `amount_out`: lamports paid out by the curve before the fee, or tokens received after the
+///   transfer fee
+/// - `fee_lamports`: fee sent to the fee receiver
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+pub struct SwapResult {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee_lamports: u64,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+}
+
+impl SwapResult {
+    pub const SIZE: usize = core::mem::size_of::<SwapResult>();
+
+    fn set_reserves(&mut self, curve: &BondingCurve) {
+        self.virtual_sol_reserves = curve.virtual_sol_reserves;
+        self.virtual_token_reserves = curve.virtual_token_reserves;
+        self.real_sol_reserves = curve.real_sol_reserves;
+        self.real_token_reserves = curve.real_token_reserves;
+    }
+}
+
+pub fn process_swap(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResult {
+    msg!("AMM INSTRUCTION: SWAP");
+    let mut ctx = SwapAccounts::try_from(accounts)?;
+    let (params_data, allowlist_data) = ix_data
+        .split_at_checked(SwapParams::len_in(ix_data))
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let SwapParams {
+        base_in,
+        padding: _,
+        exact_in_amount,
+        min_out_amount,
+        deadline_slot,
+        limit_price_lamports,
+    } = SwapParams::try_from(params_data)?;
+
+    require(
+        deadline_slot == 0 || (Clock::get()?).slot <= deadline_slot,
+        AmmError::DeadlineExceeded.into(),
+    )?;
+
+    let allowlist = load_read_only::<BondingCurve>(ctx.curve, &crate::ID)?.allowlist;
+
+    // Only buys are gated, holders can always sell
+    let allowlist_claim = if base_in == 0 && allowlist.is_active((Clock::get()?).slot) {
+        require(!allowlist_data.is_empty(), AmmError::NotAllowlisted.into())?;
+
+        let AllowlistProof {
+            max_buy_lamports,
+            proof,
+        } = AllowlistProof::try_from(allowlist_data)?;
+
         require(
-            sell_result.sol_amount >= min_out_amount,
-            AmmError::SlippageExceeded.into(),
+            allowlist.verify(ctx.buyer.key(), max_buy_lamports, proof),
+            AmmError::NotAllowlisted.into(),
         )?;
 
-        TransferChecked {
-            amount: sell_result.token_amount,
-            authority: buyer,
-            decimals,
-            from: buyer_mint_ata,
-            to: curve_mint_ata,
-            mint,
-            token_program: &TOKEN_PROGRMA_ID,
-        }
-        .invoke()?;
+        if max_buy_lamports > 0 {
+            let [claim, transfer_hook_accounts @ ..] = ctx.transfer_hook_accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            ctx.transfer_hook_accounts = transfer_hook_accounts;
 
-        FreezeAccount {
-            account: curve_mint_ata,
-            freeze_authority: curve_pda,
-            mint,
-            token_program: &TOKEN_PROGRMA_ID,
+            Some((claim, max_buy_lamports))
+        } else {
+            None
         }
-        .invoke_signed(&[seeds.clone()])?;
+    } else {
+        None
+    };
 
-        // Sending SOL from buyer to the curve_sol_escrow
-        SendSol {
-            from: curve_sol_ata,
-            lamports: sell_result.sol_amount,
-            to: buyer,
-        }
-        .invoke_signed(&[seeds])?;
+    let cooldown = take_trade_cooldown(&mut ctx, base_in)?;
 
-        // Send Fee to the fee_receiver
-        SendSol {
-            from: buyer,
-            lamports: fee_lamports,
-            to: fee_receiver,
-        }
-        .invoke()?;
+    let swap_result = execute_swap(
+        &ctx,
+        base_in,
+        exact_in_amount,
+        min_out_amount,
+        limit_price_lamports,
+        None,
+    )?;
 
-        Ok(())
-    } else {
-        Err(ProgramError::NotEnoughAccountKeys)
+    if let Some((claim, max_buy_lamports)) = allowlist_claim {
+        record_allowlist_buy(&ctx, claim, swap_result.amount_in, max_buy_lamports)?;
+    }
+
+    if let (0, Some(cooldown)) = (base_in, cooldown) {
+        record_cooldown_buy(&ctx, cooldown)?;
     }
+
+    set_return_data(bytemuck::bytes_of(&swap_result));
+
+    Ok(())
 }
 
-pub fn complete_buy(
-    accounts: &[AccountInfo],
-    buy_result: BuyResult,
-    min_out_amount: u64,
-    fee_lamports: u64,
-    decimals: u8,
-    seeds: Signer,
+/// Adds `lamports` to what the buyer spent on the curve during the allowlist phase, creating their
+/// claim PDA on their first buy
+fn record_allowlist_buy(
+    ctx: &SwapAccounts,
+    claim: &AccountInfo,
+    lamports: u64,
+    max_buy_lamports: u64,
 ) -> ProgramResult {
-    if let [buyer, buyer_mint_ata, curve_pda, curve_mint_ata, curve_sol_ata, mint, fee_receiver] =
-        accounts
-    {
-        require(
-            buy_result.token_amount >= min_out_amount,
-            AmmError::SlippageExceeded.into(),
-        )?;
-
-        TransferChecked {
-            amount: buy_result.token_amount,
-            authority: curve_pda,
-            decimals,
-            from: curve_mint_ata,
-            to: buyer_mint_ata,
-            mint,
-            token_program: &TOKEN_PROGRMA_ID,
+    let mint: &Pubkey = ctx.mint_b.key();
+    require_writable(claim)?;
+    let claim_bump = AllowlistClaim::check_id(claim, mint, ctx.buyer.key())?;
+
+    if claim.data_is_empty() {
+        let bump = [claim_bump];
+        let claim_seeds = AllowlistClaim::get_signer_seeds(mint, ctx.buyer.key(), &bump);
+
+        CreateAccount {
+            from: ctx.buyer,
+            to: claim,
+            lamports: (Rent::get()?).minimum_balance(AllowlistClaim::SIZE),
+            space: AllowlistClaim::SIZE as u64,
+            owner: &crate::ID,
         }
-        .invoke_signed(&[seeds.clone()])?;
+        .invoke_signed(&[Signer::from(&claim_seeds)])?;
 
-        FreezeAccount {
-            account: curve_mint_ata,
-            freeze_authority: curve_pda,
-            mint,
-            token_program: &TOKEN_PROGRMA_ID,
-        }
-        .invoke_signed(&[seeds])?;
+        AllowlistClaim::init(claim, claim_bump, mint, ctx.buyer.key())?;
+    }
 
-        // Sending SOL from buyer to the curve_sol_escrow
-        SendSol {
-            from: buyer,
-            lamports: buy_result.sol_amount,
-            to: curve_sol_ata,
-        }
-        .invoke()?;
+    let claim_data = load::<AllowlistClaim>(claim, &crate::ID)?;
+
+    claim_data.bought_lamports = checked_add(claim_data.bought_lamports, lamports)?;
+
+    require(
+        claim_data.bought_lamports <= max_buy_lamports,
+        AmmError::AllowlistLimitExceeded.into(),
+    )
+}
+
+/// Takes the buyer's `TradeCooldown` PDA off the remaining accounts when the config has a sell
+/// cooldown, a sell is rejected until the cooldown since the buyer's last buy on the curve is over
+pub fn take_trade_cooldown<'a>(
+    ctx: &mut SwapAccounts<'a>,
+    base_in: u8,
+) -> Result<Option<&'a AccountInfo>, ProgramError> {
+    let sell_cooldown_slots =
+        load_read_only::<GlobalConfig>(ctx.config, &crate::ID)?.sell_cooldown_slots;
+
+    if sell_cooldown_slots == 0 {
+        return Ok(None);
+    }
+
+    let [cooldown, transfer_hook_accounts @ ..] = ctx.transfer_hook_accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    ctx.transfer_hook_accounts = transfer_hook_accounts;
+
+    TradeCooldown::check_id(cooldown, ctx.mint_b.key(), ctx.buyer.key())?;
+
+    if base_in == 1 {
+        check_sell_cooldown(cooldown, sell_cooldown_slots)?;
+    }
+
+    Ok(Some(cooldown))
+}
+
+/// Rejects a sell until `sell_cooldown_slots` passed since the last buy recorded in `cooldown`
+pub fn check_sell_cooldown(cooldown: &AccountInfo, sell_cooldown_slots: u32) -> ProgramResult {
+    // A buyer without a cooldown PDA never bought while the cooldown was on
+    if cooldown.data_is_empty() {
+        return Ok(());
+    }
+
+    let cooldown_data = load_read_only::<TradeCooldown>(cooldown, &crate::ID)?;
 
-        // Send Fee to the fee_receiver
-        SendSol {
-            from: buyer,
-            lamports: fee_lamports,
-            to: fee_receiver,
+    require(
+        cooldown_data.is_over((Clock::get()?).slot, sell_cooldown_slots),
+        AmmError::SellCooldownActive.into(),
+    )
+}
+
+/// Records the slot of a buy in the buyer's cooldown PDA, creating it on their first buy
+pub fn record_cooldown_buy(ctx: &SwapAccounts, cooldown: &AccountInfo) -> ProgramResult {
+    let mint: &Pubkey = ctx.mint_b.key();
+    require_writable(cooldown)?;
+    let cooldown_bump = TradeCooldown::check_id(cooldown, mint, ctx.buyer.key())?;
+
+    if cooldown.data_is_empty() {
+        let bump = [cooldown_bump];
+        let cooldown_seeds = TradeCooldown::get_signer_seeds(mint, ctx.buyer.key(), &bump);
+
+        CreateAccount {
+            from: ctx.buyer,
+            to: cooldown,
+            lamports: (Rent::get()?).minimum_balance(TradeCooldown::SIZE),
+            space: TradeCooldown::SIZE as u64,
+            owner: &crate::ID,
         }
-        .invoke()?;
+        .invoke_signed(&[Signer::from(&cooldown_seeds)])?;
 
-        Ok(())
-    } else {
-        Err(ProgramError::NotEnoughAccountKeys)
+        TradeCooldown::init(cooldown, cooldown_bump, mint, ctx.buyer.key())?;
     }
+
+    load::<TradeCooldown>(cooldown, &crate::ID)?.last_buy_slot = (Clock::get()?).slot;
+
+    Ok(())
 }
 
-pub fn validate(
-    accounts: &[AccountInfo],
-    ix_data: &[u8],
-) -> Result<(BondingCurve, SwapParams), ProgramError> {
-    if let [buyer, buyer_mint_ata, _mint_a, mint_b, config, curve_pda, curve_sol_escrow, curve_mint_ata, fee_receiver, _, _] =
-        accounts
-    {
-        require(buyer.is_signer(), ProgramError::MissingRequiredSignature)?;
+/// Runs a buy (`base_in` 0) or a sell (`base_in` 1) against the curve of `ctx` and checks the
+/// curve invariant afterwards
+///
+/// The fee follows the curve's launch-phase schedule unless `fee_bps` is set. Trades moving the
+/// spot price beyond the config's `max_price_impact_bps` are rejected. `min_out_amount` and
+/// `limit_price_lamports`, 0 for no limit, are checked before any CPI.
+pub fn execute_swap(
+    ctx: &SwapAccounts,
+    base_in: u8,
+    exact_in_amount: u64,
+    min_out_amount: u64,
+    limit_price_lamports: u64,
+    fee_bps: Option<u16>,
+) -> Result<SwapResult, ProgramError> {
+    let curve_data = load::<BondingCurve>(ctx.curve, &crate::ID)?;
+
+    curve_data.require_tradable((Clock::get()?).slot)?;
+
+    if base_in == 0 {
+        let required_lamports = (Rent::get()?).minimum_balance(0);
 
         require(
-            curve_pda.data_len() == BondingCurve::CURVE_SIZE,
-            ProgramError::InvalidAccountData,
+            ctx.buyer.lamports() >= checked_add(required_lamports, exact_in_amount)?,
+            ProgramError::InsufficientFunds,
         )?;
+    }
 
-        BondingCurve::check_id(curve_pda, *mint_b.key())?;
+    let curve_bump = [curve_data.bump];
+    let signer_seeds = BondingCurve::get_signer_seeds(ctx.mint_b.key(), &curve_bump);
+    let signer = Signer::from(&signer_seeds);
+    let frozen_vault = curve_data.is_frozen_vault();
+
+    let decimals = Mint::from_account_info(ctx.mint_b)
+        .map_err(|_| ProgramError::InvalidAccountData)?
+        .decimals();
+    let transfer_fee = TransferFee::from_mint(ctx.mint_b)?;
+    let max_price_impact_bps =
+        load_read_only::<GlobalConfig>(ctx.config, &crate::ID)?.max_price_impact_bps;
+
+    let mut swap_result = if base_in == 1 {
+        // Sell Tokens
+        {
+            let buyer_mint_info = TokenAccount::from_account_info(ctx.buyer_mint_ata)
+                .map_err(|_| ProgramError::InvalidAccountData)?;
 
-        GlobalConfig::check_id(config)?;
+            require(
+                buyer_mint_info.state() == AccountState::Initialized,
+                ProgramError::UninitializedAccount,
+            )?;
 
-        let curve_data = load::<BondingCurve>(curve_pda)?;
+            require(
+                buyer_mint_info.amount() >= exact_in_amount,
+                ProgramError::InsufficientFunds,
+            )?;
+        }
 
-        require(curve_data.complete == 0, AmmError::CurveComplete.into())?;
-        require(curve_data.is_started(), AmmError::CurveNotStarted.into())?;
+        // The curve is only credited with what reaches its ATA, the rest is withheld there
+        let token_fee = transfer_fee
+            .calculate(exact_in_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
 
-        let global_config = load_read_only::<GlobalConfig>(config)?;
+        let sell_result =
+            curve_data.apply_sell(exact_in_amount - token_fee, decimals, max_price_impact_bps)?;
 
-        require(
-            pubkey_eq(fee_receiver.key(), &global_config.fee_receiver),
-            ProgramError::IncorrectProgramId,
-        )?;
+        let fee_lamports = curve_data.calculate_fee_with(sell_result.sol_amount, fee_bps)?;
+
+        log_value("Fee in SOL:", fee_lamports.into());
+
+        let escrow_bump = [ctx.sol_escrow_bump];
+        let escrow_seeds =
+            BondingCurve::get_sol_escrow_signer_seeds(ctx.mint_b.key(), &escrow_bump);
+
+        let swap_result = SwapResult {
+            amount_in: exact_in_amount,
+            amount_out: sell_result.sol_amount,
+            fee_lamports,
+            ..Zeroable::zeroed()
+        };
 
         require(
-            pubkey_eq(mint_b.key(), &curve_data.mint),
-            ProgramError::IncorrectProgramId,
+            sell_result.sol_amount >= min_out_amount,
+            AmmError::SlippageExceeded.into(),
         )?;
-        let buyer_mint_info = TokenAccount::from_account_info(buyer_mint_ata)
-            .map_err(|_| ProgramError::InvalidAccountData)?;
-        let curve_mint_info = TokenAccount::from_account_info(curve_mint_ata)
-            .map_err(|_| ProgramError::InvalidAccountData)?;
 
-        require(
-            pubkey_eq(buyer_mint_info.mint(), mint_b.key())
-                && pubkey_eq(curve_mint_info.mint(), mint_b.key()),
-            ProgramError::IncorrectProgramId,
+        // The seller pays the fee out of what the curve sends them
+        require_limit_price(
+            false,
+            checked_sub(sell_result.sol_amount, fee_lamports)?,
+            exact_in_amount,
+            decimals,
+            limit_price_lamports,
         )?;
 
-        require(
-            pubkey_eq(buyer_mint_info.owner(), buyer.key())
-                && pubkey_eq(curve_mint_info.owner(), curve_pda.key())
-                && pubkey_eq(curve_sol_escrow.owner(), curve_pda.key()),
-            ProgramError::IncorrectAuthority,
+        if frozen_vault {
+            thaw_vault(ctx, &signer)?;
+        }
+
+        complete_sell(
+            ctx,
+            sell_result,
+            token_fee,
+            fee_lamports,
+            decimals,
+            Signer::from(&escrow_seeds),
         )?;
 
-        // TODO: check if mint_a is wsol address
+        swap_result
+    } else {
+        // Buy tokens
+        let fee_lamports = curve_data.calculate_fee_with(exact_in_amount, fee_bps)?;
+        let buy_amount_applied = exact_in_amount - fee_lamports;
+
+        let buy_result =
+            curve_data.apply_buy(buy_amount_applied, decimals, max_price_impact_bps)?;
+
+        // The buyer receives the tokens left after the transfer fee withheld in their account
+        let token_fee = transfer_fee
+            .calculate(buy_result.token_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        if let Some(max_wallet_amount) = curve_data.max_wallet_amount()? {
+            // The cap only holds for the buyer's ATA, any other token account would start empty
+            require_pda(
+                ctx.buyer_mint_ata,
+                &[ctx.buyer.key(), ctx.token_program.key(), ctx.mint_b.key()],
+                &ASSOCIATED_TOKEN_PROGRAM_ID,
+            )?;
 
-        let ix_params = bytemuck::try_from_bytes::<SwapParams>(ix_data)
-            .map_err(|_| ProgramError::InvalidInstructionData)?;
-        let required_lamports = (Rent::get()?).minimum_balance(0);
+            let buyer_balance = TokenAccount::from_account_info(ctx.buyer_mint_ata)
+                .map_err(|_| ProgramError::InvalidAccountData)?
+                .amount();
+
+            require(
+                buyer_balance.saturating_add(buy_result.token_amount - token_fee)
+                    <= max_wallet_amount,
+                AmmError::MaxWalletExceeded.into(),
+            )?;
+        }
+
+        let swap_result = SwapResult {
+            amount_in: checked_add(buy_result.sol_amount, fee_lamports)?,
+            amount_out: buy_result.token_amount - token_fee,
+            fee_lamports,
+            ..Zeroable::zeroed()
+        };
 
         require(
-            buyer.lamports()
-                >= required_lamports
-                    .checked_add(ix_params.exact_in_amount)
-                    .unwrap(),
-            ProgramError::InsufficientFunds,
-        )?;
-        require(
-            ix_params.exact_in_amount > 0,
-            ProgramError::InvalidInstructionData,
+            swap_result.amount_out >= min_out_amount,
+            AmmError::SlippageExceeded.into(),
         )?;
 
-        require(
-            ix_params.base_in == 1 && ix_params.base_in == 0,
-            ProgramError::InvalidInstructionData,
+        require_limit_price(
+            true,
+            swap_result.amount_in,
+            swap_result.amount_out,
+            decimals,
+            limit_price_lamports,
         )?;
 
-        Ok((*curve_data, *ix_params))
+        if frozen_vault {
+            thaw_vault(ctx, &signer)?;
+        }
+
+        complete_buy(ctx, buy_result, fee_lamports, decimals, &signer)?;
+
+        swap_result
+    };
+
+    if frozen_vault {
+        FreezeAccount {
+            account: ctx.curve_mint_ata,
+            freeze_authority: ctx.curve,
+            mint: ctx.mint_b,
+            token_program: &TOKEN_PROGRMA_ID,
+        }
+        .invoke_signed(&[signer])?;
+    }
+
+    let invariant_accounts = &[*ctx.curve_mint_ata, *ctx.curve_sol_escrow];
+    curve_data.invariant(invariant_accounts)?;
+
+    swap_result.set_reserves(curve_data);
+
+    Ok(swap_result)
+}
+
+/// Checks the price of a swap against `limit_price_lamports`, in lamports per whole token, 0 for
+/// no limit
+///
+/// `lamports` is what a buy costs or what a sell leaves the seller with, fees included.
+fn require_limit_price(
+    is_buy: bool,
+    lamports: u64,
+    token_amount: u64,
+    decimals: u8,
+    limit_price_lamports: u64,
+) -> ProgramResult {
+    if limit_price_lamports == 0 {
+        return Ok(());
+    }
+
+    // lamports / (token_amount / 10^decimals) against the limit, compared by cross multiplying
+    let price = (lamports as u128) * 10u128.pow(decimals as u32);
+    let limit = (limit_price_lamports as u128) * (token_amount as u128);
+
+    let within_limit = if is_buy {
+        price <= limit
     } else {
-        Err(ProgramError::NotEnoughAccountKeys)
+        price >= limit
+    };
+
+    require(within_limit, AmmError::PriceLimitExceeded.into())
+}
+
+/// Thaws the frozen curve ATA for the duration of a swap
+fn thaw_vault(ctx: &SwapAccounts, curve_signer: &Signer) -> ProgramResult {
+    ThawAccount {
+        account: ctx.curve_mint_ata,
+        freeze_authority: ctx.curve,
+        mint: ctx.mint_b,
+        token_program: &TOKEN_PROGRMA_ID,
+    }
+    .invoke_signed(core::slice::from_ref(curve_signer))
+}
+
+/// Settles a sell, `token_fee` is the transfer fee withheld on top of the tokens the curve takes in
+pub fn complete_sell(
+    ctx: &SwapAccounts,
+    sell_result: SellResult,
+    token_fee: u64,
+    fee_lamports: u64,
+    decimals: u8,
+    sol_escrow_seeds: Signer,
+) -> ProgramResult {
+    TransferChecked {
+        token_program: &TOKEN_PROGRMA_ID,
+        amount: checked_add(sell_result.token_amount, token_fee)?,
+        authority: ctx.buyer,
+        decimals,
+        from: ctx.buyer_mint_ata,
+        to: ctx.curve_mint_ata,
+        mint: ctx.mint_b,
+        hook_accounts: ctx.transfer_hook_accounts,
+    }
+    .invoke()?;
+
+    // Sending SOL from the curve_sol_escrow to the seller
+    SendSol {
+        from: ctx.curve_sol_escrow,
+        lamports: sell_result.sol_amount,
+        to: ctx.buyer,
+    }
+    .invoke_signed(&[sol_escrow_seeds])?;
+
+    // Send Fee to the fee_receiver
+    SendSol {
+        from: ctx.buyer,
+        lamports: fee_lamports,
+        to: ctx.fee_receiver,
     }
+    .invoke()?;
+
+    Ok(())
+}
+
+/// Settles a buy, the transfer fee is withheld by the mint from the tokens sent to the buyer
+pub fn complete_buy(
+    ctx: &SwapAccounts,
+    buy_result: BuyResult,
+    fee_lamports: u64,
+    decimals: u8,
+    seeds: &Signer,
+) -> ProgramResult {
+    TransferChecked {
+        token_program: &TOKEN_PROGRMA_ID,
+        amount: buy_result.token_amount,
+        authority: ctx.curve,
+        decimals,
+        from: ctx.curve_mint_ata,
+        to: ctx.buyer_mint_ata,
+        mint: ctx.mint_b,
+        hook_accounts: ctx.transfer_hook_accounts,
+    }
+    .invoke_signed(core::slice::from_ref(seeds))?;
+
+    // Sending SOL from buyer to the curve_sol_escrow
+    SendSol {
+        from: ctx.buyer,
+        lamports: buy_result.sol_amount,
+        to: ctx.curve_sol_escrow,
+    }
+    .invoke()?;
+
+    // Send Fee to the fee_receiver
+    SendSol {
+        from: ctx.buyer,
+        lamports: fee_lamports,
+        to: ctx.fee_receiver,
+    }
+    .invoke()?;
+
+    Ok(())
 }
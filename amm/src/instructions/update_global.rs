@@ -0,0 +1,60 @@
+use crate::{
+    load_read_only, require, require_signer, require_writable,
+    states::global_config::{GlobalConfig, GlobalSettingsInput},
+};
+use pinocchio::{
+    account_info::AccountInfo, log::sol_log, program_error::ProgramError, pubkey::pubkey_eq,
+    ProgramResult,
+};
+
+/// Accounts of `UpdateGlobal`
+///
+/// - `admin`: global config admin, a multisig vault signs through an executed proposal
+/// - `config`: global config PDA
+pub struct UpdateGlobalAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdateGlobalAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        require_signer(admin)?;
+        require_writable(config)?;
+
+        GlobalConfig::check_id(config)?;
+
+        let global_config = load_read_only::<GlobalConfig>(config, &crate::ID)?;
+
+        require(
+            pubkey_eq(admin.key(), &global_config.admin),
+            ProgramError::IncorrectAuthority,
+        )?;
+
+        Ok(Self { admin, config })
+    }
+}
+
+/// Replaces every setting of the global config, the admin included
+pub fn process_update_global(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResult {
+    sol_log("AMM Instruction: Update Global");
+    let ctx = UpdateGlobalAccounts::try_from(accounts)?;
+
+    require(
+        ix_data.len() == GlobalSettingsInput::SIZE,
+        ProgramError::InvalidInstructionData,
+    )?;
+
+    let params = bytemuck::try_pod_read_unaligned::<GlobalSettingsInput>(ix_data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    GlobalConfig::validate_settings(&params)?;
+    GlobalConfig::update_global(params, ctx.config)?;
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use crate::{
+    instructions::create_treasury::TreasuryRecipientsParams,
+    load, load_read_only, require, require_signer, require_writable,
+    states::{global_config::GlobalConfig, treasury::Treasury},
+};
+use pinocchio::{
+    account_info::AccountInfo, log::sol_log, program_error::ProgramError, pubkey::pubkey_eq,
+    ProgramResult,
+};
+
+/// Accounts of `UpdateTreasury`
+///
+/// - `admin`: global config admin
+/// - `treasury`: treasury PDA
+pub struct UpdateTreasuryAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub treasury: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdateTreasuryAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config, treasury] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        require_signer(admin)?;
+        require_writable(treasury)?;
+
+        GlobalConfig::check_id(config)?;
+
+        let global_config = load_read_only::<GlobalConfig>(config, &crate::ID)?;
+
+        require(
+            pubkey_eq(admin.key(), &global_config.admin),
+            ProgramError::IncorrectAuthority,
+        )?;
+
+        Ok(Self {
+            admin,
+            config,
+            treasury,
+        })
+    }
+}
+
+/// Replaces the recipients treasury withdrawals are split between
+pub fn process_update_treasury(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResult {
+    sol_log("AMM Instruction: Update Treasury");
+    let ctx = UpdateTreasuryAccounts::try_from(accounts)?;
+    let params = TreasuryRecipientsParams::try_from(ix_data)?;
+
+    // The treasury is a singleton, its owner and discriminator identify it
+    load::<Treasury>(ctx.treasury, &crate::ID)?.set_recipients(params.recipients())
+}
@@ -0,0 +1,296 @@
+use crate::{
+    checked_add, load, load_read_only, require, require_signer, require_writable,
+    states::{
+        global_config::GlobalConfig,
+        treasury::{Treasury, TreasuryTokenRecord},
+    },
+    token::TransferChecked,
+    transfer_lamports, AmmError,
+};
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Signer,
+    log::sol_log,
+    program_error::ProgramError,
+    pubkey::{pubkey_eq, Pubkey},
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+use pinocchio_token_2022::{
+    state::{Mint, TokenAccount},
+    ID as TOKEN_PROGRAM_ID,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct WithdrawTreasuryParams {
+    /// `WithdrawTreasuryParams::SOL` or `WithdrawTreasuryParams::TOKEN`
+    pub asset: u8,
+    pub padding: [u8; 7],
+    /// Amount to withdraw, 0 withdraws everything the treasury can spare
+    pub amount: u64,
+}
+
+impl WithdrawTreasuryParams {
+    pub const SIZE: usize = core::mem::size_of::<WithdrawTreasuryParams>();
+
+    /// Withdraws lamports above the treasury's rent exemption
+    pub const SOL: u8 = 0;
+    /// Withdraws tokens from a token account of the treasury, the vested protocol allocations
+    pub const TOKEN: u8 = 1;
+}
+
+impl TryFrom<&[u8]> for WithdrawTreasuryParams {
+    type Error = ProgramError;
+
+    fn try_from(ix_data: &[u8]) -> Result<Self, Self::Error> {
+        require(
+            ix_data.len() == WithdrawTreasuryParams::SIZE,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        let params = bytemuck::try_pod_read_unaligned::<WithdrawTreasuryParams>(ix_data)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        require(
+            params.asset == WithdrawTreasuryParams::SOL
+                || params.asset == WithdrawTreasuryParams::TOKEN,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        Ok(params)
+    }
+}
+
+/// Accounts of `WithdrawTreasury`
+///
+/// - `admin`: global config admin, pays for the token record on the first withdrawal of a mint
+/// - `treasury`: treasury PDA
+/// - `remaining`: for SOL, the wallets of the treasury recipients in order. For tokens, the mint,
+///   the treasury token account, the token program, the `TreasuryTokenRecord` PDA of the mint and
+///   the system program, then the token accounts of the recipients in order and the transfer hook
+///   accounts of the mint
+pub struct WithdrawTreasuryAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub treasury: &'a AccountInfo,
+    pub remaining: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawTreasuryAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config, treasury, remaining @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        require_signer(admin)?;
+        require_writable(treasury)?;
+
+        GlobalConfig::check_id(config)?;
+
+        let global_config = load_read_only::<GlobalConfig>(config, &crate::ID)?;
+
+        require(
+            pubkey_eq(admin.key(), &global_config.admin),
+            ProgramError::IncorrectAuthority,
+        )?;
+
+        Ok(Self {
+            admin,
+            config,
+            treasury,
+            remaining,
+        })
+    }
+}
+
+/// Splits a withdrawal from the treasury between its recipients by weight
+///
+/// The lamports withdrawn add to the treasury's `total_withdrawn_lamports`, the tokens to the
+/// `total_withdrawn` of the mint's `TreasuryTokenRecord`.
+pub fn process_withdraw_treasury(accounts: &[AccountInfo], ix_data: &[u8]) -> ProgramResult {
+    sol_log("AMM Instruction: Withdraw Treasury");
+    let ctx = WithdrawTreasuryAccounts::try_from(accounts)?;
+    let WithdrawTreasuryParams {
+        asset,
+        padding: _,
+        amount,
+    } = WithdrawTreasuryParams::try_from(ix_data)?;
+
+    let treasury_data = load::<Treasury>(ctx.treasury, &crate::ID)?;
+
+    if asset == WithdrawTreasuryParams::SOL {
+        withdraw_lamports(&ctx, treasury_data, amount)
+    } else {
+        withdraw_tokens(&ctx, treasury_data, amount)
+    }
+}
+
+fn withdraw_lamports(
+    ctx: &WithdrawTreasuryAccounts,
+    treasury_data: &mut Treasury,
+    amount: u64,
+) -> ProgramResult {
+    let treasury = *treasury_data;
+    let recipients = treasury.recipients();
+
+    require(
+        ctx.remaining.len() == recipients.len(),
+        ProgramError::NotEnoughAccountKeys,
+    )?;
+
+    let available = ctx
+        .treasury
+        .lamports()
+        .saturating_sub((Rent::get()?).minimum_balance(Treasury::SIZE));
+    let amount = if amount == 0 { available } else { amount };
+
+    require(
+        amount > 0 && amount <= available,
+        ProgramError::InsufficientFunds,
+    )?;
+
+    let shares = treasury.split(amount);
+
+    for ((wallet, recipient), share) in ctx.remaining.iter().zip(recipients).zip(shares) {
+        require(
+            pubkey_eq(wallet.key(), &recipient.wallet),
+            ProgramError::InvalidArgument,
+        )?;
+        require_writable(wallet)?;
+
+        transfer_lamports(ctx.treasury, wallet, share)?;
+    }
+
+    treasury_data.total_withdrawn_lamports =
+        checked_add(treasury_data.total_withdrawn_lamports, amount)?;
+
+    Ok(())
+}
+
+fn withdraw_tokens(
+    ctx: &WithdrawTreasuryAccounts,
+    treasury_data: &Treasury,
+    amount: u64,
+) -> ProgramResult {
+    let recipients = treasury_data.recipients();
+
+    let [mint, treasury_token_account, token_program, record, _system_program, rest @ ..] =
+        ctx.remaining
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require(
+        rest.len() >= recipients.len(),
+        ProgramError::NotEnoughAccountKeys,
+    )?;
+    let (recipient_token_accounts, transfer_hook_accounts) = rest.split_at(recipients.len());
+
+    require(
+        pubkey_eq(token_program.key(), &TOKEN_PROGRAM_ID),
+        ProgramError::IncorrectProgramId,
+    )?;
+    require_writable(treasury_token_account)?;
+
+    let available = {
+        let treasury_token_info = TokenAccount::from_account_info(treasury_token_account)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        require(
+            pubkey_eq(treasury_token_info.mint(), mint.key()),
+            AmmError::InvalidMint.into(),
+        )?;
+        require(
+            pubkey_eq(treasury_token_info.owner(), ctx.treasury.key()),
+            ProgramError::IncorrectAuthority,
+        )?;
+
+        treasury_token_info.amount()
+    };
+    let amount = if amount == 0 { available } else { amount };
+
+    require(
+        amount > 0 && amount <= available,
+        ProgramError::InsufficientFunds,
+    )?;
+
+    for (token_account, recipient) in recipient_token_accounts.iter().zip(recipients) {
+        let token_info = TokenAccount::from_account_info(token_account)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        require(
+            pubkey_eq(token_info.mint(), mint.key()),
+            AmmError::InvalidMint.into(),
+        )?;
+        require(
+            pubkey_eq(token_info.owner(), &recipient.wallet),
+            ProgramError::IncorrectAuthority,
+        )?;
+    }
+
+    let decimals = Mint::from_account_info(mint)
+        .map_err(|_| ProgramError::InvalidAccountData)?
+        .decimals();
+
+    let bump = [treasury_data.bump];
+    let treasury_seeds = Treasury::get_signer_seeds(&bump);
+    let shares = treasury_data.split(amount);
+
+    for (token_account, share) in recipient_token_accounts.iter().zip(shares) {
+        if share == 0 {
+            continue;
+        }
+
+        TransferChecked {
+            token_program: &TOKEN_PROGRAM_ID,
+            amount: share,
+            authority: ctx.treasury,
+            decimals,
+            from: treasury_token_account,
+            to: token_account,
+            mint,
+            hook_accounts: transfer_hook_accounts,
+        }
+        .invoke_signed(&[Signer::from(&treasury_seeds)])?;
+    }
+
+    record_token_withdrawal(ctx, record, mint.key(), amount)
+}
+
+/// Adds `amount` to the token record of `mint`, creating it on the first withdrawal of the mint
+fn record_token_withdrawal(
+    ctx: &WithdrawTreasuryAccounts,
+    record: &AccountInfo,
+    mint: &Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    require_writable(record)?;
+    let record_bump = TreasuryTokenRecord::check_id(record, mint)?;
+
+    if record.data_is_empty() {
+        let bump = [record_bump];
+        let record_seeds = TreasuryTokenRecord::get_signer_seeds(mint, &bump);
+
+        CreateAccount {
+            from: ctx.admin,
+            to: record,
+            lamports: (Rent::get()?).minimum_balance(TreasuryTokenRecord::SIZE),
+            space: TreasuryTokenRecord::SIZE as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[Signer::from(&record_seeds)])?;
+
+        TreasuryTokenRecord::init(record, record_bump, mint)?;
+    }
+
+    let record_data = load::<TreasuryTokenRecord>(record, &crate::ID)?;
+
+    record_data.total_withdrawn = checked_add(record_data.total_withdrawn, amount)?;
+
+    Ok(())
+}
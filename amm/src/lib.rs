@@ -8,9 +8,11 @@ pub use errors::*;
 mod constants;
 mod helpers;
 pub use helpers::*;
-mod instructions;
+pub mod instructions;
 pub mod processor;
 pub mod states;
+pub mod transfer_fee;
+pub mod transfer_hook;
 
 program_entrypoint!(process_instruction);
 nostd_panic_handler!();
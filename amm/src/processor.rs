@@ -1,7 +1,15 @@
 use crate::{
     instructions::{
+        approve_proposal::process_approve_proposal, claim_vested::process_claim_vested,
+        close_curve::process_close_curve, create_multisig::process_create_multisig,
+        create_proposal::process_create_proposal, create_treasury::process_create_treasury,
+        execute_proposal::process_execute_proposal, graduate::process_graduate,
+        harvest_transfer_fees::process_harvest_transfer_fees,
         init_bonding_curve::process_init_bonding_curve, init_global_config::init_global,
-        AmmInstructions,
+        quote::process_quote, refund::process_refund, route_swap::process_route_swap,
+        set_curve_paused::process_set_curve_paused, swap::process_swap,
+        update_global::process_update_global, update_treasury::process_update_treasury,
+        withdraw_treasury::process_withdraw_treasury, AmmInstructions,
     },
     require,
 };
@@ -25,10 +33,27 @@ pub fn process_instruction(
         .ok_or(ProgramError::InvalidInstructionData)?;
 
     match AmmInstructions::try_from(disc)? {
-        AmmInstructions::CreateBondingCurve => process_init_bonding_curve(program_id, accounts)?,
+        AmmInstructions::CreateBondingCurve => {
+            process_init_bonding_curve(program_id, accounts, ix)?
+        }
         AmmInstructions::CreateGlobal => init_global(program_id, accounts, ix)?,
-        AmmInstructions::Swap => {}
-        AmmInstructions::UpdateGlobal => {}
+        AmmInstructions::Swap => process_swap(accounts, ix)?,
+        AmmInstructions::UpdateGlobal => process_update_global(accounts, ix)?,
+        AmmInstructions::Graduate => process_graduate(accounts)?,
+        AmmInstructions::CloseCurve => process_close_curve(accounts)?,
+        AmmInstructions::RouteSwap => process_route_swap(accounts, ix)?,
+        AmmInstructions::Quote => process_quote(accounts, ix)?,
+        AmmInstructions::ClaimVested => process_claim_vested(accounts)?,
+        AmmInstructions::SetCurvePaused => process_set_curve_paused(accounts, ix)?,
+        AmmInstructions::HarvestTransferFees => process_harvest_transfer_fees(accounts)?,
+        AmmInstructions::CreateMultisig => process_create_multisig(accounts, ix)?,
+        AmmInstructions::CreateProposal => process_create_proposal(accounts, ix)?,
+        AmmInstructions::ApproveProposal => process_approve_proposal(accounts)?,
+        AmmInstructions::ExecuteProposal => process_execute_proposal(accounts)?,
+        AmmInstructions::CreateTreasury => process_create_treasury(accounts, ix)?,
+        AmmInstructions::UpdateTreasury => process_update_treasury(accounts, ix)?,
+        AmmInstructions::WithdrawTreasury => process_withdraw_treasury(accounts, ix)?,
+        AmmInstructions::Refund => process_refund(accounts, ix)?,
     }
 
     Ok(())
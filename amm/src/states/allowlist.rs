@@ -0,0 +1,132 @@
+use crate::{init_account, require, require_pda, AmmError, Discriminator};
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError, pubkey::Pubkey,
+    seeds,
+};
+use solana_sha256_hasher::hashv;
+
+/// Merkle allowlist gating the buys of a curve until `end_slot`
+///
+/// Leaves are `allowlist_leaf(wallet, max_buy_lamports)` and pairs are hashed in sorted order, so
+/// proofs carry no left/right flags. A zeroed root leaves the curve open to everyone.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, Debug, Default, PartialEq, Eq)]
+pub struct Allowlist {
+    pub root: [u8; 32],
+    /// First slot buys are open to every wallet
+    pub end_slot: u64,
+}
+
+/// Domain tags keeping a leaf from passing as an inner node
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Leaf of `wallet`, allowed to spend `max_buy_lamports` on the curve during the allowlist phase,
+/// 0 for no limit
+pub fn allowlist_leaf(wallet: &Pubkey, max_buy_lamports: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, wallet, &max_buy_lamports.to_le_bytes()]).to_bytes()
+}
+
+/// Parent of two nodes of the allowlist tree
+pub fn allowlist_node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+impl Allowlist {
+    pub fn is_enabled(&self) -> bool {
+        self.root != [0; 32]
+    }
+
+    /// Checks the allowlist settings of a new curve, the root and the end slot are set together
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        require(
+            self.is_enabled() == (self.end_slot > 0),
+            ProgramError::InvalidInstructionData,
+        )
+    }
+
+    /// Buys at `slot` need a proof
+    pub fn is_active(&self, slot: u64) -> bool {
+        self.is_enabled() && slot < self.end_slot
+    }
+
+    /// Checks `proof` leads from the leaf of `wallet` and `max_buy_lamports` to the root
+    pub fn verify(&self, wallet: &Pubkey, max_buy_lamports: u64, proof: &[[u8; 32]]) -> bool {
+        let node = proof
+            .iter()
+            .fold(allowlist_leaf(wallet, max_buy_lamports), |node, sibling| {
+                allowlist_node(&node, sibling)
+            });
+
+        node == self.root
+    }
+}
+
+/// Lamports an allowlisted wallet with a buy limit spent on a curve during the allowlist phase
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, Debug)]
+pub struct AllowlistClaim {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub bump: u8,
+    pub _padding: [u8; 6],
+
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+
+    pub bought_lamports: u64,
+}
+
+impl Discriminator for AllowlistClaim {
+    // First 8 bytes of sha256("account:AllowlistClaim")
+    const DISCRIMINATOR: [u8; 8] = [0x3c, 0xd2, 0xd6, 0xc0, 0x12, 0x74, 0xcb, 0x05];
+    const VERSION: u8 = 1;
+    const UNSUPPORTED_VERSION: ProgramError =
+        ProgramError::Custom(AmmError::UnsupportedLayoutVersion as u32);
+}
+
+impl AllowlistClaim {
+    pub const SIZE: usize = core::mem::size_of::<Self>();
+    pub const SEED_PREFIX: &[u8] = b"allowlist_claim";
+
+    /// Checks the account is the claim PDA of `wallet` on the curve of `mint` and returns its bump
+    pub fn check_id(
+        claim_account: &AccountInfo,
+        mint: &Pubkey,
+        wallet: &Pubkey,
+    ) -> Result<u8, ProgramError> {
+        require_pda(
+            claim_account,
+            &[Self::SEED_PREFIX, mint.as_ref(), wallet.as_ref()],
+            &crate::ID,
+        )
+    }
+
+    pub fn get_signer_seeds<'a>(
+        mint: &'a Pubkey,
+        wallet: &'a Pubkey,
+        bump: &'a [u8; 1],
+    ) -> [Seed<'a>; 4] {
+        seeds!(Self::SEED_PREFIX, mint.as_ref(), wallet.as_ref(), bump)
+    }
+
+    pub fn init(
+        claim_account: &AccountInfo,
+        bump: u8,
+        mint: &Pubkey,
+        wallet: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        let claim_data = init_account::<AllowlistClaim>(claim_account)?;
+
+        claim_data.bump = bump;
+        claim_data._padding = [0u8; 6];
+        claim_data.mint = *mint;
+        claim_data.wallet = *wallet;
+        claim_data.bought_lamports = 0;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
-    bps_mul, constants::SOLANA_DECIMALS, helpers::log_value, load, require,
-    states::global_config::GlobalConfig, AmmError,
+    bps_mul,
+    constants::SOLANA_DECIMALS,
+    helpers::log_value,
+    init_account, mul_div, require, require_pda,
+    states::{allowlist::Allowlist, crowdfund::Crowdfund, global_config::GlobalConfig},
+    AmmError, Discriminator,
 };
 use bytemuck::{Pod, Zeroable};
 use {
@@ -9,7 +13,7 @@ use {
         instruction::Seed,
         msg,
         program_error::ProgramError,
-        pubkey::{find_program_address, pubkey_eq, Pubkey},
+        pubkey::{pubkey_eq, Pubkey},
         seeds,
         sysvars::{clock::Clock, Sysvar},
     },
@@ -19,9 +23,16 @@ use {
 #[repr(C)]
 #[derive(Clone, Copy, Pod, Zeroable, Debug)]
 pub struct BondingCurve {
-    pub complete: u8,
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    /// A `CurveStatus`
+    pub status: u8,
     pub bump: u8,
-    pub _padding: [u8; 6],
+    /// One of `FROZEN_VAULT` or `UNFROZEN_VAULT`, how the curve ATA is guarded between swaps
+    pub vault_mode: u8,
+    pub _padding: [u8; 2],
+    /// Most a wallet may hold after a curve buy, in bps of `token_total_supply`, 0 for no cap
+    pub max_wallet_bps: u16,
 
     pub mint: Pubkey,
     pub creator: Pubkey,
@@ -33,6 +44,74 @@ pub struct BondingCurve {
     pub real_token_reserves: u64,
     pub token_total_supply: u64,
     pub starting_slot: u64,
+
+    /// Wallets allowed to buy before public trading
+    pub allowlist: Allowlist,
+    /// SOL target the curve has to raise by a deadline. Cleared once the target is met past the
+    /// deadline
+    pub crowdfund: Crowdfund,
+}
+
+/// Lifecycle of a curve, stored as `BondingCurve::status`
+///
+/// Scheduled -> Active <-> Paused, Active -> Completed -> Graduated -> Closed. A paused curve
+/// resumes as Scheduled when its starting slot is still ahead. A crowdfund curve that missed its
+/// target goes from Scheduled, Active or Paused to Failed.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveStatus {
+    /// Trading
+    Active = 0,
+    /// Every curve token was sold, trading stopped
+    Completed = 1,
+    /// The admin withdrew the liquidity of a completed curve, its accounts can be closed
+    Graduated = 2,
+    /// Waiting for its starting slot, turns active on the first trade after it
+    Scheduled = 3,
+    /// Trading halted by the admin
+    Paused = 4,
+    /// The curve accounts were closed
+    Closed = 5,
+    /// The crowdfund missed its target, trading stopped and holders get refunded
+    Failed = 6,
+}
+
+impl TryFrom<u8> for CurveStatus {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(CurveStatus::Active),
+            1 => Ok(CurveStatus::Completed),
+            2 => Ok(CurveStatus::Graduated),
+            3 => Ok(CurveStatus::Scheduled),
+            4 => Ok(CurveStatus::Paused),
+            5 => Ok(CurveStatus::Closed),
+            6 => Ok(CurveStatus::Failed),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+impl CurveStatus {
+    pub fn can_transition_to(self, next: CurveStatus) -> bool {
+        use CurveStatus::*;
+
+        matches!(
+            (self, next),
+            (Scheduled, Active)
+                | (Scheduled, Paused)
+                | (Active, Paused)
+                | (Paused, Active)
+                | (Paused, Scheduled)
+                | (Active, Completed)
+                | (Completed, Graduated)
+                | (Graduated, Closed)
+                | (Scheduled, Failed)
+                | (Active, Failed)
+                | (Paused, Failed)
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -47,44 +126,88 @@ pub struct SellResult {
     pub sol_amount: u64,
 }
 
+impl Discriminator for BondingCurve {
+    // First 8 bytes of sha256("account:BondingCurve")
+    const DISCRIMINATOR: [u8; 8] = [0x17, 0xb7, 0xf8, 0x37, 0x60, 0xd8, 0xac, 0x60];
+    const VERSION: u8 = 1;
+    const UNSUPPORTED_VERSION: ProgramError =
+        ProgramError::Custom(AmmError::UnsupportedLayoutVersion as u32);
+}
+
 impl BondingCurve {
     pub const CURVE_SIZE: usize = core::mem::size_of::<Self>();
     pub const SEED_PREFIX: &[u8] = b"bonding_curve";
     pub const SOL_ESCROW_SEED_PREFIX: &[u8] = b"sol_escrow";
     pub const MINT_SEED_PREFIX: &[u8] = b"curve_mint";
 
-    pub fn check_id(curve_account: &AccountInfo, mint: Pubkey) -> Result<(), ProgramError> {
-        let curve_seeds: &[&[u8]] = &[BondingCurve::SEED_PREFIX, mint.as_ref()];
-
-        let (expected_curve_pda, _) = find_program_address(curve_seeds, &crate::ID);
+    /// The curve ATA is frozen between swaps, nobody can move tokens in or out of it
+    pub const FROZEN_VAULT: u8 = 0;
+    /// The curve ATA stays thawed, only the curve PDA can move tokens out of it but anyone can
+    /// send tokens to it, so its balance may exceed the reserves
+    pub const UNFROZEN_VAULT: u8 = 1;
+
+    /// Checks the account is the curve PDA of `mint` and returns its bump
+    pub fn check_id(curve_account: &AccountInfo, mint: Pubkey) -> Result<u8, ProgramError> {
+        require_pda(
+            curve_account,
+            &[Self::SEED_PREFIX, mint.as_ref()],
+            &crate::ID,
+        )
+    }
 
-        require(
-            pubkey_eq(curve_account.key(), &expected_curve_pda),
-            ProgramError::IncorrectProgramId,
-        )?;
+    /// Checks the account is the sol escrow PDA of `mint` and returns its bump
+    pub fn check_sol_escrow_id(
+        sol_escrow_account: &AccountInfo,
+        mint: &Pubkey,
+    ) -> Result<u8, ProgramError> {
+        require_pda(
+            sol_escrow_account,
+            &[Self::SOL_ESCROW_SEED_PREFIX, mint.as_ref()],
+            &crate::ID,
+        )
+    }
 
-        Ok(())
+    pub fn get_signer_seeds<'a>(mint: &'a Pubkey, bump: &'a [u8; 1]) -> [Seed<'a>; 3] {
+        seeds!(Self::SEED_PREFIX, mint.as_ref(), bump)
     }
 
-    pub fn get_signer_seeds<'a>(mint: &'a Pubkey) -> [Seed<'a>; 2] {
-        seeds!(Self::SEED_PREFIX, mint.as_ref())
+    pub fn get_sol_escrow_signer_seeds<'a>(mint: &'a Pubkey, bump: &'a [u8; 1]) -> [Seed<'a>; 3] {
+        seeds!(Self::SOL_ESCROW_SEED_PREFIX, mint.as_ref(), bump)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn init(
         bump: u8,
         configs: GlobalConfig,
         curve_account: &AccountInfo,
         creator_key: &Pubkey,
         mint: &Pubkey,
+        vault_mode: u8,
+        starting_slot: u64,
+        max_wallet_bps: u16,
+        allowlist: Allowlist,
+        crowdfund: Crowdfund,
     ) -> Result<(), ProgramError> {
-        let curve_data = load::<BondingCurve>(curve_account)?;
+        let curve_data = init_account::<BondingCurve>(curve_account)?;
 
         let slot = (Clock::get()?).slot;
+        let starting_slot = starting_slot.max(slot);
+
+        require(
+            !crowdfund.is_enabled() || crowdfund.deadline_slot > starting_slot,
+            ProgramError::InvalidInstructionData,
+        )?;
 
-        curve_data._padding = [0u8; 6];
+        curve_data._padding = [0u8; 2];
+        curve_data.max_wallet_bps = max_wallet_bps;
         curve_data.bump = bump;
-        curve_data.complete = 0;
-        curve_data.starting_slot = slot;
+        curve_data.vault_mode = vault_mode;
+        curve_data.status = if starting_slot > slot {
+            CurveStatus::Scheduled
+        } else {
+            CurveStatus::Active
+        } as u8;
+        curve_data.starting_slot = starting_slot;
         curve_data.creator = *creator_key;
         curve_data.initial_real_token_reserves = configs.initial_real_token_reserves;
         curve_data.mint = *mint;
@@ -93,14 +216,135 @@ impl BondingCurve {
         curve_data.virtual_sol_reserves = configs.initial_virtual_sol_reserves;
         curve_data.virtual_token_reserves = configs.initial_virtual_token_reserves;
         curve_data.token_total_supply = configs.token_total_supply;
+        curve_data.allowlist = allowlist;
+        curve_data.crowdfund = crowdfund;
+
+        Ok(())
+    }
+
+    pub fn is_frozen_vault(&self) -> bool {
+        self.vault_mode == Self::FROZEN_VAULT
+    }
+
+    pub fn status(&self) -> Result<CurveStatus, ProgramError> {
+        CurveStatus::try_from(self.status)
+    }
+
+    /// Moves the curve to `next`, every lifecycle change goes through here
+    pub fn transition(&mut self, next: CurveStatus) -> Result<(), ProgramError> {
+        if !self.status()?.can_transition_to(next) {
+            let error = match next {
+                CurveStatus::Completed => AmmError::CurveComplete,
+                CurveStatus::Graduated => AmmError::CurveNotComplete,
+                CurveStatus::Closed => AmmError::CurveNotGraduated,
+                _ => AmmError::InvalidStatusTransition,
+            };
+
+            return Err(error.into());
+        }
+
+        self.status = next as u8;
 
         Ok(())
     }
 
-    pub fn is_started(&self) -> bool {
-        let slot = (Clock::get().unwrap()).slot;
+    /// Checks the curve can be traded at `slot`, activating a scheduled curve whose starting slot
+    /// was reached and settling a crowdfund whose deadline passed
+    pub fn require_tradable(&mut self, slot: u64) -> Result<(), ProgramError> {
+        let started = slot >= self.starting_slot;
+
+        if self.status()? == CurveStatus::Scheduled && started {
+            self.transition(CurveStatus::Active)?;
+        }
+
+        if self.crowdfund.is_over(slot) {
+            if !self.crowdfund_reached()? {
+                return Err(AmmError::CrowdfundFailed.into());
+            }
+
+            self.crowdfund = Crowdfund::default();
+        }
+
+        match self.status()? {
+            CurveStatus::Active if started => Ok(()),
+            CurveStatus::Active | CurveStatus::Scheduled => Err(AmmError::CurveNotStarted.into()),
+            CurveStatus::Paused => Err(AmmError::CurvePaused.into()),
+            CurveStatus::Completed | CurveStatus::Graduated | CurveStatus::Closed => {
+                Err(AmmError::CurveComplete.into())
+            }
+            CurveStatus::Failed => Err(AmmError::CrowdfundFailed.into()),
+        }
+    }
+
+    /// The curve holds its crowdfund target in real reserves or sold out
+    pub fn crowdfund_reached(&self) -> Result<bool, ProgramError> {
+        Ok(self.real_sol_reserves >= self.crowdfund.target_sol
+            || self.status()? == CurveStatus::Completed)
+    }
+
+    /// The curve can no longer fail its crowdfund at `slot`, it has none, sold out or held its
+    /// target at the deadline
+    pub fn crowdfund_settled(&self, slot: u64) -> Result<bool, ProgramError> {
+        if !self.crowdfund.is_enabled() {
+            return Ok(true);
+        }
+
+        Ok(match self.status()? {
+            CurveStatus::Failed => false,
+            CurveStatus::Completed | CurveStatus::Graduated | CurveStatus::Closed => true,
+            _ => self.crowdfund.is_over(slot) && self.crowdfund_reached()?,
+        })
+    }
+
+    /// Marks a crowdfund curve that missed its target as failed once its deadline passed, a
+    /// failed curve stays failed
+    pub fn fail_crowdfund(&mut self, slot: u64) -> Result<(), ProgramError> {
+        if self.status()? == CurveStatus::Failed {
+            return Ok(());
+        }
+
+        require(
+            self.crowdfund.is_over(slot) && !self.crowdfund_reached()?,
+            AmmError::CrowdfundNotFailed.into(),
+        )?;
+
+        self.transition(CurveStatus::Failed)
+    }
+
+    /// Takes back `token_amount` sold tokens and returns the lamports refunded for them, their
+    /// share of `real_sol_reserves` among the tokens sold and not yet refunded
+    ///
+    /// The virtual reserves stay as they are, a failed curve never trades again.
+    pub fn apply_refund(&mut self, token_amount: u64) -> Option<u64> {
+        let sold_tokens = self
+            .initial_real_token_reserves
+            .checked_sub(self.real_token_reserves)?;
+
+        if token_amount == 0 || token_amount > sold_tokens {
+            return None;
+        }
+
+        let sol_amount = mul_div(self.real_sol_reserves, token_amount, sold_tokens)?;
+
+        log_value("ApplyRefund: sol_amount:", sol_amount.into());
+
+        self.real_sol_reserves = self.real_sol_reserves.checked_sub(sol_amount)?;
+        self.real_token_reserves = self.real_token_reserves.checked_add(token_amount)?;
+
+        Some(sol_amount)
+    }
+
+    /// Most tokens a wallet may hold after a buy, `None` when the curve has no cap
+    ///
+    /// The cap lifts once the curve is completed, the buy completing it included.
+    pub fn max_wallet_amount(&self) -> Result<Option<u64>, ProgramError> {
+        if self.max_wallet_bps == 0 || self.status()? == CurveStatus::Completed {
+            return Ok(None);
+        }
 
-        self.starting_slot > slot
+        bps_mul(self.max_wallet_bps as u64, self.token_total_supply, 10_000)
+            .map(Some)
+            .ok_or(ProgramError::ArithmeticOverflow)
     }
 
     pub fn calculate_fee(&self, amount: u64) -> Result<u64, ProgramError> {
@@ -113,7 +357,7 @@ impl BondingCurve {
 
         if slots_passed < 150 {
             msg!("Phase 1: 99% fees between slot 0 - 150");
-            sol_fee = bps_mul(9900, amount, 10_000).unwrap();
+            sol_fee = bps_mul(9900, amount, 10_000).ok_or(ProgramError::ArithmeticOverflow)?;
         } else if (150..250).contains(&slots_passed) {
             msg!("Phase 2: Linear decrease between 150 - 250");
 
@@ -126,15 +370,104 @@ impl BondingCurve {
                 .checked_div(100_000)
                 .ok_or(ProgramError::ArithmeticOverflow)?;
 
-            sol_fee = bps_mul(fee_bps as u64, amount, 10_000).unwrap();
+            sol_fee =
+                bps_mul(fee_bps as u64, amount, 10_000).ok_or(ProgramError::ArithmeticOverflow)?;
         } else if slots_passed > 250 {
             msg!("Phase 3: 1% fees after 250");
-            sol_fee = bps_mul(100, amount, 10_000).unwrap();
+            sol_fee = bps_mul(100, amount, 10_000).ok_or(ProgramError::ArithmeticOverflow)?;
         }
         Ok(sol_fee)
     }
 
-    pub fn apply_sell(&mut self, token_amount: u64, decimals: u8) -> Option<SellResult> {
+    /// Fee on `amount` at `fee_bps` when set, at the launch-phase schedule of `calculate_fee`
+    /// otherwise
+    pub fn calculate_fee_with(
+        &self,
+        amount: u64,
+        fee_bps: Option<u16>,
+    ) -> Result<u64, ProgramError> {
+        match fee_bps {
+            Some(fee_bps) => {
+                bps_mul(fee_bps as u64, amount, 10_000).ok_or(ProgramError::ArithmeticOverflow)
+            }
+            None => self.calculate_fee(amount),
+        }
+    }
+
+    /// Sells `token_amount` to the curve, rejecting the trade when it moves the spot price more
+    /// than `max_price_impact_bps`, 0 for no limit
+    pub fn apply_sell(
+        &mut self,
+        token_amount: u64,
+        decimals: u8,
+        max_price_impact_bps: u16,
+    ) -> Result<SellResult, ProgramError> {
+        let mut curve = *self;
+        let result = curve
+            .sell(token_amount, decimals)
+            .ok_or(AmmError::CouldNotSell)?;
+
+        curve.check_price_impact(self, max_price_impact_bps)?;
+        *self = curve;
+
+        Ok(result)
+    }
+
+    /// Buys with `sol_amount` from the curve, rejecting the trade when it moves the spot price more
+    /// than `max_price_impact_bps`, 0 for no limit
+    pub fn apply_buy(
+        &mut self,
+        sol_amount: u64,
+        decimals: u8,
+        max_price_impact_bps: u16,
+    ) -> Result<BuyResult, ProgramError> {
+        let mut curve = *self;
+        let result = curve
+            .buy(sol_amount, decimals)
+            .ok_or(AmmError::CouldNotBuy)?;
+
+        curve.check_price_impact(self, max_price_impact_bps)?;
+        *self = curve;
+
+        Ok(result)
+    }
+
+    /// How far the spot price of the virtual reserves moved from its value in `before`, in bps
+    pub fn spot_price_change_bps(&self, before: &BondingCurve) -> Option<u64> {
+        // spot price = virtual_sol / virtual_token, compared by cross multiplying
+        let price_before = (before.virtual_sol_reserves as u128)
+            .checked_mul(self.virtual_token_reserves as u128)?;
+        let price_after = (self.virtual_sol_reserves as u128)
+            .checked_mul(before.virtual_token_reserves as u128)?;
+
+        let change_bps = price_after
+            .abs_diff(price_before)
+            .checked_mul(10_000)?
+            .checked_div(price_before)?;
+
+        <u128 as TryInto<u64>>::try_into(change_bps).ok()
+    }
+
+    fn check_price_impact(
+        &self,
+        before: &BondingCurve,
+        max_price_impact_bps: u16,
+    ) -> Result<(), ProgramError> {
+        if max_price_impact_bps == 0 {
+            return Ok(());
+        }
+
+        let change_bps = self
+            .spot_price_change_bps(before)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        require(
+            change_bps <= max_price_impact_bps as u64,
+            AmmError::PriceImpactExceeded.into(),
+        )
+    }
+
+    fn sell(&mut self, token_amount: u64, decimals: u8) -> Option<SellResult> {
         log_value("apply_sell: token_amount:", token_amount as u128);
 
         let sol_amount = self.get_sol_for_sell_tokens(token_amount, decimals)?;
@@ -186,7 +519,7 @@ impl BondingCurve {
         })
     }
 
-    pub fn apply_buy(&mut self, mut sol_amount: u64, decimals: u8) -> Option<BuyResult> {
+    fn buy(&mut self, mut sol_amount: u64, decimals: u8) -> Option<BuyResult> {
         let mut token_amount = self.get_tokens_for_buy_sol(sol_amount, decimals)?;
 
         log_value("ApplyBuy: sol_amount:", sol_amount.into());
@@ -200,18 +533,8 @@ impl BondingCurve {
             // Last Buy
             token_amount = self.real_token_reserves;
 
-            // Temporarily store the current state
-            let current_virtual_token_reserves = self.virtual_token_reserves;
-            let current_virtual_sol_reserves = self.virtual_sol_reserves;
-
-            // Update self with the new token amount
-            self.virtual_token_reserves = (current_virtual_token_reserves as u128)
-                .checked_sub(token_amount as u128)?
-                .try_into()
-                .ok()?;
-            self.virtual_sol_reserves = 115_005_359_056; // Total raise amount at end
-
-            let recomputed_sol_amount = self.get_sol_for_sell_tokens(token_amount, decimals)?;
+            // Charge exactly what the remaining tokens cost on the curve
+            let recomputed_sol_amount = self.get_sol_for_buy_tokens(token_amount)?;
 
             log_value(
                 "ApplyBuy: recomputed_sol_amount:",
@@ -219,12 +542,7 @@ impl BondingCurve {
             );
             sol_amount = recomputed_sol_amount;
 
-            // Restore the state with the recomputed sol_amount
-            self.virtual_token_reserves = current_virtual_token_reserves;
-            self.virtual_sol_reserves = current_virtual_sol_reserves;
-
-            // Set complete to true
-            self.complete = 1;
+            self.transition(CurveStatus::Completed).ok()?;
         }
 
         // Adjusting token reserve values
@@ -273,23 +591,23 @@ impl BondingCurve {
         })
     }
 
+    /// Factor to bring token amounts to SOL's decimal basis before applying the curve
+    fn decimal_scale(decimals: u8) -> Option<u128> {
+        Some(10u128.pow((SOLANA_DECIMALS as u32).checked_sub(decimals as u32)?))
+    }
+
+    /// SOL paid out for selling `token_amount`, rounded down in favour of the curve
     pub fn get_sol_for_sell_tokens(&self, token_amount: u64, decimals: u8) -> Option<u64> {
-        let mint_decimals = 10u128.pow(decimals as u32);
+        let scale = Self::decimal_scale(decimals)?;
 
         // Convert to common decimal basis (using 9 decimals as base)
         let current_sol = self.virtual_sol_reserves as u128;
-        let current_tokens = (self.virtual_token_reserves as u128)
-            .checked_mul(SOLANA_DECIMALS as u128)? // Scale tokens up to 9 decimals
-            .checked_div(mint_decimals)?; // From 6 decimals
+        let current_tokens = (self.virtual_token_reserves as u128).checked_mul(scale)?;
 
         // Calculate new reserves using constant product formula
-        let new_tokens = current_tokens.checked_add(
-            (token_amount as u128)
-                .checked_mul(SOLANA_DECIMALS as u128)? // Scale input tokens to 9 decimals
-                .checked_div(mint_decimals)?, // From 6 decimals
-        )?;
+        let new_tokens = current_tokens.checked_add((token_amount as u128).checked_mul(scale)?)?;
 
-        let new_sol = (current_sol.checked_mul(current_tokens)?).checked_div(new_tokens)?;
+        let new_sol = (current_sol.checked_mul(current_tokens)?).div_ceil(new_tokens);
 
         let sol_out = current_sol.checked_sub(new_sol)?;
 
@@ -298,32 +616,75 @@ impl BondingCurve {
         <u128 as TryInto<u64>>::try_into(sol_out).ok()
     }
 
+    /// Tokens received for `sol_amount`, rounded down in favour of the curve
     pub fn get_tokens_for_buy_sol(&self, sol_amount: u64, decimals: u8) -> Option<u64> {
-        let mint_decimals = 10u128.pow(decimals as u32);
+        let scale = Self::decimal_scale(decimals)?;
 
         // Convert to common decimal basis (using 9 decimals as base)
         let current_sol: u128 = self.virtual_sol_reserves as u128;
         // Scaling to SOL's decimal point
-        let current_tokens = (self.virtual_token_reserves as u128)
-            .checked_mul(SOLANA_DECIMALS as u128)?
-            .checked_div(mint_decimals)?;
+        let current_tokens = (self.virtual_token_reserves as u128).checked_mul(scale)?;
 
         // Calculate new reserves using constant product formula
         let new_sol: u128 = current_sol.checked_add(sol_amount as u128)?;
         let k_value_before = current_sol.checked_mul(current_tokens)?;
-        let new_tokens = k_value_before.checked_div(new_sol)?;
+        let new_tokens = k_value_before.div_ceil(new_sol);
 
         let tokens_out: u128 = current_tokens.checked_sub(new_tokens)?;
 
         // Convert back to mint decimal places for tokens
-        let tokens_out = tokens_out
-            .checked_mul(mint_decimals)? // Convert to mint decimals
-            .checked_div(SOLANA_DECIMALS as u128)?; // From 9 decimals
+        let tokens_out = tokens_out.checked_div(scale)?;
 
         log_value("GetTokensForBuySol: tokens_out:", tokens_out);
         <u128 as TryInto<u64>>::try_into(tokens_out).ok()
     }
 
+    /// SOL needed to buy exactly `token_amount`, rounded up in favour of the curve
+    pub fn get_sol_for_buy_tokens(&self, token_amount: u64) -> Option<u64> {
+        let current_sol = self.virtual_sol_reserves as u128;
+        let current_tokens = self.virtual_token_reserves as u128;
+
+        let new_tokens = current_tokens.checked_sub(token_amount as u128)?;
+        if new_tokens == 0 {
+            return None;
+        }
+
+        let new_sol = (current_sol.checked_mul(current_tokens)?).div_ceil(new_tokens);
+
+        let sol_in = new_sol.checked_sub(current_sol)?;
+
+        log_value("GetSolForBuyTokens: sol_in:", sol_in);
+
+        <u128 as TryInto<u64>>::try_into(sol_in).ok()
+    }
+
+    /// How much worse than the spot price a trade of `sol_amount` for `token_amount` executes, in
+    /// bps, measured against the reserves before the trade
+    pub fn price_impact_bps(
+        &self,
+        sol_amount: u64,
+        token_amount: u64,
+        is_buy: bool,
+    ) -> Option<u64> {
+        if sol_amount == 0 || token_amount == 0 {
+            return Some(10_000);
+        }
+
+        // execution price / spot price = (sol_amount / token_amount) / (virtual_sol / virtual_token)
+        let execution = (sol_amount as u128).checked_mul(self.virtual_token_reserves as u128)?;
+        let spot = (token_amount as u128).checked_mul(self.virtual_sol_reserves as u128)?;
+
+        let impact = if is_buy {
+            execution.saturating_sub(spot)
+        } else {
+            spot.saturating_sub(execution)
+        };
+
+        let impact_bps = impact.checked_mul(10_000)?.checked_div(spot)?;
+
+        <u128 as TryInto<u64>>::try_into(impact_bps.min(10_000)).ok()
+    }
+
     pub fn invariant(&self, accounts: &[AccountInfo]) -> Result<(), ProgramError> {
         if let [curve_mint_ata, curve_sol_escrow] = accounts {
             require(
@@ -334,38 +695,77 @@ impl BondingCurve {
             let token_account_info = TokenAccount::from_account_info(curve_mint_ata)
                 .map_err(|_| ProgramError::InvalidAccountData)?;
 
-            if curve_sol_escrow.lamports() < self.real_sol_reserves {
-                msg!("Invariant failed: real_sol_reserves != bonding_curve_pool_lamports");
-                return Err(AmmError::InvariantFailed.into());
-            }
+            self.check_balances(
+                token_account_info.amount(),
+                curve_sol_escrow.lamports(),
+                token_account_info.is_frozen(),
+            )
+        } else {
+            Err(ProgramError::NotEnoughAccountKeys)
+        }
+    }
 
-            let tkn_balance_minus_liquidity = token_account_info
-                .amount()
-                .checked_sub(
-                    self.token_total_supply
-                        .checked_sub(self.initial_real_token_reserves)
-                        .ok_or(ProgramError::ArithmeticOverflow)?,
-                )
-                .ok_or(ProgramError::ArithmeticOverflow)?;
+    /// Checks the curve reserves against the balances actually held by the curve accounts
+    ///
+    /// Arguments:
+    /// - `token_balance`: amount held by the curve's mint ATA, transfer fees withheld in it excluded
+    /// - `sol_escrow_lamports`: lamports held by the curve's sol escrow
+    /// - `ata_frozen`: whether the curve's mint ATA is currently frozen
+    pub fn check_balances(
+        &self,
+        token_balance: u64,
+        sol_escrow_lamports: u64,
+        ata_frozen: bool,
+    ) -> Result<(), ProgramError> {
+        let status = self.status()?;
 
-            if tkn_balance_minus_liquidity != self.real_token_reserves {
-                msg!("Invariant failed: real_token_reserves != tkn_balance");
-                return Err(AmmError::InvariantFailed.into());
-            }
+        if sol_escrow_lamports < self.real_sol_reserves {
+            msg!("Invariant failed: real_sol_reserves != bonding_curve_pool_lamports");
+            return Err(AmmError::InvariantFailed.into());
+        }
 
-            if self.complete == 1 && self.real_token_reserves != 0 {
-                msg!("Invariant failed: bonding curve marked as complete but real_token_reserves != 0");
-                return Err(AmmError::InvariantFailed.into());
-            }
+        match status {
+            // The liquidity left the curve accounts, only the reserves have to be cleared
+            CurveStatus::Graduated => {
+                if self.real_sol_reserves != 0 || self.real_token_reserves != 0 {
+                    msg!("Invariant failed: graduated bonding curve still holds reserves");
+                    return Err(AmmError::InvariantFailed.into());
+                }
 
-            if self.complete == 0 && !token_account_info.is_frozen() {
-                msg!("Active BondingCurve TokenAccount must always be frozen at the end");
+                return Ok(());
+            }
+            CurveStatus::Closed => {
+                msg!("Invariant failed: bonding curve is closed");
                 return Err(AmmError::InvariantFailed.into());
             }
+            _ => {}
+        }
 
-            Ok(())
+        // A frozen ATA can't receive tokens from outside, an unfrozen one may hold donations on top
+        // of the reserves
+        let balance_matches = if self.is_frozen_vault() {
+            token_balance == self.real_token_reserves
         } else {
-            Err(ProgramError::NotEnoughAccountKeys)
+            token_balance >= self.real_token_reserves
+        };
+
+        if !balance_matches {
+            msg!("Invariant failed: real_token_reserves != tkn_balance");
+            return Err(AmmError::InvariantFailed.into());
+        }
+
+        if (status == CurveStatus::Completed) != (self.real_token_reserves == 0) {
+            msg!(
+                "Invariant failed: bonding curve is complete exactly when real_token_reserves == 0"
+            );
+            return Err(AmmError::InvariantFailed.into());
         }
+
+        if status != CurveStatus::Completed && self.is_frozen_vault() && !ata_frozen {
+            msg!("Tradable BondingCurve TokenAccount must always be frozen at the end");
+            return Err(AmmError::InvariantFailed.into());
+        }
+
+        Ok(())
     }
 }
@@ -0,0 +1,78 @@
+use crate::{init_account, require_pda, AmmError, Discriminator};
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError, pubkey::Pubkey,
+    seeds,
+};
+
+/// Last slot a wallet bought on a curve, sells wait `sell_cooldown_slots` of the config after it
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, Debug)]
+pub struct TradeCooldown {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub bump: u8,
+    pub _padding: [u8; 6],
+
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+
+    pub last_buy_slot: u64,
+}
+
+impl Discriminator for TradeCooldown {
+    // First 8 bytes of sha256("account:TradeCooldown")
+    const DISCRIMINATOR: [u8; 8] = [0x8d, 0x51, 0xb5, 0x4a, 0x87, 0xe9, 0xb2, 0xb4];
+    const VERSION: u8 = 1;
+    const UNSUPPORTED_VERSION: ProgramError =
+        ProgramError::Custom(AmmError::UnsupportedLayoutVersion as u32);
+}
+
+impl TradeCooldown {
+    pub const SIZE: usize = core::mem::size_of::<Self>();
+    pub const SEED_PREFIX: &[u8] = b"trade_cooldown";
+
+    /// Checks the account is the cooldown PDA of `wallet` on the curve of `mint` and returns its
+    /// bump
+    pub fn check_id(
+        cooldown_account: &AccountInfo,
+        mint: &Pubkey,
+        wallet: &Pubkey,
+    ) -> Result<u8, ProgramError> {
+        require_pda(
+            cooldown_account,
+            &[Self::SEED_PREFIX, mint.as_ref(), wallet.as_ref()],
+            &crate::ID,
+        )
+    }
+
+    pub fn get_signer_seeds<'a>(
+        mint: &'a Pubkey,
+        wallet: &'a Pubkey,
+        bump: &'a [u8; 1],
+    ) -> [Seed<'a>; 4] {
+        seeds!(Self::SEED_PREFIX, mint.as_ref(), wallet.as_ref(), bump)
+    }
+
+    pub fn init(
+        cooldown_account: &AccountInfo,
+        bump: u8,
+        mint: &Pubkey,
+        wallet: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        let cooldown_data = init_account::<TradeCooldown>(cooldown_account)?;
+
+        cooldown_data.bump = bump;
+        cooldown_data._padding = [0u8; 6];
+        cooldown_data.mint = *mint;
+        cooldown_data.wallet = *wallet;
+        cooldown_data.last_buy_slot = 0;
+
+        Ok(())
+    }
+
+    /// A sell at `slot` is at least `cooldown_slots` after the last buy
+    pub fn is_over(&self, slot: u64, cooldown_slots: u32) -> bool {
+        slot.saturating_sub(self.last_buy_slot) >= cooldown_slots as u64
+    }
+}
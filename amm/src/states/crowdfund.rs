@@ -0,0 +1,35 @@
+use crate::require;
+use bytemuck::{Pod, Zeroable};
+use pinocchio::program_error::ProgramError;
+
+/// SOL target a curve has to raise by `deadline_slot` to keep trading
+///
+/// A curve that holds `target_sol` in real reserves at the deadline, or completed before it,
+/// trades on as a regular curve. Otherwise it fails and holders get refunded. A zeroed target
+/// launches a regular curve.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, Debug, Default, PartialEq, Eq)]
+pub struct Crowdfund {
+    pub target_sol: u64,
+    /// Slot the outcome is settled at
+    pub deadline_slot: u64,
+}
+
+impl Crowdfund {
+    pub fn is_enabled(&self) -> bool {
+        self.target_sol > 0
+    }
+
+    /// Checks the crowdfund settings of a new curve, the target and the deadline are set together
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        require(
+            self.is_enabled() == (self.deadline_slot > 0),
+            ProgramError::InvalidInstructionData,
+        )
+    }
+
+    /// The deadline passed at `slot`, the curve either reached its target or failed
+    pub fn is_over(&self, slot: u64) -> bool {
+        self.is_enabled() && slot >= self.deadline_slot
+    }
+}
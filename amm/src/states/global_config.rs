@@ -1,19 +1,32 @@
-use crate::{load, require};
+use crate::{
+    load, require, require_pda,
+    states::{treasury::Treasury, vesting::Vesting},
+    AmmError, Discriminator,
+};
 use bytemuck::{Pod, Zeroable};
 use pinocchio::{
     account_info::AccountInfo,
+    instruction::Seed,
     program_error::ProgramError,
     pubkey::{find_program_address, pubkey_eq, Pubkey},
+    seeds,
 };
 
 #[repr(C)]
 #[derive(Clone, Copy, Pod, Zeroable, Debug)]
 pub struct GlobalConfig {
+    pub discriminator: [u8; 8],
+    pub version: u8,
     pub inittialized: u8,
     pub mint_decimals: u8,
-    pub _padding: [u8; 6],
+    pub _padding: [u8; 1],
+    /// Highest Token-2022 transfer fee a creator can put on a launched mint, 0 disables it
+    pub max_transfer_fee_bps: u16,
+    /// Fee rate of the creator's dev buy, 0 charges the launch-phase fee of a regular buy
+    pub creator_fee_bps: u16,
 
     pub admin: Pubkey,
+    /// Treasury PDA, swap fees and everything else owed to the protocol accrue to it
     pub fee_receiver: Pubkey,
 
     // initial values for bonding curve
@@ -21,17 +34,50 @@ pub struct GlobalConfig {
     pub initial_virtual_sol_reserves: u64,
     pub initial_real_token_reserves: u64,
     pub token_total_supply: u64,
+
+    /// Lamports a creator pays the treasury to launch a curve
+    pub creation_fee_lamports: u64,
+
+    /// Most a single curve trade may move the spot price, in bps, 0 for no limit
+    pub max_price_impact_bps: u16,
+    pub _padding_end: [u8; 2],
+    /// Slots a wallet waits between a buy and a following sell on the same curve, 0 for no
+    /// cooldown
+    pub sell_cooldown_slots: u32,
+
+    /// Highest cap a creator can put on the transfer fee of a single transfer of a launched mint,
+    /// in base units of the mint
+    pub max_transfer_fee: u64,
+
+    /// Schedule every curve allocation vests on from launch, nothing releases before the cliff
+    pub vesting_cliff_slots: u64,
+    pub vesting_duration_slots: u64,
+    /// `Vesting::CREATOR_BENEFICIARY` or `Vesting::TREASURY_BENEFICIARY`
+    pub vesting_beneficiary: u8,
+    pub _padding_vesting: [u8; 7],
+
+    /// Programs a creator can hook a launched mint to, zeroed entries are unused
+    pub allowed_transfer_hooks: [Pubkey; GlobalConfig::MAX_TRANSFER_HOOKS],
+}
+
+impl Discriminator for GlobalConfig {
+    // First 8 bytes of sha256("account:GlobalConfig")
+    const DISCRIMINATOR: [u8; 8] = [0x95, 0x08, 0x9c, 0xca, 0xa0, 0xfc, 0xb0, 0xd9];
+    const VERSION: u8 = 1;
+    const UNSUPPORTED_VERSION: ProgramError =
+        ProgramError::Custom(AmmError::UnsupportedLayoutVersion as u32);
 }
 
 impl GlobalConfig {
     pub const GLOBAL_PEFIX: &[u8; 13] = b"global_config";
     pub const SIZE: usize = core::mem::size_of::<GlobalConfig>();
+    pub const MAX_TRANSFER_HOOKS: usize = 4;
 
     pub fn update_global(
         params: GlobalSettingsInput,
         global_account: &AccountInfo,
     ) -> Result<(), ProgramError> {
-        let global_data = load::<GlobalConfig>(global_account)?;
+        let global_data = load::<GlobalConfig>(global_account, &crate::ID)?;
         let GlobalSettingsInput {
             mint_decimals,
             fee_receiver,
@@ -40,7 +86,19 @@ impl GlobalConfig {
             initial_virtual_sol_reserves,
             initial_real_token_reserves,
             token_total_supply,
+            creator_fee_bps,
+            max_transfer_fee_bps,
+            creation_fee_lamports,
+            max_price_impact_bps,
+            sell_cooldown_slots,
+            max_transfer_fee,
+            vesting_cliff_slots,
+            vesting_duration_slots,
+            vesting_beneficiary,
+            allowed_transfer_hooks,
             _padding: _,
+            _padding_end: _,
+            _padding_vesting: _,
         } = params;
 
         global_data.admin = admin;
@@ -50,22 +108,40 @@ impl GlobalConfig {
         global_data.initial_virtual_sol_reserves = initial_virtual_sol_reserves;
         global_data.initial_virtual_token_reserves = initial_virtual_token_reserves;
         global_data.token_total_supply = token_total_supply;
+        global_data.creator_fee_bps = creator_fee_bps;
+        global_data.max_transfer_fee_bps = max_transfer_fee_bps;
+        global_data.creation_fee_lamports = creation_fee_lamports;
+        global_data.max_price_impact_bps = max_price_impact_bps;
+        global_data.sell_cooldown_slots = sell_cooldown_slots;
+        global_data.max_transfer_fee = max_transfer_fee;
+        global_data.vesting_cliff_slots = vesting_cliff_slots;
+        global_data.vesting_duration_slots = vesting_duration_slots;
+        global_data.vesting_beneficiary = vesting_beneficiary;
+        global_data._padding_vesting = [0u8; 7];
+        global_data.allowed_transfer_hooks = allowed_transfer_hooks;
+        global_data._padding_end = [0u8; 2];
         global_data.inittialized = 1;
-        global_data._padding = [0u8; 6];
+        global_data._padding = [0u8; 1];
 
         Ok(())
     }
 
-    pub fn check_id(global_account: &AccountInfo) -> Result<(), ProgramError> {
-        let global_seeds: &[&[u8]] = &[GlobalConfig::GLOBAL_PEFIX];
+    /// `program_id` is one of the transfer hooks the admin allowed
+    pub fn is_transfer_hook_allowed(&self, program_id: &Pubkey) -> bool {
+        *program_id != [0; 32]
+            && self
+                .allowed_transfer_hooks
+                .iter()
+                .any(|allowed| pubkey_eq(allowed, program_id))
+    }
 
-        let (expected_global_config, _) = find_program_address(global_seeds, &crate::ID);
+    /// Checks the account is the global config PDA and returns its bump
+    pub fn check_id(global_account: &AccountInfo) -> Result<u8, ProgramError> {
+        require_pda(global_account, &[GlobalConfig::GLOBAL_PEFIX], &crate::ID)
+    }
 
-        require(
-            pubkey_eq(global_account.key(), &expected_global_config),
-            ProgramError::IncorrectProgramId,
-        )?;
-        Ok(())
+    pub fn get_signer_seeds(bump: &[u8; 1]) -> [Seed<'_>; 2] {
+        seeds!(GlobalConfig::GLOBAL_PEFIX, bump)
     }
 
     pub fn validate_settings(params: &GlobalSettingsInput) -> Result<(), ProgramError> {
@@ -74,6 +150,21 @@ impl GlobalConfig {
             ProgramError::InvalidInstructionData,
         )?;
 
+        require(
+            params.creator_fee_bps <= 10_000,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        require(
+            params.max_transfer_fee_bps <= 10_000,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        require(
+            params.max_price_impact_bps <= 10_000,
+            ProgramError::InvalidInstructionData,
+        )?;
+
         require(
             params.token_total_supply <= u64::MAX / 2,
             ProgramError::InvalidInstructionData,
@@ -97,6 +188,25 @@ impl GlobalConfig {
             ProgramError::InvalidInstructionData,
         )?;
 
+        require(
+            params.vesting_duration_slots > 0
+                && params.vesting_cliff_slots <= params.vesting_duration_slots,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        require(
+            params.vesting_beneficiary == Vesting::CREATOR_BENEFICIARY
+                || params.vesting_beneficiary == Vesting::TREASURY_BENEFICIARY,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        let (treasury, _) = find_program_address(&[Treasury::SEED_PREFIX], &crate::ID);
+
+        require(
+            pubkey_eq(&params.fee_receiver, &treasury),
+            ProgramError::InvalidInstructionData,
+        )?;
+
         Ok(())
     }
 }
@@ -105,7 +215,11 @@ impl GlobalConfig {
 #[derive(Clone, Copy, Pod, Zeroable)]
 pub struct GlobalSettingsInput {
     pub mint_decimals: u8,
-    pub _padding: [u8; 7],
+    pub _padding: [u8; 1],
+    /// Most a single curve trade may move the spot price, in bps, 0 for no limit
+    pub max_price_impact_bps: u16,
+    pub max_transfer_fee_bps: u16,
+    pub creator_fee_bps: u16,
 
     pub fee_receiver: Pubkey,
     pub admin: Pubkey,
@@ -114,4 +228,31 @@ pub struct GlobalSettingsInput {
     pub initial_virtual_sol_reserves: u64,
     pub initial_real_token_reserves: u64,
     pub token_total_supply: u64,
+
+    /// Lamports a creator pays the treasury to launch a curve, 0 makes launches free
+    pub creation_fee_lamports: u64,
+
+    /// Slots a wallet waits between a buy and a following sell on the same curve, 0 for no
+    /// cooldown
+    pub sell_cooldown_slots: u32,
+    pub _padding_end: [u8; 4],
+
+    /// Highest cap a creator can put on the transfer fee of a single transfer, in base units of
+    /// the mint
+    pub max_transfer_fee: u64,
+
+    /// Vesting schedule of the curve allocations, the duration can't be 0 or shorter than the
+    /// cliff
+    pub vesting_cliff_slots: u64,
+    pub vesting_duration_slots: u64,
+    /// `Vesting::CREATOR_BENEFICIARY` or `Vesting::TREASURY_BENEFICIARY`
+    pub vesting_beneficiary: u8,
+    pub _padding_vesting: [u8; 7],
+
+    /// Transfer hook programs curves may launch with, zeroed entries are unused
+    pub allowed_transfer_hooks: [Pubkey; GlobalConfig::MAX_TRANSFER_HOOKS],
+}
+
+impl GlobalSettingsInput {
+    pub const SIZE: usize = core::mem::size_of::<GlobalSettingsInput>();
 }
@@ -1,2 +1,8 @@
+pub mod allowlist;
 pub mod bonding_curve;
+pub mod cooldown;
+pub mod crowdfund;
 pub mod global_config;
+pub mod multisig;
+pub mod treasury;
+pub mod vesting;
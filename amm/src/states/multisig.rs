@@ -0,0 +1,269 @@
+use crate::{init_account, require, require_pda, AmmError, Discriminator};
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::{pubkey_eq, Pubkey},
+    seeds,
+};
+
+/// Most signers a multisig can have, approvals are tracked in a `u16` bitmap
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+/// Most accounts the instruction of a proposal can take
+pub const MAX_PROPOSAL_ACCOUNTS: usize = 16;
+/// Most bytes of instruction data a proposal can carry
+pub const MAX_PROPOSAL_DATA_LEN: usize = 256;
+
+/// M-of-N signer set acting through its vault PDA
+///
+/// The vault is a system-owned PDA that only signs the instructions of executed proposals. Set it
+/// as the `GlobalConfig` admin to put the protocol under the multisig. The signer set never
+/// changes, rotating it means creating a new multisig and handing the admin role over to its vault.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, Debug)]
+pub struct Multisig {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub bump: u8,
+    pub vault_bump: u8,
+    /// Approvals a proposal needs before it can be executed
+    pub threshold: u8,
+    pub signer_count: u8,
+    pub _padding: [u8; 3],
+
+    /// Signer the multisig address derives from
+    pub create_key: Pubkey,
+    /// Number of proposals created so far, the index of the next one
+    pub proposal_count: u64,
+    /// The first `signer_count` entries are the signers, the rest is zeroed
+    pub signers: [Pubkey; MAX_MULTISIG_SIGNERS],
+}
+
+impl Discriminator for Multisig {
+    // First 8 bytes of sha256("account:Multisig")
+    const DISCRIMINATOR: [u8; 8] = [0xe0, 0x74, 0x79, 0xba, 0x44, 0xa1, 0x4f, 0xec];
+    const VERSION: u8 = 1;
+    const UNSUPPORTED_VERSION: ProgramError =
+        ProgramError::Custom(AmmError::UnsupportedLayoutVersion as u32);
+}
+
+impl Multisig {
+    pub const SIZE: usize = core::mem::size_of::<Self>();
+    pub const SEED_PREFIX: &[u8] = b"multisig";
+    pub const VAULT_SEED_PREFIX: &[u8] = b"multisig_vault";
+
+    /// Checks the account is the multisig PDA of `create_key` and returns its bump
+    pub fn check_id(
+        multisig_account: &AccountInfo,
+        create_key: &Pubkey,
+    ) -> Result<u8, ProgramError> {
+        require_pda(
+            multisig_account,
+            &[Self::SEED_PREFIX, create_key.as_ref()],
+            &crate::ID,
+        )
+    }
+
+    /// Checks the account is the vault PDA of `multisig` and returns its bump
+    pub fn check_vault_id(
+        vault_account: &AccountInfo,
+        multisig: &Pubkey,
+    ) -> Result<u8, ProgramError> {
+        require_pda(
+            vault_account,
+            &[Self::VAULT_SEED_PREFIX, multisig.as_ref()],
+            &crate::ID,
+        )
+    }
+
+    pub fn get_signer_seeds<'a>(create_key: &'a Pubkey, bump: &'a [u8; 1]) -> [Seed<'a>; 3] {
+        seeds!(Self::SEED_PREFIX, create_key.as_ref(), bump)
+    }
+
+    pub fn get_vault_signer_seeds<'a>(multisig: &'a Pubkey, bump: &'a [u8; 1]) -> [Seed<'a>; 3] {
+        seeds!(Self::VAULT_SEED_PREFIX, multisig.as_ref(), bump)
+    }
+
+    /// Checks a signer set, at most `MAX_MULTISIG_SIGNERS` distinct non-default keys and a
+    /// threshold between 1 and their number
+    pub fn validate_signers(signers: &[Pubkey], threshold: u8) -> Result<(), ProgramError> {
+        require(
+            !signers.is_empty() && signers.len() <= MAX_MULTISIG_SIGNERS,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        require(
+            threshold > 0 && threshold as usize <= signers.len(),
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        for (index, signer) in signers.iter().enumerate() {
+            require(*signer != [0; 32], ProgramError::InvalidInstructionData)?;
+            require(
+                !signers[index + 1..].contains(signer),
+                ProgramError::InvalidInstructionData,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn init(
+        multisig_account: &AccountInfo,
+        bump: u8,
+        vault_bump: u8,
+        create_key: &Pubkey,
+        signers: &[Pubkey],
+        threshold: u8,
+    ) -> Result<(), ProgramError> {
+        Self::validate_signers(signers, threshold)?;
+
+        let multisig_data = init_account::<Multisig>(multisig_account)?;
+
+        multisig_data.bump = bump;
+        multisig_data.vault_bump = vault_bump;
+        multisig_data.threshold = threshold;
+        multisig_data.signer_count = signers.len() as u8;
+        multisig_data._padding = [0u8; 3];
+        multisig_data.create_key = *create_key;
+        multisig_data.proposal_count = 0;
+        multisig_data.signers = [[0; 32]; MAX_MULTISIG_SIGNERS];
+        multisig_data.signers[..signers.len()].copy_from_slice(signers);
+
+        Ok(())
+    }
+
+    /// Position of `key` in the signer set, errors for anyone outside of it
+    pub fn signer_index(&self, key: &Pubkey) -> Result<usize, ProgramError> {
+        self.signers[..self.signer_count as usize]
+            .iter()
+            .position(|signer| pubkey_eq(signer, key))
+            .ok_or(AmmError::NotMultisigSigner.into())
+    }
+}
+
+/// Account of the instruction a proposal executes
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, Debug, Default, PartialEq, Eq)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: u8,
+    pub is_writable: u8,
+}
+
+/// Instruction a multisig executes once enough of its signers approved it, signed by its vault
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, Debug)]
+pub struct Proposal {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub bump: u8,
+    /// 1 once executed, a proposal runs at most once
+    pub executed: u8,
+    pub account_count: u8,
+    /// Bit `i` is set once the signer at index `i` of the multisig approved
+    pub approvals: u16,
+    pub data_len: u16,
+
+    pub multisig: Pubkey,
+    pub index: u64,
+
+    pub program_id: Pubkey,
+    pub accounts: [ProposalAccount; MAX_PROPOSAL_ACCOUNTS],
+    pub data: [u8; MAX_PROPOSAL_DATA_LEN],
+}
+
+impl Discriminator for Proposal {
+    // First 8 bytes of sha256("account:Proposal")
+    const DISCRIMINATOR: [u8; 8] = [0x1a, 0x5e, 0xbd, 0xbb, 0x74, 0x88, 0x35, 0x21];
+    const VERSION: u8 = 1;
+    const UNSUPPORTED_VERSION: ProgramError =
+        ProgramError::Custom(AmmError::UnsupportedLayoutVersion as u32);
+}
+
+impl Proposal {
+    pub const SIZE: usize = core::mem::size_of::<Self>();
+    pub const SEED_PREFIX: &[u8] = b"proposal";
+
+    /// Checks the account is the PDA of proposal `index` of `multisig` and returns its bump
+    pub fn check_id(
+        proposal_account: &AccountInfo,
+        multisig: &Pubkey,
+        index: u64,
+    ) -> Result<u8, ProgramError> {
+        require_pda(
+            proposal_account,
+            &[Self::SEED_PREFIX, multisig.as_ref(), &index.to_le_bytes()],
+            &crate::ID,
+        )
+    }
+
+    pub fn get_signer_seeds<'a>(
+        multisig: &'a Pubkey,
+        index: &'a [u8; 8],
+        bump: &'a [u8; 1],
+    ) -> [Seed<'a>; 4] {
+        seeds!(Self::SEED_PREFIX, multisig.as_ref(), index, bump)
+    }
+
+    pub fn init(
+        proposal_account: &AccountInfo,
+        bump: u8,
+        multisig: &Pubkey,
+        index: u64,
+        program_id: &Pubkey,
+        accounts: &[ProposalAccount],
+        data: &[u8],
+    ) -> Result<(), ProgramError> {
+        require(
+            accounts.len() <= MAX_PROPOSAL_ACCOUNTS && data.len() <= MAX_PROPOSAL_DATA_LEN,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        let proposal_data = init_account::<Proposal>(proposal_account)?;
+
+        proposal_data.bump = bump;
+        proposal_data.executed = 0;
+        proposal_data.account_count = accounts.len() as u8;
+        proposal_data.approvals = 0;
+        proposal_data.data_len = data.len() as u16;
+        proposal_data.multisig = *multisig;
+        proposal_data.index = index;
+        proposal_data.program_id = *program_id;
+        proposal_data.accounts[..accounts.len()].copy_from_slice(accounts);
+        proposal_data.data[..data.len()].copy_from_slice(data);
+
+        Ok(())
+    }
+
+    pub fn approval_count(&self) -> u32 {
+        self.approvals.count_ones()
+    }
+
+    /// Records the approval of the signer at `signer_index`, each signer approves once
+    pub fn approve(&mut self, signer_index: usize) -> Result<(), ProgramError> {
+        require(self.executed == 0, AmmError::ProposalExecuted.into())?;
+
+        let bit = 1u16 << signer_index;
+
+        require(self.approvals & bit == 0, AmmError::AlreadyApproved.into())?;
+
+        self.approvals |= bit;
+
+        Ok(())
+    }
+
+    /// Marks the proposal executed, it needs `threshold` approvals and runs only once
+    pub fn execute(&mut self, threshold: u8) -> Result<(), ProgramError> {
+        require(self.executed == 0, AmmError::ProposalExecuted.into())?;
+        require(
+            self.approval_count() >= threshold as u32,
+            AmmError::ThresholdNotMet.into(),
+        )?;
+
+        self.executed = 1;
+
+        Ok(())
+    }
+}
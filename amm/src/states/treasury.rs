@@ -0,0 +1,176 @@
+use crate::{init_account, require, require_pda, AmmError, Discriminator};
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError, pubkey::Pubkey,
+    seeds,
+};
+
+/// Most wallets a treasury withdrawal can be split between
+pub const MAX_TREASURY_RECIPIENTS: usize = 8;
+
+/// Wallet receiving `weight_bps` of every treasury withdrawal
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, Debug, Default, PartialEq, Eq)]
+pub struct TreasuryRecipient {
+    pub wallet: Pubkey,
+    pub weight_bps: u16,
+    pub _padding: [u8; 6],
+}
+
+/// Program-owned PDA the protocol fees accrue to
+///
+/// Only `WithdrawTreasury` moves funds out of it, split between the recipients by weight. The
+/// global config's `fee_receiver` has to be this PDA.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, Debug)]
+pub struct Treasury {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub bump: u8,
+    pub recipient_count: u8,
+    pub _padding: [u8; 5],
+
+    /// Lamports withdrawn over the lifetime of the treasury
+    pub total_withdrawn_lamports: u64,
+    /// The first `recipient_count` entries are the recipients, their weights add up to 10_000
+    pub recipients: [TreasuryRecipient; MAX_TREASURY_RECIPIENTS],
+}
+
+impl Discriminator for Treasury {
+    // First 8 bytes of sha256("account:Treasury")
+    const DISCRIMINATOR: [u8; 8] = [0xee, 0xef, 0x7b, 0xee, 0x59, 0x01, 0xa8, 0xfd];
+    const VERSION: u8 = 1;
+    const UNSUPPORTED_VERSION: ProgramError =
+        ProgramError::Custom(AmmError::UnsupportedLayoutVersion as u32);
+}
+
+impl Treasury {
+    pub const SIZE: usize = core::mem::size_of::<Self>();
+    pub const SEED_PREFIX: &[u8] = b"treasury";
+
+    /// Checks the account is the treasury PDA and returns its bump
+    pub fn check_id(treasury_account: &AccountInfo) -> Result<u8, ProgramError> {
+        require_pda(treasury_account, &[Self::SEED_PREFIX], &crate::ID)
+    }
+
+    pub fn get_signer_seeds(bump: &[u8; 1]) -> [Seed<'_>; 2] {
+        seeds!(Self::SEED_PREFIX, bump)
+    }
+
+    /// Checks a recipient set, at most `MAX_TREASURY_RECIPIENTS` non-default wallets with non-zero
+    /// weights adding up to 10_000
+    pub fn validate_recipients(recipients: &[TreasuryRecipient]) -> Result<(), ProgramError> {
+        require(
+            !recipients.is_empty() && recipients.len() <= MAX_TREASURY_RECIPIENTS,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        let mut total_weight = 0u32;
+
+        for recipient in recipients {
+            require(
+                recipient.wallet != [0; 32] && recipient.weight_bps > 0,
+                ProgramError::InvalidInstructionData,
+            )?;
+
+            total_weight += recipient.weight_bps as u32;
+        }
+
+        require(total_weight == 10_000, ProgramError::InvalidInstructionData)
+    }
+
+    pub fn init(treasury_account: &AccountInfo, bump: u8) -> Result<(), ProgramError> {
+        let treasury_data = init_account::<Treasury>(treasury_account)?;
+
+        treasury_data.bump = bump;
+        treasury_data._padding = [0u8; 5];
+        treasury_data.total_withdrawn_lamports = 0;
+
+        Ok(())
+    }
+
+    pub fn set_recipients(&mut self, recipients: &[TreasuryRecipient]) -> Result<(), ProgramError> {
+        Self::validate_recipients(recipients)?;
+
+        self.recipient_count = recipients.len() as u8;
+        self.recipients = [TreasuryRecipient::default(); MAX_TREASURY_RECIPIENTS];
+        self.recipients[..recipients.len()].copy_from_slice(recipients);
+
+        Ok(())
+    }
+
+    pub fn recipients(&self) -> &[TreasuryRecipient] {
+        &self.recipients[..self.recipient_count as usize]
+    }
+
+    /// Shares of `amount` going to each recipient, rounded down, the last recipient also receives
+    /// the rounding dust so the shares always add up to `amount`
+    pub fn split(&self, amount: u64) -> [u64; MAX_TREASURY_RECIPIENTS] {
+        let mut shares = [0u64; MAX_TREASURY_RECIPIENTS];
+        let mut remaining = amount;
+
+        for (share, recipient) in shares.iter_mut().zip(self.recipients()) {
+            // weight_bps <= 10_000, the share never exceeds amount
+            *share = (amount as u128 * recipient.weight_bps as u128 / 10_000) as u64;
+            remaining = remaining.saturating_sub(*share);
+        }
+
+        if let Some(last) = shares[..self.recipient_count as usize].last_mut() {
+            *last += remaining;
+        }
+
+        shares
+    }
+}
+
+/// Tokens of one mint withdrawn from the treasury, created by the first withdrawal of the mint
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, Debug)]
+pub struct TreasuryTokenRecord {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub bump: u8,
+    pub _padding: [u8; 6],
+
+    pub mint: Pubkey,
+
+    /// Tokens withdrawn over the lifetime of the treasury, in base units of the mint
+    pub total_withdrawn: u64,
+}
+
+impl Discriminator for TreasuryTokenRecord {
+    // First 8 bytes of sha256("account:TreasuryTokenRecord")
+    const DISCRIMINATOR: [u8; 8] = [0x46, 0x36, 0x22, 0xc4, 0xfe, 0x30, 0x91, 0xb9];
+    const VERSION: u8 = 1;
+    const UNSUPPORTED_VERSION: ProgramError =
+        ProgramError::Custom(AmmError::UnsupportedLayoutVersion as u32);
+}
+
+impl TreasuryTokenRecord {
+    pub const SIZE: usize = core::mem::size_of::<Self>();
+    pub const SEED_PREFIX: &[u8] = b"treasury_token";
+
+    /// Checks the account is the token record PDA of `mint` and returns its bump
+    pub fn check_id(record_account: &AccountInfo, mint: &Pubkey) -> Result<u8, ProgramError> {
+        require_pda(
+            record_account,
+            &[Self::SEED_PREFIX, mint.as_ref()],
+            &crate::ID,
+        )
+    }
+
+    pub fn get_signer_seeds<'a>(mint: &'a Pubkey, bump: &'a [u8; 1]) -> [Seed<'a>; 3] {
+        seeds!(Self::SEED_PREFIX, mint.as_ref(), bump)
+    }
+
+    pub fn init(record_account: &AccountInfo, bump: u8, mint: &Pubkey) -> Result<(), ProgramError> {
+        let record_data = init_account::<TreasuryTokenRecord>(record_account)?;
+
+        record_data.bump = bump;
+        record_data._padding = [0u8; 6];
+        record_data.mint = *mint;
+        record_data.total_withdrawn = 0;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{init_account, mul_div, require, require_pda, AmmError, Discriminator};
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    seeds,
+    sysvars::{clock::Clock, Sysvar},
+};
+
+/// Locks the allocation a curve mints outside its reserves at launch and releases it to the
+/// beneficiary linearly over `duration_slots`, nothing is released before `cliff_slots`
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, Debug)]
+pub struct Vesting {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub bump: u8,
+    pub _padding: [u8; 6],
+
+    pub mint: Pubkey,
+    pub beneficiary: Pubkey,
+
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_slot: u64,
+    pub cliff_slots: u64,
+    pub duration_slots: u64,
+}
+
+impl Discriminator for Vesting {
+    // First 8 bytes of sha256("account:Vesting")
+    const DISCRIMINATOR: [u8; 8] = [0x64, 0x95, 0x42, 0x8a, 0x5f, 0xc8, 0x80, 0xf1];
+    const VERSION: u8 = 1;
+    const UNSUPPORTED_VERSION: ProgramError =
+        ProgramError::Custom(AmmError::UnsupportedLayoutVersion as u32);
+}
+
+impl Vesting {
+    pub const SIZE: usize = core::mem::size_of::<Self>();
+    pub const SEED_PREFIX: &[u8] = b"vesting";
+
+    /// The allocation vests to the curve creator
+    pub const CREATOR_BENEFICIARY: u8 = 0;
+    /// The allocation vests to the protocol treasury
+    pub const TREASURY_BENEFICIARY: u8 = 1;
+
+    /// Checks the account is the vesting PDA of `mint` and returns its bump
+    pub fn check_id(vesting_account: &AccountInfo, mint: &Pubkey) -> Result<u8, ProgramError> {
+        require_pda(
+            vesting_account,
+            &[Self::SEED_PREFIX, mint.as_ref()],
+            &crate::ID,
+        )
+    }
+
+    pub fn get_signer_seeds<'a>(mint: &'a Pubkey, bump: &'a [u8; 1]) -> [Seed<'a>; 3] {
+        seeds!(Self::SEED_PREFIX, mint.as_ref(), bump)
+    }
+
+    pub fn init(
+        vesting_account: &AccountInfo,
+        bump: u8,
+        mint: &Pubkey,
+        beneficiary: &Pubkey,
+        total_amount: u64,
+        cliff_slots: u64,
+        duration_slots: u64,
+    ) -> Result<(), ProgramError> {
+        require(
+            duration_slots > 0 && cliff_slots <= duration_slots,
+            ProgramError::InvalidInstructionData,
+        )?;
+
+        let vesting_data = init_account::<Vesting>(vesting_account)?;
+
+        vesting_data.bump = bump;
+        vesting_data._padding = [0u8; 6];
+        vesting_data.mint = *mint;
+        vesting_data.beneficiary = *beneficiary;
+        vesting_data.total_amount = total_amount;
+        vesting_data.claimed_amount = 0;
+        vesting_data.start_slot = (Clock::get()?).slot;
+        vesting_data.cliff_slots = cliff_slots;
+        vesting_data.duration_slots = duration_slots;
+
+        Ok(())
+    }
+
+    /// Amount released by `slot`, claimed or not
+    pub fn unlocked_amount(&self, slot: u64) -> Option<u64> {
+        let elapsed = slot.saturating_sub(self.start_slot);
+
+        if elapsed < self.cliff_slots {
+            return Some(0);
+        }
+
+        if elapsed >= self.duration_slots {
+            return Some(self.total_amount);
+        }
+
+        mul_div(self.total_amount, elapsed, self.duration_slots)
+    }
+
+    /// Amount the beneficiary can claim at `slot`
+    pub fn claimable_amount(&self, slot: u64) -> Option<u64> {
+        self.unlocked_amount(slot)?.checked_sub(self.claimed_amount)
+    }
+}
@@ -0,0 +1,240 @@
+//! Token-2022 `TransferFeeConfig` extension, not covered by `pinocchio_token_2022` yet
+//!
+//! A launched mint either has no extension or a transfer fee whose config authority was revoked at
+//! launch, so the fee of a mint never changes once the curve exists.
+
+use crate::require;
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::{invoke, invoke_signed, slice_invoke_signed},
+    instruction::{AccountMeta, Instruction, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+use pinocchio_token_2022::ID as TOKEN_PROGRAM_ID;
+
+/// `TokenInstruction::TransferFeeExtension`
+const TRANSFER_FEE_EXTENSION: u8 = 26;
+const INITIALIZE_TRANSFER_FEE_CONFIG: u8 = 0;
+const WITHDRAW_WITHHELD_TOKENS_FROM_MINT: u8 = 2;
+const HARVEST_WITHHELD_TOKENS_TO_MINT: u8 = 4;
+
+/// Extensions start after the base account padded to a token account and the account type
+const ACCOUNT_TYPE_OFFSET: usize = 165;
+pub const EXTENSIONS_OFFSET: usize = ACCOUNT_TYPE_OFFSET + 1;
+const ACCOUNT_TYPE_MINT: u8 = 1;
+const EXTENSION_TYPE_UNINITIALIZED: u16 = 0;
+const EXTENSION_TYPE_TRANSFER_FEE_CONFIG: u16 = 1;
+/// Both authorities, the withheld amount and the older and newer `TransferFee`
+const TRANSFER_FEE_CONFIG_LEN: usize = 108;
+/// `TransferFee { epoch, maximum_fee, transfer_fee_basis_points }` applying from its epoch on
+const NEWER_TRANSFER_FEE_OFFSET: usize = 90;
+
+/// Space the `TransferFeeConfig` extension takes in a mint, its type and length included
+pub const TRANSFER_FEE_CONFIG_TLV_LEN: usize = 4 + TRANSFER_FEE_CONFIG_LEN;
+
+/// Space of a mint carrying only the `TransferFeeConfig` extension
+pub const MINT_WITH_TRANSFER_FEE_LEN: usize = EXTENSIONS_OFFSET + TRANSFER_FEE_CONFIG_TLV_LEN;
+
+/// Most source accounts `HarvestWithheldTokensToMint` takes in one instruction
+pub const MAX_HARVEST_SOURCES: usize = 16;
+
+pub const MAX_FEE_BASIS_POINTS: u16 = 10_000;
+
+/// Transfer fee of a mint, as set when the curve was launched
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransferFee {
+    pub basis_points: u16,
+    pub maximum_fee: u64,
+}
+
+impl TransferFee {
+    /// Reads the fee of the `TransferFeeConfig` extension, no fee when the mint has none
+    pub fn from_mint(mint: &AccountInfo) -> Result<Self, ProgramError> {
+        require(
+            mint.is_owned_by(&TOKEN_PROGRAM_ID),
+            ProgramError::InvalidAccountOwner,
+        )?;
+
+        let data = mint.try_borrow_data()?;
+
+        Self::from_mint_data(&data)
+    }
+
+    pub fn from_mint_data(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() <= ACCOUNT_TYPE_OFFSET {
+            return Ok(Self::default());
+        }
+
+        require(
+            data[ACCOUNT_TYPE_OFFSET] == ACCOUNT_TYPE_MINT,
+            ProgramError::InvalidAccountData,
+        )?;
+
+        let mut offset = EXTENSIONS_OFFSET;
+
+        while let Some(header) = data.get(offset..offset + 4) {
+            let extension_type = u16::from_le_bytes([header[0], header[1]]);
+            let length = u16::from_le_bytes([header[2], header[3]]) as usize;
+            let value = data
+                .get(offset + 4..offset + 4 + length)
+                .ok_or(ProgramError::InvalidAccountData)?;
+
+            match extension_type {
+                EXTENSION_TYPE_UNINITIALIZED => break,
+                EXTENSION_TYPE_TRANSFER_FEE_CONFIG if length == TRANSFER_FEE_CONFIG_LEN => {
+                    let fee = &value[NEWER_TRANSFER_FEE_OFFSET..];
+                    let mut maximum_fee = [0u8; 8];
+                    maximum_fee.copy_from_slice(&fee[8..16]);
+
+                    return Ok(Self {
+                        basis_points: u16::from_le_bytes([fee[16], fee[17]]),
+                        maximum_fee: u64::from_le_bytes(maximum_fee),
+                    });
+                }
+                _ => offset += 4 + length,
+            }
+        }
+
+        Ok(Self::default())
+    }
+
+    /// Fee withheld on a transfer of `amount`, rounded up and capped at `maximum_fee` like
+    /// Token-2022 does
+    pub fn calculate(&self, amount: u64) -> Option<u64> {
+        if self.basis_points == 0 || amount == 0 {
+            return Some(0);
+        }
+
+        let fee = (amount as u128)
+            .checked_mul(self.basis_points as u128)?
+            .div_ceil(MAX_FEE_BASIS_POINTS as u128);
+
+        Some(u64::try_from(fee).ok()?.min(self.maximum_fee))
+    }
+
+    /// What the receiver of a transfer of `amount` ends up with
+    pub fn net_amount(&self, amount: u64) -> Option<u64> {
+        amount.checked_sub(self.calculate(amount)?)
+    }
+}
+
+/// Initializes the `TransferFeeConfig` extension of an uninitialized mint, the fee can't be
+/// changed afterwards
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint to initialize.
+pub struct InitializeTransferFeeConfig<'a> {
+    pub mint: &'a AccountInfo,
+    /// Authority allowed to withdraw the withheld fees
+    pub withdraw_withheld_authority: &'a Pubkey,
+    pub transfer_fee_basis_points: u16,
+    pub maximum_fee: u64,
+}
+
+impl InitializeTransferFeeConfig<'_> {
+    pub fn invoke(&self) -> ProgramResult {
+        let account_metas = [AccountMeta::writable(self.mint.key())];
+
+        // Instruction data layout:
+        // -  [0..2]: extension and instruction discriminators
+        // -  [2]: no transfer fee config authority
+        // -  [3..36]: withdraw withheld authority (COption<Pubkey>)
+        // -  [36..38]: transfer fee basis points (u16)
+        // -  [38..46]: maximum fee (u64)
+        let mut instruction_data = [0u8; 46];
+        instruction_data[0] = TRANSFER_FEE_EXTENSION;
+        instruction_data[1] = INITIALIZE_TRANSFER_FEE_CONFIG;
+        instruction_data[3] = 1;
+        instruction_data[4..36].copy_from_slice(self.withdraw_withheld_authority);
+        instruction_data[36..38].copy_from_slice(&self.transfer_fee_basis_points.to_le_bytes());
+        instruction_data[38..46].copy_from_slice(&self.maximum_fee.to_le_bytes());
+
+        let instruction = Instruction {
+            program_id: &TOKEN_PROGRAM_ID,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke(&instruction, &[self.mint])
+    }
+}
+
+/// Moves the fees withheld in token accounts of a mint to the mint, anyone can harvest
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint.
+///   1. ..`[WRITE]` The token accounts to harvest from.
+pub struct HarvestWithheldTokensToMint<'a, 'b> {
+    pub mint: &'a AccountInfo,
+    /// At most `MAX_HARVEST_SOURCES` token accounts of `mint`
+    pub sources: &'b [AccountInfo],
+}
+
+impl HarvestWithheldTokensToMint<'_, '_> {
+    pub fn invoke(&self) -> ProgramResult {
+        require(
+            self.sources.len() <= MAX_HARVEST_SOURCES,
+            ProgramError::InvalidArgument,
+        )?;
+
+        let accounts_len = 1 + self.sources.len();
+
+        let account_metas: [AccountMeta; 1 + MAX_HARVEST_SOURCES] =
+            core::array::from_fn(|index| match index {
+                0 => AccountMeta::writable(self.mint.key()),
+                index if index < accounts_len => {
+                    AccountMeta::writable(self.sources[index - 1].key())
+                }
+                _ => AccountMeta::readonly(self.mint.key()),
+            });
+        let account_infos: [&AccountInfo; 1 + MAX_HARVEST_SOURCES] =
+            core::array::from_fn(|index| match index {
+                index if index > 0 && index < accounts_len => &self.sources[index - 1],
+                _ => self.mint,
+            });
+
+        let instruction = Instruction {
+            program_id: &TOKEN_PROGRAM_ID,
+            accounts: &account_metas[..accounts_len],
+            data: &[TRANSFER_FEE_EXTENSION, HARVEST_WITHHELD_TOKENS_TO_MINT],
+        };
+
+        slice_invoke_signed(&instruction, &account_infos[..accounts_len], &[])
+    }
+}
+
+/// Withdraws the fees harvested to a mint
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint.
+///   1. `[WRITE]` The token account receiving the fees.
+///   2. `[SIGNER]` The withdraw withheld authority of the mint.
+pub struct WithdrawWithheldTokensFromMint<'a> {
+    pub mint: &'a AccountInfo,
+    pub destination: &'a AccountInfo,
+    pub authority: &'a AccountInfo,
+}
+
+impl WithdrawWithheldTokensFromMint<'_> {
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_metas = [
+            AccountMeta::writable(self.mint.key()),
+            AccountMeta::writable(self.destination.key()),
+            AccountMeta::readonly_signer(self.authority.key()),
+        ];
+
+        let instruction = Instruction {
+            program_id: &TOKEN_PROGRAM_ID,
+            accounts: &account_metas,
+            data: &[TRANSFER_FEE_EXTENSION, WITHDRAW_WITHHELD_TOKENS_FROM_MINT],
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.mint, self.destination, self.authority],
+            signers,
+        )
+    }
+}
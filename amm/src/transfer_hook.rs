@@ -0,0 +1,58 @@
+//! Token-2022 `TransferHook` extension, not covered by `pinocchio_token_2022` yet
+//!
+//! Token-2022 invokes the hook program of a mint on every transfer and looks for the accounts the
+//! hook needs among the remaining accounts of the transfer. Instructions moving curve tokens take
+//! those accounts, already resolved by the client from the mint's extra account metas, after their
+//! own accounts and forward them untouched with `program_utils::token::TransferChecked`.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::invoke,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    ProgramResult,
+};
+use pinocchio_token_2022::ID as TOKEN_PROGRAM_ID;
+
+/// `TokenInstruction::TransferHookExtension`
+const TRANSFER_HOOK_EXTENSION: u8 = 36;
+const INITIALIZE_TRANSFER_HOOK: u8 = 0;
+/// Authority and program id of the hook
+const TRANSFER_HOOK_LEN: usize = 64;
+
+/// Space the `TransferHook` extension takes in a mint, its type and length included
+pub const TRANSFER_HOOK_TLV_LEN: usize = 4 + TRANSFER_HOOK_LEN;
+
+/// Initializes the `TransferHook` extension of an uninitialized mint, nobody can change the hook
+/// program afterwards
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint to initialize.
+pub struct InitializeTransferHook<'a> {
+    pub mint: &'a AccountInfo,
+    /// Program invoked on every transfer of the mint
+    pub program_id: &'a Pubkey,
+}
+
+impl InitializeTransferHook<'_> {
+    pub fn invoke(&self) -> ProgramResult {
+        let account_metas = [AccountMeta::writable(self.mint.key())];
+
+        // Instruction data layout:
+        // -  [0..2]: extension and instruction discriminators
+        // -  [2..34]: authority (OptionalNonZeroPubkey), zeroed for none
+        // -  [34..66]: hook program id (OptionalNonZeroPubkey)
+        let mut instruction_data = [0u8; 66];
+        instruction_data[0] = TRANSFER_HOOK_EXTENSION;
+        instruction_data[1] = INITIALIZE_TRANSFER_HOOK;
+        instruction_data[34..66].copy_from_slice(self.program_id);
+
+        let instruction = Instruction {
+            program_id: &TOKEN_PROGRAM_ID,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke(&instruction, &[self.mint])
+    }
+}
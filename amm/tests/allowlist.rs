@@ -0,0 +1,93 @@
+#[cfg(test)]
+pub mod allowlist_tests {
+    use amm::states::allowlist::{allowlist_leaf, allowlist_node, Allowlist};
+    use proptest::prelude::*;
+
+    /// Builds the tree of `leaves` bottom up, an odd node is carried to the next level as is
+    fn get_levels(leaves: Vec<[u8; 32]>) -> Vec<Vec<[u8; 32]>> {
+        let mut levels = vec![leaves];
+
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => allowlist_node(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+
+            levels.push(next);
+        }
+
+        levels
+    }
+
+    fn get_proof(levels: &[Vec<[u8; 32]>], mut index: usize) -> Vec<[u8; 32]> {
+        let mut proof = vec![];
+
+        for level in &levels[..levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+
+        proof
+    }
+
+    #[test]
+    fn test_root_and_end_slot_are_set_together() {
+        let root = allowlist_leaf(&[0x1; 32], 0);
+
+        assert!(Allowlist::default().validate().is_ok());
+        assert!(Allowlist { root, end_slot: 10 }.validate().is_ok());
+        assert!(Allowlist { root, end_slot: 0 }.validate().is_err());
+        assert!(Allowlist {
+            root: [0; 32],
+            end_slot: 10
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_allowlist_is_active_until_end_slot() {
+        let allowlist = Allowlist {
+            root: allowlist_leaf(&[0x1; 32], 0),
+            end_slot: 10,
+        };
+
+        assert!(allowlist.is_active(9));
+        assert!(!allowlist.is_active(10));
+        assert!(!Allowlist::default().is_active(0));
+    }
+
+    proptest! {
+        #[test]
+        fn test_every_entry_proves_its_own_leaf(
+            entries in prop::collection::vec((any::<[u8; 32]>(), any::<u64>()), 1..40),
+            index in any::<prop::sample::Index>(),
+        ) {
+            let leaves = entries
+                .iter()
+                .map(|(wallet, max_buy)| allowlist_leaf(wallet, *max_buy))
+                .collect();
+            let levels = get_levels(leaves);
+            let allowlist = Allowlist {
+                root: levels.last().unwrap()[0],
+                end_slot: 1,
+            };
+
+            let index = index.index(entries.len());
+            let (wallet, max_buy) = entries[index];
+            let proof = get_proof(&levels, index);
+
+            prop_assert!(allowlist.verify(&wallet, max_buy, &proof));
+            // The limit is part of the leaf, a buyer can't raise it
+            prop_assert!(!allowlist.verify(&wallet, max_buy.wrapping_add(1), &proof));
+        }
+    }
+}
@@ -0,0 +1,336 @@
+#[cfg(test)]
+pub mod bonding_curve_invariant_tests {
+    use amm::{
+        states::{
+            allowlist::Allowlist,
+            bonding_curve::{BondingCurve, CurveStatus},
+            crowdfund::Crowdfund,
+        },
+        Discriminator,
+    };
+    use proptest::prelude::*;
+
+    const DECIMALS: u8 = 6;
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    #[derive(Debug, Clone)]
+    enum Trade {
+        /// Buy with the given amount of lamports
+        Buy(u64),
+        /// Sell the given share (in bps) of the trader's token holdings
+        Sell(u64),
+    }
+
+    /// Curve state and the balances the curve accounts would hold after every trade
+    struct Simulation {
+        curve: BondingCurve,
+        curve_ata_balance: u64,
+        sol_escrow_lamports: u64,
+        trader_tokens: u64,
+    }
+
+    impl Simulation {
+        fn new() -> Self {
+            let curve = get_curve();
+
+            Simulation {
+                curve_ata_balance: curve.real_token_reserves,
+                curve,
+                sol_escrow_lamports: 0,
+                trader_tokens: 0,
+            }
+        }
+
+        fn k(&self) -> u128 {
+            self.curve.virtual_sol_reserves as u128 * self.curve.virtual_token_reserves as u128
+        }
+
+        /// Applies a trade the way the program would, a failed trade leaves the state untouched
+        fn apply(&mut self, trade: &Trade) {
+            // Swaps are rejected once the curve is complete
+            if self.curve.status == CurveStatus::Completed as u8 {
+                return;
+            }
+
+            let mut curve = self.curve;
+
+            match *trade {
+                Trade::Buy(sol_amount) => {
+                    if let Ok(result) = curve.apply_buy(sol_amount, DECIMALS, 0) {
+                        self.curve = curve;
+                        self.curve_ata_balance -= result.token_amount;
+                        self.sol_escrow_lamports += result.sol_amount;
+                        self.trader_tokens += result.token_amount;
+                    }
+                }
+                Trade::Sell(share_bps) => {
+                    let token_amount = self.trader_tokens * share_bps / 10_000;
+
+                    if token_amount == 0 {
+                        return;
+                    }
+
+                    if let Ok(result) = curve.apply_sell(token_amount, DECIMALS, 0) {
+                        self.curve = curve;
+                        self.curve_ata_balance += result.token_amount;
+                        self.sol_escrow_lamports -= result.sol_amount;
+                        self.trader_tokens -= result.token_amount;
+                    }
+                }
+            }
+        }
+    }
+
+    fn get_curve() -> BondingCurve {
+        BondingCurve {
+            discriminator: BondingCurve::DISCRIMINATOR,
+            version: BondingCurve::VERSION,
+            status: CurveStatus::Active as u8,
+            bump: 255,
+            vault_mode: BondingCurve::FROZEN_VAULT,
+            _padding: [0u8; 2],
+            max_wallet_bps: 0,
+            mint: [0x1; 32],
+            creator: [0x2; 32],
+            initial_real_token_reserves: 793_100_000_000_000,
+            virtual_sol_reserves: 30_000_000_000,
+            virtual_token_reserves: 1_073_000_000_000_000,
+            real_sol_reserves: 0,
+            real_token_reserves: 793_100_000_000_000,
+            token_total_supply: 1_000_000_000_000_000,
+            starting_slot: 0,
+            allowlist: Allowlist::default(),
+            crowdfund: Crowdfund::default(),
+        }
+    }
+
+    fn trade_strategy() -> impl Strategy<Value = Trade> {
+        prop_oneof![
+            (1..=100 * LAMPORTS_PER_SOL).prop_map(Trade::Buy),
+            (1..=10_000u64).prop_map(Trade::Sell),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_k_never_decreases(trades in prop::collection::vec(trade_strategy(), 1..50)) {
+            let mut sim = Simulation::new();
+
+            for trade in trades.iter() {
+                let k_before = sim.k();
+                sim.apply(trade);
+
+                prop_assert!(sim.k() >= k_before, "k decreased after {:?}", trade);
+            }
+        }
+
+        #[test]
+        fn test_buy_then_sell_never_profits(
+            trades in prop::collection::vec(trade_strategy(), 0..20),
+            sol_in in 1..=100 * LAMPORTS_PER_SOL,
+        ) {
+            let mut sim = Simulation::new();
+
+            for trade in trades.iter() {
+                sim.apply(trade);
+            }
+
+            prop_assume!(sim.curve.status == CurveStatus::Active as u8);
+
+            let mut curve = sim.curve;
+            let buy_result = curve.apply_buy(sol_in, DECIMALS, 0);
+            prop_assume!(buy_result.is_ok());
+            let buy_result = buy_result.unwrap();
+
+            if let Ok(sell_result) = curve.apply_sell(buy_result.token_amount, DECIMALS, 0) {
+                prop_assert!(sell_result.sol_amount <= buy_result.sol_amount);
+            }
+        }
+
+        #[test]
+        fn test_real_token_reserves_never_negative(
+            trades in prop::collection::vec(trade_strategy(), 1..50)
+        ) {
+            let mut sim = Simulation::new();
+            let initial_reserves = sim.curve.real_token_reserves as i128;
+
+            for trade in trades.iter() {
+                sim.apply(trade);
+
+                let expected_reserves = initial_reserves - sim.trader_tokens as i128;
+
+                prop_assert!(expected_reserves >= 0);
+                prop_assert_eq!(sim.curve.real_token_reserves as i128, expected_reserves);
+            }
+        }
+
+        #[test]
+        fn test_complete_set_exactly_when_reserves_empty(
+            trades in prop::collection::vec(trade_strategy(), 1..50)
+        ) {
+            let mut sim = Simulation::new();
+
+            for trade in trades.iter() {
+                sim.apply(trade);
+
+                prop_assert_eq!(sim.curve.status == CurveStatus::Completed as u8, sim.curve.real_token_reserves == 0);
+            }
+        }
+
+        #[test]
+        fn test_invariant_holds_against_balances(
+            trades in prop::collection::vec(trade_strategy(), 1..50)
+        ) {
+            let mut sim = Simulation::new();
+
+            for trade in trades.iter() {
+                sim.apply(trade);
+
+                // The curve ATA is frozen again at the end of every swap while the curve is active
+                let ata_frozen = sim.curve.status == CurveStatus::Active as u8;
+
+                prop_assert!(sim
+                    .curve
+                    .check_balances(sim.curve_ata_balance, sim.sol_escrow_lamports, ata_frozen)
+                    .is_ok());
+                prop_assert!(sim.sol_escrow_lamports >= sim.curve.real_sol_reserves);
+            }
+        }
+
+        #[test]
+        fn test_price_impact_grows_with_buy_size(
+            small in 1..=50 * LAMPORTS_PER_SOL,
+            extra in 0..=50 * LAMPORTS_PER_SOL,
+        ) {
+            let curve = get_curve();
+
+            let impact = |sol_amount: u64| {
+                let mut curve = curve;
+                let result = curve.apply_buy(sol_amount, DECIMALS, 0).unwrap();
+
+                get_curve()
+                    .price_impact_bps(result.sol_amount, result.token_amount, true)
+                    .unwrap()
+            };
+
+            let small_impact = impact(small);
+
+            prop_assert!(small_impact <= 10_000);
+            prop_assert!(impact(small + extra) >= small_impact);
+        }
+
+        #[test]
+        fn test_price_impact_limit_bounds_every_trade(
+            trades in prop::collection::vec(trade_strategy(), 1..50),
+            max_price_impact_bps in 1..=10_000u16,
+        ) {
+            let mut curve = get_curve();
+            let mut trader_tokens = 0;
+
+            for trade in trades.iter() {
+                let before = curve;
+
+                let result = match trade {
+                    Trade::Buy(sol_amount) => curve
+                        .apply_buy(*sol_amount, DECIMALS, max_price_impact_bps)
+                        .map(|result| trader_tokens += result.token_amount),
+                    Trade::Sell(share_bps) => {
+                        let token_amount = trader_tokens * share_bps / 10_000;
+
+                        if token_amount == 0 {
+                            continue;
+                        }
+
+                        curve
+                            .apply_sell(token_amount, DECIMALS, max_price_impact_bps)
+                            .map(|result| trader_tokens -= result.token_amount)
+                    }
+                };
+
+                match result {
+                    Ok(()) => prop_assert!(
+                        curve.spot_price_change_bps(&before).unwrap() <= max_price_impact_bps as u64
+                    ),
+                    // A rejected trade leaves the curve untouched
+                    Err(_) => prop_assert_eq!(bytemuck::bytes_of(&curve), bytemuck::bytes_of(&before)),
+                }
+            }
+        }
+
+        #[test]
+        fn test_only_unfrozen_vault_tolerates_donations(
+            trades in prop::collection::vec(trade_strategy(), 1..50),
+            donation in 1..=1_000_000_000_000u64,
+        ) {
+            let mut sim = Simulation::new();
+
+            for trade in trades.iter() {
+                sim.apply(trade);
+            }
+
+            let donated_balance = sim.curve_ata_balance + donation;
+            let ata_frozen = sim.curve.status == CurveStatus::Active as u8;
+
+            prop_assert!(sim
+                .curve
+                .check_balances(donated_balance, sim.sol_escrow_lamports, ata_frozen)
+                .is_err());
+
+            let mut curve = sim.curve;
+            curve.vault_mode = BondingCurve::UNFROZEN_VAULT;
+
+            prop_assert!(curve
+                .check_balances(donated_balance, sim.sol_escrow_lamports, false)
+                .is_ok());
+        }
+
+        #[test]
+        fn test_status_only_moves_along_lifecycle(
+            steps in prop::collection::vec(0u8..6, 1..30),
+            slot in 0..200u64,
+        ) {
+            let mut curve = get_curve();
+            let mut reached_completed = false;
+
+            for step in steps {
+                let before = curve.status().unwrap();
+                let next = CurveStatus::try_from(step).unwrap();
+
+                match curve.transition(next) {
+                    Ok(()) => prop_assert!(before.can_transition_to(next)),
+                    Err(_) => prop_assert_eq!(curve.status().unwrap(), before),
+                }
+
+                reached_completed |= curve.status().unwrap() == CurveStatus::Completed;
+
+                // Nothing brings a finished curve back to trading
+                if reached_completed {
+                    prop_assert!(curve.require_tradable(slot).is_err());
+                }
+            }
+        }
+
+        #[test]
+        fn test_max_wallet_cap_lifts_on_completion(
+            max_wallet_bps in 1..=10_000u16,
+            buys in prop::collection::vec(1..=100 * LAMPORTS_PER_SOL, 1..50),
+        ) {
+            let mut curve = get_curve();
+            curve.max_wallet_bps = max_wallet_bps;
+
+            let cap = curve.token_total_supply / 10_000 * max_wallet_bps as u64;
+
+            for lamports in buys {
+                if curve.apply_buy(lamports, DECIMALS, 0).is_err() {
+                    break;
+                }
+
+                if curve.status().unwrap() == CurveStatus::Completed {
+                    prop_assert_eq!(curve.max_wallet_amount(), Ok(None));
+                } else {
+                    prop_assert_eq!(curve.max_wallet_amount(), Ok(Some(cap)));
+                }
+            }
+        }
+    }
+}
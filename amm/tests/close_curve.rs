@@ -0,0 +1,100 @@
+mod helpers;
+
+#[cfg(test)]
+pub mod close_curve_tests {
+    use super::*;
+    use amm::{AmmError, ID};
+    use helpers::{
+        get_mollusk, ix_configs::close_curve_configs::get_close_curve_configs, ReturnVal,
+    };
+    use mollusk_svm::result::Check;
+    use solana_sdk::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
+    use spl_token::{solana_program::program_pack::Pack, state::Mint};
+
+    #[test]
+    pub fn test_close_curve_returns_rent_to_creator() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_close_curve_configs(&mollusk, &program_id, 0, 0);
+
+        let (creator, creator_account) = &account_infos[0];
+        let reclaimed: u64 = [2, 4, 5]
+            .iter()
+            .map(|index| account_infos[*index].1.lamports)
+            .sum();
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&account_infos[2].0).closed().build(),
+                Check::account(&account_infos[4].0).closed().build(),
+                Check::account(&account_infos[5].0).closed().build(),
+                Check::account(creator)
+                    .lamports(creator_account.lamports + reclaimed)
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    pub fn test_close_curve_burns_leftover_tokens() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+        let leftover_tokens = 1_000_000;
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_close_curve_configs(&mollusk, &program_id, 0, leftover_tokens);
+
+        let (mint, mint_account) = &account_infos[3];
+        let supply = Mint::unpack(&mint_account.data).unwrap().supply;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let result = mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&account_infos[4].0).closed().build(),
+            ],
+        );
+
+        let mint_data = Mint::unpack(&result.get_account(mint).unwrap().data).unwrap();
+
+        assert_eq!(mint_data.supply, supply - leftover_tokens);
+    }
+
+    #[test]
+    pub fn test_close_curve_fails_with_reserves_left() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_close_curve_configs(&mollusk, &program_id, 1, 0);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::ReservesNotEmpty as u32,
+            ))],
+        );
+    }
+}
@@ -0,0 +1,169 @@
+#[cfg(test)]
+pub mod crowdfund_tests {
+    use amm::{
+        states::{
+            allowlist::Allowlist,
+            bonding_curve::{BondingCurve, CurveStatus},
+            crowdfund::Crowdfund,
+        },
+        AmmError, Discriminator,
+    };
+    use pinocchio::program_error::ProgramError;
+    use proptest::prelude::*;
+
+    const DECIMALS: u8 = 6;
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+    const DEADLINE_SLOT: u64 = 1_000;
+
+    /// Crowdfund curve targeting 10 SOL, after buys worth `raised_sol` lamports
+    fn get_curve(raised_sol: u64) -> BondingCurve {
+        let mut curve = BondingCurve {
+            discriminator: BondingCurve::DISCRIMINATOR,
+            version: BondingCurve::VERSION,
+            status: CurveStatus::Active as u8,
+            bump: 255,
+            vault_mode: BondingCurve::FROZEN_VAULT,
+            _padding: [0u8; 2],
+            max_wallet_bps: 0,
+            mint: [0x1; 32],
+            creator: [0x2; 32],
+            initial_real_token_reserves: 793_100_000_000_000,
+            virtual_sol_reserves: 30_000_000_000,
+            virtual_token_reserves: 1_073_000_000_000_000,
+            real_sol_reserves: 0,
+            real_token_reserves: 793_100_000_000_000,
+            token_total_supply: 1_000_000_000_000_000,
+            starting_slot: 0,
+            allowlist: Allowlist::default(),
+            crowdfund: Crowdfund {
+                target_sol: 10 * LAMPORTS_PER_SOL,
+                deadline_slot: DEADLINE_SLOT,
+            },
+        };
+
+        if raised_sol > 0 {
+            curve.apply_buy(raised_sol, DECIMALS, 0).unwrap();
+        }
+
+        curve
+    }
+
+    #[test]
+    fn test_target_and_deadline_are_set_together() {
+        assert!(Crowdfund::default().validate().is_ok());
+        assert!(Crowdfund {
+            target_sol: 1,
+            deadline_slot: 10
+        }
+        .validate()
+        .is_ok());
+        assert!(Crowdfund {
+            target_sol: 1,
+            deadline_slot: 0
+        }
+        .validate()
+        .is_err());
+        assert!(Crowdfund {
+            target_sol: 0,
+            deadline_slot: 10
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_missed_target_stops_trading_at_deadline() {
+        let mut curve = get_curve(LAMPORTS_PER_SOL);
+
+        assert!(curve.require_tradable(DEADLINE_SLOT - 1).is_ok());
+        assert_eq!(
+            curve.require_tradable(DEADLINE_SLOT),
+            Err(ProgramError::Custom(AmmError::CrowdfundFailed as u32))
+        );
+        assert_eq!(
+            curve.fail_crowdfund(DEADLINE_SLOT - 1),
+            Err(ProgramError::Custom(AmmError::CrowdfundNotFailed as u32))
+        );
+
+        curve.fail_crowdfund(DEADLINE_SLOT).unwrap();
+
+        assert_eq!(curve.status, CurveStatus::Failed as u8);
+        assert_eq!(
+            curve.require_tradable(DEADLINE_SLOT),
+            Err(ProgramError::Custom(AmmError::CrowdfundFailed as u32))
+        );
+    }
+
+    #[test]
+    fn test_reached_target_trades_on_as_regular_curve() {
+        let mut curve = get_curve(20 * LAMPORTS_PER_SOL);
+
+        assert!(curve.require_tradable(DEADLINE_SLOT).is_ok());
+        assert_eq!(curve.crowdfund, Crowdfund::default());
+        assert_eq!(
+            curve.fail_crowdfund(DEADLINE_SLOT),
+            Err(ProgramError::Custom(AmmError::CrowdfundNotFailed as u32))
+        );
+    }
+
+    #[test]
+    fn test_allocation_stays_locked_until_the_crowdfund_settles() {
+        let mut missed = get_curve(LAMPORTS_PER_SOL);
+        let reached = get_curve(20 * LAMPORTS_PER_SOL);
+
+        assert_eq!(missed.crowdfund_settled(DEADLINE_SLOT - 1), Ok(false));
+        assert_eq!(reached.crowdfund_settled(DEADLINE_SLOT - 1), Ok(false));
+        assert_eq!(reached.crowdfund_settled(DEADLINE_SLOT), Ok(true));
+        assert_eq!(missed.crowdfund_settled(DEADLINE_SLOT), Ok(false));
+
+        missed.fail_crowdfund(DEADLINE_SLOT).unwrap();
+
+        assert_eq!(missed.crowdfund_settled(DEADLINE_SLOT), Ok(false));
+
+        let mut regular = get_curve(0);
+        regular.crowdfund = Crowdfund::default();
+
+        assert_eq!(regular.crowdfund_settled(0), Ok(true));
+    }
+
+    proptest! {
+        #[test]
+        fn test_refunds_pay_out_the_reserves(
+            raised_sol in 1..10 * LAMPORTS_PER_SOL,
+            shares_bps in prop::collection::vec(1..=10_000u64, 1..10),
+        ) {
+            let mut curve = get_curve(raised_sol);
+            curve.fail_crowdfund(DEADLINE_SLOT).unwrap();
+
+            let reserves = curve.real_sol_reserves;
+            let mut held = curve.initial_real_token_reserves - curve.real_token_reserves;
+            let mut refunded = 0;
+
+            for share_bps in shares_bps {
+                let token_amount = held * share_bps / 10_000;
+
+                if token_amount == 0 {
+                    continue;
+                }
+
+                let sol_before = curve.real_sol_reserves;
+                let refund = curve.apply_refund(token_amount).unwrap();
+
+                // No holder gets more than their share of what is left
+                prop_assert!(refund as u128 * held as u128 <= sol_before as u128 * token_amount as u128);
+
+                held -= token_amount;
+                refunded += refund;
+            }
+
+            // The last holder takes whatever rounding left behind
+            if held > 0 {
+                refunded += curve.apply_refund(held).unwrap();
+            }
+
+            prop_assert_eq!(refunded, reserves);
+            prop_assert_eq!(curve.real_token_reserves, curve.initial_real_token_reserves);
+            prop_assert!(curve.apply_refund(1).is_none());
+        }
+    }
+}
@@ -0,0 +1,83 @@
+mod helpers;
+
+#[cfg(test)]
+pub mod graduate_tests {
+    use super::*;
+    use amm::{
+        states::bonding_curve::{BondingCurve, CurveStatus},
+        AmmError, ID,
+    };
+    use helpers::{
+        get_mollusk,
+        ix_configs::{graduate_configs::get_graduate_configs, swap_configs::SWAP_SLOT},
+        ReturnVal,
+    };
+    use mollusk_svm::result::Check;
+    use solana_sdk::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
+
+    #[test]
+    pub fn test_graduate_works() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let (
+            expected_curve,
+            ReturnVal {
+                account_infos,
+                account_meta,
+                ix_data,
+            },
+        ) = get_graduate_configs(&mollusk, &program_id);
+
+        let curve_pda = account_infos[2].0;
+        let curve_reserves = bytemuck::pod_read_unaligned::<BondingCurve>(&account_infos[2].1.data)
+            .real_sol_reserves;
+        let (treasury, treasury_account) = &account_infos[7];
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&curve_pda)
+                    .data(bytemuck::bytes_of(&expected_curve))
+                    .build(),
+                Check::account(treasury)
+                    .lamports(treasury_account.lamports + curve_reserves)
+                    .build(),
+                Check::all_rent_exempt(),
+            ],
+        );
+    }
+
+    #[test]
+    pub fn test_graduate_fails_for_active_curve() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let (
+            mut curve,
+            ReturnVal {
+                mut account_infos,
+                account_meta,
+                ix_data,
+            },
+        ) = get_graduate_configs(&mollusk, &program_id);
+
+        curve.status = CurveStatus::Active as u8;
+        account_infos[2].1.data = bytemuck::bytes_of(&curve).to_vec();
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::CurveNotComplete as u32,
+            ))],
+        );
+    }
+}
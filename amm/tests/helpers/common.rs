@@ -1,4 +1,5 @@
 use super::structs::SystemConfig;
+use amm::states::treasury::Treasury;
 use mollusk_svm::Mollusk;
 use solana_sdk::{
     account::{Account, WritableAccount},
@@ -30,6 +31,14 @@ pub fn get_mollusk(program_id: &Pubkey) -> Mollusk {
     mollusk
 }
 
+/// Address of the treasury PDA, the fee receiver of every test config
+pub fn get_treasury_pubkey() -> Pubkey {
+    let (treasury, _) =
+        Pubkey::find_program_address(&[Treasury::SEED_PREFIX], &Pubkey::new_from_array(amm::ID));
+
+    treasury
+}
+
 pub fn find_deterministic_pubkey(id: &str) -> Pubkey {
     assert!(id.len() <= 32, "id too long, must be <= 32");
 
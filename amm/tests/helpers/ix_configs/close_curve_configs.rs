@@ -0,0 +1,123 @@
+use crate::helpers::{
+    find_deterministic_pubkey, get_ata_accounts, get_ata_config, get_mint_accounts,
+    get_program_configs,
+    ix_configs::{graduate_configs::get_complete_curve, swap_configs::get_global_config},
+    to_spl_pubkey, ReturnVal, SystemConfig,
+};
+use amm::states::{
+    bonding_curve::{BondingCurve, CurveStatus},
+    global_config::GlobalConfig,
+};
+use mollusk_svm::Mollusk;
+use solana_sdk::{
+    account::Account, message::AccountMeta, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+};
+use spl_token::{solana_program::program_option::COption, state::Mint};
+
+/// Get the accounts for the creator closing a graduated curve
+///
+/// Arguments:
+/// - `real_sol_reserves`: SOL reserves the curve still accounts for, anything above 0 must fail
+/// - `leftover_tokens`: tokens sent to the curve ATA after graduation, burned by the close
+pub fn get_close_curve_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    real_sol_reserves: u64,
+    leftover_tokens: u64,
+) -> ReturnVal {
+    let SystemConfig {
+        system_config: (system_program, system_program_account),
+        token_config: (token_program, token_program_account),
+        associated_program_config: _,
+    } = get_program_configs();
+
+    let rent = &mollusk.sysvars.rent;
+    let global_field = get_global_config();
+
+    let creator = find_deterministic_pubkey("creator");
+    let creator_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let mint = find_deterministic_pubkey("mint");
+
+    let (global_config, _) =
+        Pubkey::find_program_address(&[GlobalConfig::GLOBAL_PEFIX], program_id);
+    let mut global_account = Account::new(
+        rent.minimum_balance(GlobalConfig::SIZE),
+        GlobalConfig::SIZE,
+        program_id,
+    );
+    global_account.data = bytemuck::bytes_of(&global_field).to_vec();
+
+    let mut curve = get_complete_curve(program_id, &mint, &creator);
+    curve.real_sol_reserves = real_sol_reserves;
+    curve.status = CurveStatus::Graduated as u8;
+
+    let (curve_pda, _) =
+        Pubkey::find_program_address(&[BondingCurve::SEED_PREFIX, mint.as_ref()], program_id);
+    let mut curve_account = Account::new(
+        rent.minimum_balance(BondingCurve::CURVE_SIZE),
+        BondingCurve::CURVE_SIZE,
+        program_id,
+    );
+    curve_account.data = bytemuck::bytes_of(&curve).to_vec();
+
+    let (sol_escrow_pda, _) = Pubkey::find_program_address(
+        &[BondingCurve::SOL_ESCROW_SEED_PREFIX, mint.as_ref()],
+        program_id,
+    );
+    let sol_escrow_account = Account::new(
+        rent.minimum_balance(0) + real_sol_reserves,
+        0,
+        &system_program,
+    );
+
+    let mint_config = Mint {
+        decimals: global_field.mint_decimals,
+        freeze_authority: COption::Some(to_spl_pubkey(&curve_pda)),
+        is_initialized: true,
+        mint_authority: COption::None,
+        supply: global_field.token_total_supply,
+    };
+    let (_, mint_account) = get_mint_accounts(None, mollusk, mint_config);
+
+    // Graduation emptied the curve ATA and left it thawed
+    let curve_ata_config = get_ata_config(
+        leftover_tokens,
+        to_spl_pubkey(&mint),
+        to_spl_pubkey(&curve_pda),
+    );
+    let (curve_mint_ata, curve_mint_ata_account) = get_ata_accounts(
+        Some(*find_deterministic_pubkey("curve_ata").as_array()),
+        mollusk,
+        curve_ata_config,
+    );
+
+    // instruction discriminator = 5
+    let ix_data = vec![5];
+
+    ReturnVal {
+        account_infos: vec![
+            (creator, creator_account),
+            (global_config, global_account),
+            (curve_pda, curve_account),
+            (mint, mint_account),
+            (curve_mint_ata, curve_mint_ata_account),
+            (sol_escrow_pda, sol_escrow_account),
+            (system_program, system_program_account),
+            (token_program, token_program_account),
+        ],
+        account_meta: vec![
+            AccountMeta::new(creator, true),
+            AccountMeta::new_readonly(global_config, false),
+            AccountMeta::new(curve_pda, false),
+            // The mint supply changes when the leftover tokens are burned
+            AccountMeta::new(mint, false),
+            AccountMeta::new(curve_mint_ata, false),
+            AccountMeta::new(sol_escrow_pda, false),
+            // The creator signed, so the lamports go back to the creator
+            AccountMeta::new(creator, false),
+            AccountMeta::new_readonly(system_program, false),
+            AccountMeta::new_readonly(token_program, false),
+        ],
+        ix_data,
+    }
+}
@@ -0,0 +1,158 @@
+use crate::helpers::{
+    find_deterministic_pubkey, get_ata_accounts, get_ata_config, get_mint_accounts,
+    get_program_configs, get_treasury_pubkey,
+    ix_configs::{swap_configs::get_global_config, treasury_configs::get_treasury},
+    to_spl_pubkey, ReturnVal, SystemConfig,
+};
+use amm::states::{
+    bonding_curve::{BondingCurve, CurveStatus},
+    global_config::GlobalConfig,
+    treasury::Treasury,
+};
+use mollusk_svm::Mollusk;
+use solana_sdk::{
+    account::Account, message::AccountMeta, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+};
+use spl_token::{
+    solana_program::program_option::COption,
+    state::{AccountState, Mint},
+};
+
+use super::swap_configs::get_curve;
+
+/// Returns a curve for `mint` that sold its whole real token reserves
+pub fn get_complete_curve(program_id: &Pubkey, mint: &Pubkey, creator: &Pubkey) -> BondingCurve {
+    let mut curve = get_curve(program_id, mint, creator);
+
+    curve
+        .apply_buy(
+            1_000 * LAMPORTS_PER_SOL,
+            get_global_config().mint_decimals,
+            0,
+        )
+        .expect("completing buy should succeed");
+
+    assert_eq!(curve.status, CurveStatus::Completed as u8);
+
+    curve
+}
+
+/// Get the accounts for the admin graduating a complete curve
+///
+/// Returns the curve the program is expected to leave behind along with the instruction configs
+pub fn get_graduate_configs(mollusk: &Mollusk, program_id: &Pubkey) -> (BondingCurve, ReturnVal) {
+    let SystemConfig {
+        system_config: (system_program, system_program_account),
+        token_config: (token_program, token_program_account),
+        associated_program_config: _,
+    } = get_program_configs();
+
+    let rent = &mollusk.sysvars.rent;
+    let global_field = get_global_config();
+
+    let admin = Pubkey::new_from_array(global_field.admin);
+    let admin_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+
+    let creator = find_deterministic_pubkey("creator");
+    let mint = find_deterministic_pubkey("mint");
+
+    let (global_config, _) =
+        Pubkey::find_program_address(&[GlobalConfig::GLOBAL_PEFIX], program_id);
+    let mut global_account = Account::new(
+        rent.minimum_balance(GlobalConfig::SIZE),
+        GlobalConfig::SIZE,
+        program_id,
+    );
+    global_account.data = bytemuck::bytes_of(&global_field).to_vec();
+
+    let curve = get_complete_curve(program_id, &mint, &creator);
+    let (curve_pda, _) =
+        Pubkey::find_program_address(&[BondingCurve::SEED_PREFIX, mint.as_ref()], program_id);
+    let mut curve_account = Account::new(
+        rent.minimum_balance(BondingCurve::CURVE_SIZE),
+        BondingCurve::CURVE_SIZE,
+        program_id,
+    );
+    curve_account.data = bytemuck::bytes_of(&curve).to_vec();
+
+    let (sol_escrow_pda, _) = Pubkey::find_program_address(
+        &[BondingCurve::SOL_ESCROW_SEED_PREFIX, mint.as_ref()],
+        program_id,
+    );
+    let sol_escrow_account = Account::new(
+        rent.minimum_balance(0) + curve.real_sol_reserves,
+        0,
+        &system_program,
+    );
+
+    let mint_config = Mint {
+        decimals: global_field.mint_decimals,
+        freeze_authority: COption::Some(to_spl_pubkey(&curve_pda)),
+        is_initialized: true,
+        mint_authority: COption::None,
+        supply: global_field.token_total_supply,
+    };
+    let (_, mint_account) = get_mint_accounts(None, mollusk, mint_config);
+
+    // Every curve token was sold
+    let mut curve_ata_config = get_ata_config(0, to_spl_pubkey(&mint), to_spl_pubkey(&curve_pda));
+    curve_ata_config.state = AccountState::Frozen;
+    let (curve_mint_ata, curve_mint_ata_account) = get_ata_accounts(
+        Some(*find_deterministic_pubkey("curve_ata").as_array()),
+        mollusk,
+        curve_ata_config,
+    );
+
+    let destination_config = get_ata_config(0, to_spl_pubkey(&mint), to_spl_pubkey(&admin));
+    let (token_destination, token_destination_account) = get_ata_accounts(
+        Some(*find_deterministic_pubkey("admin_ata").as_array()),
+        mollusk,
+        destination_config,
+    );
+
+    let treasury = get_treasury_pubkey();
+    let mut treasury_account = Account::new(
+        rent.minimum_balance(Treasury::SIZE),
+        Treasury::SIZE,
+        program_id,
+    );
+    treasury_account.data = bytemuck::bytes_of(&get_treasury(program_id)).to_vec();
+
+    let mut expected_curve = curve;
+    expected_curve.real_sol_reserves = 0;
+    expected_curve.status = CurveStatus::Graduated as u8;
+
+    // instruction discriminator = 4
+    let ix_data = vec![4];
+
+    (
+        expected_curve,
+        ReturnVal {
+            account_infos: vec![
+                (admin, admin_account),
+                (global_config, global_account),
+                (curve_pda, curve_account),
+                (mint, mint_account),
+                (curve_mint_ata, curve_mint_ata_account),
+                (sol_escrow_pda, sol_escrow_account),
+                (token_destination, token_destination_account),
+                (treasury, treasury_account),
+                (system_program, system_program_account),
+                (token_program, token_program_account),
+            ],
+            account_meta: vec![
+                AccountMeta::new_readonly(admin, true),
+                AccountMeta::new_readonly(global_config, false),
+                AccountMeta::new(curve_pda, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new(curve_mint_ata, false),
+                AccountMeta::new(sol_escrow_pda, false),
+                AccountMeta::new(token_destination, false),
+                AccountMeta::new(treasury, false),
+                AccountMeta::new_readonly(system_program, false),
+                AccountMeta::new_readonly(token_program, false),
+            ],
+            ix_data,
+        },
+    )
+}
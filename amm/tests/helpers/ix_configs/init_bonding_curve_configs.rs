@@ -1,7 +1,19 @@
 use crate::helpers::{
-    find_deterministic_pubkey, get_program_configs, to_associated_pubkey, ReturnVal, SystemConfig,
+    find_deterministic_pubkey, get_program_configs, get_treasury_pubkey,
+    ix_configs::{
+        swap_configs::TRANSFER_HOOK_PROGRAM_ID,
+        vesting_configs::{VESTING_CLIFF_SLOTS, VESTING_DURATION_SLOTS},
+    },
+    to_associated_pubkey, ReturnVal, SystemConfig,
+};
+use amm::{
+    instructions::init_bonding_curve::CreateBondingCurveParams,
+    states::{
+        allowlist::Allowlist, bonding_curve::BondingCurve, crowdfund::Crowdfund,
+        global_config::GlobalConfig, vesting::Vesting,
+    },
+    Discriminator,
 };
-use amm::states::{bonding_curve::BondingCurve, global_config::GlobalConfig};
 use mollusk_svm::Mollusk;
 use {
     solana_sdk::{
@@ -32,16 +44,35 @@ pub fn get_init_bonding_curve_configs(mollusk: &Mollusk, program_id: &Pubkey) ->
     );
 
     let global_field = GlobalConfig {
+        discriminator: GlobalConfig::DISCRIMINATOR,
+        version: GlobalConfig::VERSION,
         mint_decimals: 6,
-        _padding: [0; 6],
+        _padding: [0; 1],
+        max_transfer_fee_bps: 500,
+        creator_fee_bps: 0,
         inittialized: 1,
         admin: Pubkey::new_from_array([0x2; 32]).to_bytes(),
-        fee_receiver: Pubkey::new_from_array([0x1; 32]).to_bytes(),
+        fee_receiver: get_treasury_pubkey().to_bytes(),
 
         initial_real_token_reserves: 793_100_000_000_000,
         initial_virtual_token_reserves: 1_073_000_000_000_000,
         initial_virtual_sol_reserves: 30000000000,
         token_total_supply: 1_000_000_000_000_000,
+        creation_fee_lamports: 0,
+        max_price_impact_bps: 0,
+        _padding_end: [0; 2],
+        sell_cooldown_slots: 0,
+        max_transfer_fee: 10_000_000,
+        vesting_cliff_slots: VESTING_CLIFF_SLOTS,
+        vesting_duration_slots: VESTING_DURATION_SLOTS,
+        vesting_beneficiary: Vesting::CREATOR_BENEFICIARY,
+        _padding_vesting: [0; 7],
+        allowed_transfer_hooks: [
+            TRANSFER_HOOK_PROGRAM_ID.to_bytes(),
+            [0; 32],
+            [0; 32],
+            [0; 32],
+        ],
     };
 
     global_account.data = bytemuck::bytes_of(&global_field).to_vec();
@@ -71,6 +102,20 @@ pub fn get_init_bonding_curve_configs(mollusk: &Mollusk, program_id: &Pubkey) ->
 
     let sol_escrow_account = Account::new(0, 0, &system_program);
 
+    let fee_receiver = get_treasury_pubkey();
+    let fee_receiver_account =
+        Account::new(mollusk.sysvars.rent.minimum_balance(0), 0, &system_program);
+
+    let (vesting_pda, _) =
+        Pubkey::find_program_address(&[Vesting::SEED_PREFIX, mint.as_ref()], program_id);
+
+    let vesting_vault = get_associated_token_address_with_program_id(
+        &to_associated_pubkey(&vesting_pda),
+        &to_associated_pubkey(&mint),
+        &to_associated_pubkey(&token_program),
+    );
+    let vesting_vault = Pubkey::new_from_array(*vesting_vault.as_array());
+
     let ix_data = vec![2];
 
     ReturnVal {
@@ -84,18 +129,177 @@ pub fn get_init_bonding_curve_configs(mollusk: &Mollusk, program_id: &Pubkey) ->
             (system_program, system_program_account),
             (token_program, token_program_account),
             (associated_token_program, associated_program_account),
+            (fee_receiver, fee_receiver_account),
+            (vesting_pda, Account::new(0, 0, &system_program)),
+            (vesting_vault, Account::new(0, 0, &system_program)),
         ],
         account_meta: vec![
             AccountMeta::new(creator, true),
             AccountMeta::new(global_config, false),
-            AccountMeta::new(curve_pda, true),
+            AccountMeta::new(curve_pda, false),
             AccountMeta::new(mint, true),
             AccountMeta::new(curve_mint_ata, true),
             AccountMeta::new(sol_escrow_pda, false),
             AccountMeta::new(system_program, false),
             AccountMeta::new(token_program, false),
             AccountMeta::new(associated_token_program, false),
+            AccountMeta::new(fee_receiver, false),
+            AccountMeta::new(vesting_pda, false),
+            AccountMeta::new(vesting_vault, false),
         ],
         ix_data,
     }
 }
+
+/// Get the accounts for launching a curve with a dev buy of `dev_buy_lamports`
+///
+/// The creator ATA is appended to the accounts of `get_init_bonding_curve_configs`, the config
+/// charges `creator_fee_bps` on the dev buy
+pub fn get_dev_buy_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    dev_buy_lamports: u64,
+    creator_fee_bps: u16,
+) -> ReturnVal {
+    let ReturnVal {
+        mut account_infos,
+        mut account_meta,
+        ix_data: _,
+    } = get_init_bonding_curve_configs(mollusk, program_id);
+
+    let (creator, _) = account_infos[0];
+    let (mint, _) = account_infos[3];
+    let (token_program, _) = account_infos[7];
+    let (system_program, _) = account_infos[6];
+
+    let mut global_field = *bytemuck::from_bytes::<GlobalConfig>(&account_infos[1].1.data);
+    global_field.creator_fee_bps = creator_fee_bps;
+    account_infos[1].1.data = bytemuck::bytes_of(&global_field).to_vec();
+
+    let creator_mint_ata = get_associated_token_address_with_program_id(
+        &to_associated_pubkey(&creator),
+        &to_associated_pubkey(&mint),
+        &to_associated_pubkey(&token_program),
+    );
+    let creator_mint_ata = Pubkey::new_from_array(*creator_mint_ata.as_array());
+
+    account_infos.push((creator_mint_ata, Account::new(0, 0, &system_program)));
+    account_meta.push(AccountMeta::new(creator_mint_ata, false));
+
+    let params = CreateBondingCurveParams {
+        vault_mode: BondingCurve::FROZEN_VAULT,
+        padding: [0; 3],
+        max_wallet_bps: 0,
+        transfer_fee_bps: 0,
+        dev_buy_lamports,
+        starting_slot: 0,
+        max_transfer_fee: 0,
+        transfer_hook_program: [0; 32],
+        allowlist: Allowlist::default(),
+        crowdfund: Crowdfund::default(),
+    };
+
+    let mut ix_data = vec![2];
+    ix_data.extend_from_slice(bytemuck::bytes_of(&params));
+
+    ReturnVal {
+        account_infos,
+        account_meta,
+        ix_data,
+    }
+}
+
+/// Get the accounts for launching a curve whose mint charges a `transfer_fee_bps` transfer fee,
+/// capped at `max_transfer_fee`
+pub fn get_transfer_fee_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    transfer_fee_bps: u16,
+    max_transfer_fee: u64,
+) -> ReturnVal {
+    let ReturnVal {
+        account_infos,
+        account_meta,
+        ix_data: _,
+    } = get_init_bonding_curve_configs(mollusk, program_id);
+
+    let params = CreateBondingCurveParams {
+        vault_mode: BondingCurve::FROZEN_VAULT,
+        padding: [0; 3],
+        max_wallet_bps: 0,
+        transfer_fee_bps,
+        dev_buy_lamports: 0,
+        starting_slot: 0,
+        max_transfer_fee,
+        transfer_hook_program: [0; 32],
+        allowlist: Allowlist::default(),
+        crowdfund: Crowdfund::default(),
+    };
+
+    let mut ix_data = vec![2];
+    ix_data.extend_from_slice(bytemuck::bytes_of(&params));
+
+    ReturnVal {
+        account_infos,
+        account_meta,
+        ix_data,
+    }
+}
+
+/// Get the accounts for launching a curve whose mint is hooked to `transfer_hook_program`
+pub fn get_transfer_hook_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    transfer_hook_program: &Pubkey,
+) -> ReturnVal {
+    let ReturnVal {
+        account_infos,
+        account_meta,
+        ix_data: _,
+    } = get_init_bonding_curve_configs(mollusk, program_id);
+
+    let params = CreateBondingCurveParams {
+        vault_mode: BondingCurve::FROZEN_VAULT,
+        padding: [0; 3],
+        max_wallet_bps: 0,
+        transfer_fee_bps: 0,
+        dev_buy_lamports: 0,
+        starting_slot: 0,
+        max_transfer_fee: 0,
+        transfer_hook_program: transfer_hook_program.to_bytes(),
+        allowlist: Allowlist::default(),
+        crowdfund: Crowdfund::default(),
+    };
+
+    let mut ix_data = vec![2];
+    ix_data.extend_from_slice(bytemuck::bytes_of(&params));
+
+    ReturnVal {
+        account_infos,
+        account_meta,
+        ix_data,
+    }
+}
+
+/// Get the accounts for launching a curve under a config charging `creation_fee_lamports`
+pub fn get_creation_fee_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    creation_fee_lamports: u64,
+) -> ReturnVal {
+    let ReturnVal {
+        mut account_infos,
+        account_meta,
+        ix_data,
+    } = get_init_bonding_curve_configs(mollusk, program_id);
+
+    let mut global_field = *bytemuck::from_bytes::<GlobalConfig>(&account_infos[1].1.data);
+    global_field.creation_fee_lamports = creation_fee_lamports;
+    account_infos[1].1.data = bytemuck::bytes_of(&global_field).to_vec();
+
+    ReturnVal {
+        account_infos,
+        account_meta,
+        ix_data,
+    }
+}
@@ -1,5 +1,12 @@
-use crate::helpers::{get_program_configs, ReturnVal, SystemConfig};
-use amm::states::global_config::{GlobalConfig, GlobalSettingsInput};
+use crate::helpers::{
+    get_program_configs, get_treasury_pubkey,
+    ix_configs::vesting_configs::{VESTING_CLIFF_SLOTS, VESTING_DURATION_SLOTS},
+    ReturnVal, SystemConfig,
+};
+use amm::states::{
+    global_config::{GlobalConfig, GlobalSettingsInput},
+    vesting::Vesting,
+};
 use solana_sdk::native_token::LAMPORTS_PER_SOL;
 use solana_sdk::{account::Account, message::AccountMeta, pubkey::Pubkey};
 
@@ -21,15 +28,27 @@ pub fn get_init_global_configs(program_id: &Pubkey) -> ReturnVal {
 
     let ix_args = GlobalSettingsInput {
         mint_decimals: 6,
-        _padding: [0; 7],
+        _padding: [0; 1],
+        max_price_impact_bps: 0,
+        max_transfer_fee_bps: 500,
+        creator_fee_bps: 0,
 
         admin: Pubkey::new_from_array([0x2; 32]).to_bytes(),
-        fee_receiver: Pubkey::new_from_array([0x1; 32]).to_bytes(),
+        fee_receiver: get_treasury_pubkey().to_bytes(),
 
         initial_real_token_reserves: 793_100_000_000_000,
         initial_virtual_token_reserves: 1_073_000_000_000_000,
         initial_virtual_sol_reserves: 30000000000,
         token_total_supply: 1_000_000_000_000_000,
+        creation_fee_lamports: 0,
+        sell_cooldown_slots: 0,
+        _padding_end: [0; 4],
+        max_transfer_fee: 10_000_000,
+        vesting_cliff_slots: VESTING_CLIFF_SLOTS,
+        vesting_duration_slots: VESTING_DURATION_SLOTS,
+        vesting_beneficiary: Vesting::CREATOR_BENEFICIARY,
+        _padding_vesting: [0; 7],
+        allowed_transfer_hooks: [[0; 32]; GlobalConfig::MAX_TRANSFER_HOOKS],
     };
 
     // instruction discriminator = 0
@@ -46,7 +65,7 @@ pub fn get_init_global_configs(program_id: &Pubkey) -> ReturnVal {
         ],
         account_meta: vec![
             AccountMeta::new(admin, true),
-            AccountMeta::new(global_pubkey, true),
+            AccountMeta::new(global_pubkey, false),
             AccountMeta::new(system_address, false),
         ],
         ix_data,
@@ -1,2 +1,11 @@
+pub mod close_curve_configs;
+pub mod graduate_configs;
 pub mod init_bonding_curve_configs;
 pub mod init_global_configs;
+pub mod multisig_configs;
+pub mod quote_configs;
+pub mod refund_configs;
+pub mod route_swap_configs;
+pub mod swap_configs;
+pub mod treasury_configs;
+pub mod vesting_configs;
@@ -0,0 +1,397 @@
+use crate::helpers::{
+    find_deterministic_pubkey, get_program_configs, ix_configs::swap_configs::get_global_config,
+    ReturnVal, SystemConfig,
+};
+use amm::{
+    instructions::create_multisig::CreateMultisigParams,
+    states::{
+        global_config::{GlobalConfig, GlobalSettingsInput},
+        multisig::{Multisig, Proposal, ProposalAccount, MAX_MULTISIG_SIGNERS},
+    },
+    Discriminator,
+};
+use mollusk_svm::Mollusk;
+use solana_sdk::{
+    account::Account, message::AccountMeta, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+};
+
+pub const MULTISIG_THRESHOLD: u8 = 2;
+
+/// The 3 signers of the test multisig
+pub fn get_multisig_signers() -> [Pubkey; 3] {
+    [
+        find_deterministic_pubkey("signer_a"),
+        find_deterministic_pubkey("signer_b"),
+        find_deterministic_pubkey("signer_c"),
+    ]
+}
+
+/// Returns the address of the test multisig, its vault and its state
+pub fn get_multisig(program_id: &Pubkey) -> (Pubkey, Pubkey, Multisig) {
+    let create_key = find_deterministic_pubkey("create_key");
+
+    let (multisig, bump) =
+        Pubkey::find_program_address(&[Multisig::SEED_PREFIX, create_key.as_ref()], program_id);
+    let (vault, vault_bump) = Pubkey::find_program_address(
+        &[Multisig::VAULT_SEED_PREFIX, multisig.as_ref()],
+        program_id,
+    );
+
+    let mut signers = [[0; 32]; MAX_MULTISIG_SIGNERS];
+    for (signer, key) in signers.iter_mut().zip(get_multisig_signers()) {
+        *signer = key.to_bytes();
+    }
+
+    let multisig_data = Multisig {
+        discriminator: Multisig::DISCRIMINATOR,
+        version: Multisig::VERSION,
+        bump,
+        vault_bump,
+        threshold: MULTISIG_THRESHOLD,
+        signer_count: 3,
+        _padding: [0; 3],
+        create_key: create_key.to_bytes(),
+        proposal_count: 0,
+        signers,
+    };
+
+    (multisig, vault, multisig_data)
+}
+
+/// Settings replacing the ones of `get_global_config`
+pub fn get_new_global_settings() -> GlobalSettingsInput {
+    let config = get_global_config();
+
+    GlobalSettingsInput {
+        mint_decimals: config.mint_decimals,
+        _padding: [0; 1],
+        max_price_impact_bps: config.max_price_impact_bps,
+        max_transfer_fee_bps: 100,
+        creator_fee_bps: 50,
+
+        fee_receiver: config.fee_receiver,
+        admin: config.admin,
+
+        initial_virtual_token_reserves: config.initial_virtual_token_reserves,
+        initial_virtual_sol_reserves: config.initial_virtual_sol_reserves,
+        initial_real_token_reserves: config.initial_real_token_reserves,
+        token_total_supply: config.token_total_supply,
+        creation_fee_lamports: config.creation_fee_lamports,
+        sell_cooldown_slots: config.sell_cooldown_slots,
+        _padding_end: [0; 4],
+        max_transfer_fee: config.max_transfer_fee,
+        vesting_cliff_slots: config.vesting_cliff_slots,
+        vesting_duration_slots: config.vesting_duration_slots,
+        vesting_beneficiary: config.vesting_beneficiary,
+        _padding_vesting: [0; 7],
+        allowed_transfer_hooks: config.allowed_transfer_hooks,
+    }
+}
+
+/// Get the accounts for `admin` updating the global config to `get_new_global_settings`
+pub fn get_update_global_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    admin: &Pubkey,
+) -> ReturnVal {
+    let rent = &mollusk.sysvars.rent;
+
+    let mut global_field = get_global_config();
+    global_field.admin = admin.to_bytes();
+
+    let (global_config, _) =
+        Pubkey::find_program_address(&[GlobalConfig::GLOBAL_PEFIX], program_id);
+    let mut global_account = Account::new(
+        rent.minimum_balance(GlobalConfig::SIZE),
+        GlobalConfig::SIZE,
+        program_id,
+    );
+    global_account.data = bytemuck::bytes_of(&global_field).to_vec();
+
+    let mut settings = get_new_global_settings();
+    settings.admin = admin.to_bytes();
+
+    // instruction discriminator = 1
+    let mut ix_data = vec![1];
+    ix_data.extend_from_slice(bytemuck::bytes_of(&settings));
+
+    ReturnVal {
+        account_infos: vec![
+            (
+                *admin,
+                Account::new(LAMPORTS_PER_SOL, 0, &Pubkey::default()),
+            ),
+            (global_config, global_account),
+        ],
+        account_meta: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(global_config, false),
+        ],
+        ix_data,
+    }
+}
+
+/// Get the accounts for the first signer creating the test multisig
+pub fn get_create_multisig_configs(program_id: &Pubkey) -> ReturnVal {
+    let SystemConfig {
+        system_config: (system_program, system_program_account),
+        token_config: _,
+        associated_program_config: _,
+    } = get_program_configs();
+
+    let (multisig, vault, multisig_data) = get_multisig(program_id);
+    let payer = get_multisig_signers()[0];
+    let create_key = Pubkey::new_from_array(multisig_data.create_key);
+
+    let params = CreateMultisigParams {
+        threshold: multisig_data.threshold,
+        signer_count: multisig_data.signer_count,
+        signers: multisig_data.signers,
+    };
+
+    // instruction discriminator = 11
+    let mut ix_data = vec![11];
+    ix_data.extend_from_slice(bytemuck::bytes_of(&params));
+
+    ReturnVal {
+        account_infos: vec![
+            (
+                payer,
+                Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program),
+            ),
+            (create_key, Account::new(0, 0, &system_program)),
+            (multisig, Account::new(0, 0, &system_program)),
+            (vault, Account::new(0, 0, &system_program)),
+            (system_program, system_program_account),
+        ],
+        account_meta: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(create_key, true),
+            AccountMeta::new(multisig, false),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+        ix_data,
+    }
+}
+
+/// Returns the first proposal of the test multisig, an `UpdateGlobal` signed by its vault
+pub fn get_update_global_proposal(program_id: &Pubkey, approvals: u16) -> (Pubkey, Proposal) {
+    let (multisig, vault, _) = get_multisig(program_id);
+    let (global_config, _) =
+        Pubkey::find_program_address(&[GlobalConfig::GLOBAL_PEFIX], program_id);
+
+    let (proposal, bump) = Pubkey::find_program_address(
+        &[
+            Proposal::SEED_PREFIX,
+            multisig.as_ref(),
+            &0u64.to_le_bytes(),
+        ],
+        program_id,
+    );
+
+    let mut settings = get_new_global_settings();
+    settings.admin = vault.to_bytes();
+
+    let mut ix_data = vec![1];
+    ix_data.extend_from_slice(bytemuck::bytes_of(&settings));
+
+    let mut proposal_data = Proposal {
+        discriminator: Proposal::DISCRIMINATOR,
+        version: Proposal::VERSION,
+        bump,
+        executed: 0,
+        account_count: 2,
+        approvals,
+        data_len: ix_data.len() as u16,
+        multisig: multisig.to_bytes(),
+        index: 0,
+        program_id: program_id.to_bytes(),
+        accounts: [ProposalAccount::default(); 16],
+        data: [0; 256],
+    };
+    proposal_data.accounts[0] = ProposalAccount {
+        pubkey: vault.to_bytes(),
+        is_signer: 1,
+        is_writable: 0,
+    };
+    proposal_data.accounts[1] = ProposalAccount {
+        pubkey: global_config.to_bytes(),
+        is_signer: 0,
+        is_writable: 1,
+    };
+    proposal_data.data[..ix_data.len()].copy_from_slice(&ix_data);
+
+    (proposal, proposal_data)
+}
+
+/// Get the accounts for the first signer proposing `get_update_global_proposal`
+pub fn get_create_proposal_configs(mollusk: &Mollusk, program_id: &Pubkey) -> ReturnVal {
+    let SystemConfig {
+        system_config: (system_program, system_program_account),
+        token_config: _,
+        associated_program_config: _,
+    } = get_program_configs();
+
+    let rent = &mollusk.sysvars.rent;
+    let (multisig, vault, multisig_data) = get_multisig(program_id);
+    let (proposal, proposal_data) = get_update_global_proposal(program_id, 0);
+    let proposer = get_multisig_signers()[0];
+
+    let mut multisig_account = Account::new(
+        rent.minimum_balance(Multisig::SIZE),
+        Multisig::SIZE,
+        program_id,
+    );
+    multisig_account.data = bytemuck::bytes_of(&multisig_data).to_vec();
+
+    let (global_config, _) =
+        Pubkey::find_program_address(&[GlobalConfig::GLOBAL_PEFIX], program_id);
+
+    // instruction discriminator = 12
+    let mut ix_data = vec![12];
+    ix_data.extend_from_slice(program_id.as_ref());
+    // vault signs, the global config is writable
+    ix_data.extend_from_slice(&[1, 2]);
+    ix_data.extend_from_slice(&proposal_data.data[..proposal_data.data_len as usize]);
+
+    ReturnVal {
+        account_infos: vec![
+            (
+                proposer,
+                Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program),
+            ),
+            (multisig, multisig_account),
+            (proposal, Account::new(0, 0, &system_program)),
+            (system_program, system_program_account),
+            (vault, Account::new(0, 0, &system_program)),
+            (global_config, Account::new(0, 0, &system_program)),
+        ],
+        account_meta: vec![
+            AccountMeta::new(proposer, true),
+            AccountMeta::new(multisig, false),
+            AccountMeta::new(proposal, false),
+            AccountMeta::new_readonly(system_program, false),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new_readonly(global_config, false),
+        ],
+        ix_data,
+    }
+}
+
+/// Get the accounts for the second signer approving `get_update_global_proposal`, already
+/// approved by the signers in `approvals`
+pub fn get_approve_proposal_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    approvals: u16,
+) -> ReturnVal {
+    let rent = &mollusk.sysvars.rent;
+    let (multisig, _, multisig_data) = get_multisig(program_id);
+    let (proposal, proposal_data) = get_update_global_proposal(program_id, approvals);
+    let signer = get_multisig_signers()[1];
+
+    let mut multisig_account = Account::new(
+        rent.minimum_balance(Multisig::SIZE),
+        Multisig::SIZE,
+        program_id,
+    );
+    multisig_account.data = bytemuck::bytes_of(&multisig_data).to_vec();
+
+    let mut proposal_account = Account::new(
+        rent.minimum_balance(Proposal::SIZE),
+        Proposal::SIZE,
+        program_id,
+    );
+    proposal_account.data = bytemuck::bytes_of(&proposal_data).to_vec();
+
+    // instruction discriminator = 13
+    let ix_data = vec![13];
+
+    ReturnVal {
+        account_infos: vec![
+            (
+                signer,
+                Account::new(LAMPORTS_PER_SOL, 0, &Pubkey::default()),
+            ),
+            (multisig, multisig_account),
+            (proposal, proposal_account),
+        ],
+        account_meta: vec![
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new_readonly(multisig, false),
+            AccountMeta::new(proposal, false),
+        ],
+        ix_data,
+    }
+}
+
+/// Get the accounts for the first signer executing `get_update_global_proposal` against a global
+/// config administered by the multisig vault
+pub fn get_execute_proposal_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    approvals: u16,
+) -> ReturnVal {
+    let rent = &mollusk.sysvars.rent;
+    let (multisig, vault, multisig_data) = get_multisig(program_id);
+    let (proposal, proposal_data) = get_update_global_proposal(program_id, approvals);
+    let signer = get_multisig_signers()[0];
+
+    let mut multisig_account = Account::new(
+        rent.minimum_balance(Multisig::SIZE),
+        Multisig::SIZE,
+        program_id,
+    );
+    multisig_account.data = bytemuck::bytes_of(&multisig_data).to_vec();
+
+    let mut proposal_account = Account::new(
+        rent.minimum_balance(Proposal::SIZE),
+        Proposal::SIZE,
+        program_id,
+    );
+    proposal_account.data = bytemuck::bytes_of(&proposal_data).to_vec();
+
+    let mut global_field = get_global_config();
+    global_field.admin = vault.to_bytes();
+
+    let (global_config, _) =
+        Pubkey::find_program_address(&[GlobalConfig::GLOBAL_PEFIX], program_id);
+    let mut global_account = Account::new(
+        rent.minimum_balance(GlobalConfig::SIZE),
+        GlobalConfig::SIZE,
+        program_id,
+    );
+    global_account.data = bytemuck::bytes_of(&global_field).to_vec();
+
+    // instruction discriminator = 14
+    let ix_data = vec![14];
+
+    ReturnVal {
+        account_infos: vec![
+            (
+                signer,
+                Account::new(LAMPORTS_PER_SOL, 0, &Pubkey::default()),
+            ),
+            (multisig, multisig_account),
+            (proposal, proposal_account),
+            (vault, Account::new(0, 0, &Pubkey::default())),
+            (global_config, global_account),
+            (
+                *program_id,
+                mollusk_svm::program::create_program_account_loader_v3(program_id),
+            ),
+        ],
+        account_meta: vec![
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new_readonly(multisig, false),
+            AccountMeta::new(proposal, false),
+            AccountMeta::new_readonly(vault, false),
+            // accounts of the proposed `UpdateGlobal`, the vault again and the global config
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(global_config, false),
+            AccountMeta::new_readonly(*program_id, false),
+        ],
+        ix_data,
+    }
+}
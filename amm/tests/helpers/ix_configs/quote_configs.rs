@@ -0,0 +1,47 @@
+use crate::helpers::{ix_configs::swap_configs::get_swap_accounts, ReturnVal};
+use amm::instructions::quote::QuoteParams;
+use mollusk_svm::Mollusk;
+use solana_sdk::{message::AccountMeta, pubkey::Pubkey};
+
+/// Get the accounts for quoting a swap against a freshly launched curve
+///
+/// Arguments:
+/// - `base_in`: 0 to quote a buy, 1 to quote a sell
+/// - `exact_in_amount`: lamports in for buys, tokens in for sells
+pub fn get_quote_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    base_in: u8,
+    exact_in_amount: u64,
+) -> ReturnVal {
+    let swap_accounts = get_swap_accounts(mollusk, program_id, "mint", 0);
+
+    // Swap accounts are buyer, buyer ATA, mint_a, mint_b, config, curve, ...
+    let (mint, mint_account) = swap_accounts.account_infos[3].clone();
+    let (global_config, global_account) = swap_accounts.account_infos[4].clone();
+    let (curve_pda, curve_account) = swap_accounts.account_infos[5].clone();
+
+    let quote_params = QuoteParams {
+        base_in,
+        padding: [0; 7],
+        exact_in_amount,
+    };
+
+    // instruction discriminator = 7
+    let mut ix_data = vec![7];
+    ix_data.extend_from_slice(bytemuck::bytes_of(&quote_params));
+
+    ReturnVal {
+        account_infos: vec![
+            (curve_pda, curve_account),
+            (mint, mint_account),
+            (global_config, global_account),
+        ],
+        account_meta: vec![
+            AccountMeta::new_readonly(curve_pda, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(global_config, false),
+        ],
+        ix_data,
+    }
+}
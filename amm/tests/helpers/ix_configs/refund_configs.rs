@@ -0,0 +1,78 @@
+use crate::helpers::{
+    ix_configs::swap_configs::{get_swap_accounts, SWAP_SLOT},
+    ReturnVal,
+};
+use amm::{
+    instructions::refund::RefundParams,
+    states::{bonding_curve::BondingCurve, crowdfund::Crowdfund},
+};
+use mollusk_svm::Mollusk;
+use solana_sdk::{
+    account::Account, message::AccountMeta, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+};
+use spl_token::{solana_program::program_pack::Pack, state::Account as TokenAccount};
+
+/// Lamports the holder spent on the test crowdfund curve, short of its target
+pub const CROWDFUND_RAISED_SOL: u64 = LAMPORTS_PER_SOL;
+
+/// Crowdfund of the test curve, a 10 SOL target due at `SWAP_SLOT`
+pub fn get_crowdfund() -> Crowdfund {
+    Crowdfund {
+        target_sol: 10 * LAMPORTS_PER_SOL,
+        deadline_slot: SWAP_SLOT,
+    }
+}
+
+/// Sets the crowdfund of `get_crowdfund` on the curve of swap accounts
+pub fn set_crowdfund(account_infos: &mut [(Pubkey, Account)]) {
+    let mut curve = *bytemuck::from_bytes::<BondingCurve>(&account_infos[5].1.data);
+    curve.crowdfund = get_crowdfund();
+    account_infos[5].1.data = bytemuck::bytes_of(&curve).to_vec();
+}
+
+/// Get the accounts for a holder returning `refund_bps` of their tokens to a crowdfund curve that
+/// raised `CROWDFUND_RAISED_SOL`, all of it from the holder
+pub fn get_refund_configs(mollusk: &Mollusk, program_id: &Pubkey, refund_bps: u64) -> ReturnVal {
+    let ReturnVal {
+        mut account_infos,
+        account_meta: _,
+        ix_data: _,
+    } = get_swap_accounts(mollusk, program_id, "mint", CROWDFUND_RAISED_SOL);
+
+    set_crowdfund(&mut account_infos);
+
+    // Same accounts as a swap, without `mint_a`, the config and the fee receiver
+    let account_infos: Vec<_> = [0, 1, 3, 5, 6, 7, 9, 10]
+        .into_iter()
+        .map(|index| account_infos[index].clone())
+        .collect();
+
+    let [holder, holder_mint_ata, mint, curve, sol_escrow, curve_mint_ata, system_program, token_program] =
+        core::array::from_fn(|index| account_infos[index].0);
+
+    let holder_tokens = TokenAccount::unpack(&account_infos[1].1.data)
+        .unwrap()
+        .amount;
+    let params = RefundParams {
+        token_amount: holder_tokens * refund_bps / 10_000,
+    };
+
+    // instruction discriminator = 18
+    let mut ix_data = vec![18];
+    ix_data.extend_from_slice(bytemuck::bytes_of(&params));
+
+    ReturnVal {
+        account_infos,
+        account_meta: vec![
+            AccountMeta::new(holder, true),
+            AccountMeta::new(holder_mint_ata, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(curve, false),
+            AccountMeta::new(sol_escrow, false),
+            AccountMeta::new(curve_mint_ata, false),
+            AccountMeta::new_readonly(system_program, false),
+            AccountMeta::new_readonly(token_program, false),
+        ],
+        ix_data,
+    }
+}
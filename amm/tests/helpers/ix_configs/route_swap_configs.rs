@@ -0,0 +1,114 @@
+use crate::helpers::{
+    ix_configs::swap_configs::{get_swap_accounts, SELL_COOLDOWN_SLOTS},
+    ReturnVal,
+};
+use amm::{
+    instructions::route_swap::RouteSwapParams,
+    states::{cooldown::TradeCooldown, global_config::GlobalConfig},
+    Discriminator,
+};
+use mollusk_svm::Mollusk;
+use solana_sdk::{
+    account::Account, message::AccountMeta, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+};
+
+/// Get the accounts for selling `exact_in_amount` tokens of the "mint" curve for tokens of the
+/// "mint_out" curve, the buyer already spent 1 SOL on the "mint" curve
+pub fn get_route_swap_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    exact_in_amount: u64,
+    min_out_amount: u64,
+) -> ReturnVal {
+    let sell_leg = get_swap_accounts(mollusk, program_id, "mint", LAMPORTS_PER_SOL);
+    let buy_leg = get_swap_accounts(mollusk, program_id, "mint_out", 0);
+
+    let mut account_infos = sell_leg.account_infos;
+
+    // The buyer, config, fee receiver and programs are shared by both legs
+    for (key, account) in buy_leg.account_infos {
+        if !account_infos.iter().any(|(existing, _)| *existing == key) {
+            account_infos.push((key, account));
+        }
+    }
+
+    let mut account_meta = sell_leg.account_meta;
+    account_meta.extend(buy_leg.account_meta);
+
+    let route_params = RouteSwapParams {
+        exact_in_amount,
+        min_out_amount,
+    };
+
+    // instruction discriminator = 6
+    let mut ix_data = vec![6];
+    ix_data.extend_from_slice(bytemuck::bytes_of(&route_params));
+
+    ReturnVal {
+        account_infos,
+        account_meta,
+        ix_data,
+    }
+}
+
+/// Get the accounts for a route swap like `get_route_swap_configs` under a config with a sell
+/// cooldown
+///
+/// The buyer's cooldown PDAs of both legs follow the swap accounts, the one of the sell leg
+/// recording a buy at `last_buy_slot` and the one of the buy leg empty.
+pub fn get_cooldown_route_swap_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    exact_in_amount: u64,
+    last_buy_slot: u64,
+) -> ReturnVal {
+    let ReturnVal {
+        mut account_infos,
+        mut account_meta,
+        ix_data,
+    } = get_route_swap_configs(mollusk, program_id, exact_in_amount, 1);
+
+    let (buyer, _) = account_infos[0];
+
+    let mut config = *bytemuck::from_bytes::<GlobalConfig>(&account_infos[4].1.data);
+    config.sell_cooldown_slots = SELL_COOLDOWN_SLOTS;
+    account_infos[4].1.data = bytemuck::bytes_of(&config).to_vec();
+
+    for (mint_meta, last_buy_slot) in [(3, Some(last_buy_slot)), (14, None)] {
+        let mint = account_meta[mint_meta].pubkey;
+        let cooldown_seeds: &[&[u8]] = &[TradeCooldown::SEED_PREFIX, mint.as_ref(), buyer.as_ref()];
+        let (cooldown_pda, bump) = Pubkey::find_program_address(cooldown_seeds, program_id);
+
+        let cooldown_account = match last_buy_slot {
+            Some(last_buy_slot) => {
+                let cooldown = TradeCooldown {
+                    discriminator: TradeCooldown::DISCRIMINATOR,
+                    version: TradeCooldown::VERSION,
+                    bump,
+                    _padding: [0; 6],
+                    mint: mint.to_bytes(),
+                    wallet: buyer.to_bytes(),
+                    last_buy_slot,
+                };
+
+                let mut account = Account::new(
+                    mollusk.sysvars.rent.minimum_balance(TradeCooldown::SIZE),
+                    TradeCooldown::SIZE,
+                    program_id,
+                );
+                account.data = bytemuck::bytes_of(&cooldown).to_vec();
+                account
+            }
+            None => Account::default(),
+        };
+
+        account_infos.push((cooldown_pda, cooldown_account));
+        account_meta.push(AccountMeta::new(cooldown_pda, false));
+    }
+
+    ReturnVal {
+        account_infos,
+        account_meta,
+        ix_data,
+    }
+}
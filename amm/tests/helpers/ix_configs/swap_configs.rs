@@ -0,0 +1,529 @@
+use crate::helpers::{
+    find_deterministic_pubkey, get_ata_accounts, get_ata_config, get_mint_accounts,
+    get_program_configs, get_treasury_pubkey,
+    ix_configs::vesting_configs::{VESTING_CLIFF_SLOTS, VESTING_DURATION_SLOTS},
+    to_associated_pubkey, to_spl_pubkey, ReturnVal, SystemConfig,
+};
+use amm::{
+    instructions::swap::SwapParams,
+    states::{
+        allowlist::{allowlist_leaf, allowlist_node, Allowlist, AllowlistClaim},
+        bonding_curve::{BondingCurve, CurveStatus},
+        cooldown::TradeCooldown,
+        crowdfund::Crowdfund,
+        global_config::GlobalConfig,
+        vesting::Vesting,
+    },
+    Discriminator,
+};
+use mollusk_svm::Mollusk;
+use solana_sdk::{
+    account::Account, message::AccountMeta, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+    rent::Rent,
+};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList};
+use spl_token::{
+    solana_program::{program_option::COption, program_pack::Pack},
+    state::{AccountState, Mint},
+};
+use spl_token_2022::{
+    extension::{
+        transfer_hook::{TransferHook, TransferHookAccount},
+        BaseState, BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+    },
+    state::{Account as Token2022Account, Mint as Token2022Mint},
+};
+use spl_transfer_hook_interface::{
+    get_extra_account_metas_address, instruction::ExecuteInstruction,
+};
+
+/// Slot the curve was launched at, swaps run well after the launch fee phases
+pub const CURVE_STARTING_SLOT: u64 = 0;
+pub const SWAP_SLOT: u64 = 1_000;
+
+pub fn get_global_config() -> GlobalConfig {
+    GlobalConfig {
+        discriminator: GlobalConfig::DISCRIMINATOR,
+        version: GlobalConfig::VERSION,
+        mint_decimals: 6,
+        _padding: [0; 1],
+        max_transfer_fee_bps: 500,
+        creator_fee_bps: 0,
+        inittialized: 1,
+        admin: Pubkey::new_from_array([0x2; 32]).to_bytes(),
+        fee_receiver: get_treasury_pubkey().to_bytes(),
+
+        initial_real_token_reserves: 793_100_000_000_000,
+        initial_virtual_token_reserves: 1_073_000_000_000_000,
+        initial_virtual_sol_reserves: 30000000000,
+        token_total_supply: 1_000_000_000_000_000,
+        creation_fee_lamports: 0,
+        max_price_impact_bps: 0,
+        _padding_end: [0; 2],
+        sell_cooldown_slots: 0,
+        max_transfer_fee: 10_000_000,
+        vesting_cliff_slots: VESTING_CLIFF_SLOTS,
+        vesting_duration_slots: VESTING_DURATION_SLOTS,
+        vesting_beneficiary: Vesting::CREATOR_BENEFICIARY,
+        _padding_vesting: [0; 7],
+        allowed_transfer_hooks: [
+            TRANSFER_HOOK_PROGRAM_ID.to_bytes(),
+            [0; 32],
+            [0; 32],
+            [0; 32],
+        ],
+    }
+}
+
+/// Returns a freshly launched curve for `mint`
+pub fn get_curve(program_id: &Pubkey, mint: &Pubkey, creator: &Pubkey) -> BondingCurve {
+    let config = get_global_config();
+    let curve_seeds: &[&[u8]] = &[BondingCurve::SEED_PREFIX, mint.as_ref()];
+    let (_, bump) = Pubkey::find_program_address(curve_seeds, program_id);
+
+    BondingCurve {
+        discriminator: BondingCurve::DISCRIMINATOR,
+        version: BondingCurve::VERSION,
+        status: CurveStatus::Active as u8,
+        bump,
+        vault_mode: BondingCurve::FROZEN_VAULT,
+        _padding: [0u8; 2],
+        max_wallet_bps: 0,
+        mint: *mint.as_array(),
+        creator: *creator.as_array(),
+        initial_real_token_reserves: config.initial_real_token_reserves,
+        virtual_sol_reserves: config.initial_virtual_sol_reserves,
+        virtual_token_reserves: config.initial_virtual_token_reserves,
+        real_sol_reserves: 0,
+        real_token_reserves: config.initial_real_token_reserves,
+        token_total_supply: config.token_total_supply,
+        starting_slot: CURVE_STARTING_SLOT,
+        allowlist: Allowlist::default(),
+        crowdfund: Crowdfund::default(),
+    }
+}
+
+/// Get the accounts for a swap against a curve that already sold `pre_bought_sol` worth of tokens
+/// to the buyer
+///
+/// Arguments:
+/// - `base_in`: 0 to buy tokens with SOL, 1 to sell tokens for SOL
+/// - `exact_in_amount`: lamports in for buys, tokens in for sells
+/// - `min_out_amount`: slippage bound
+/// - `pre_bought_sol`: lamports the buyer already spent on the curve
+pub fn get_swap_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    base_in: u8,
+    exact_in_amount: u64,
+    min_out_amount: u64,
+    pre_bought_sol: u64,
+) -> ReturnVal {
+    let swap_params = SwapParams {
+        base_in,
+        padding: [0; 7],
+        exact_in_amount,
+        min_out_amount,
+        deadline_slot: 0,
+        limit_price_lamports: 0,
+    };
+
+    // instruction discriminator = 3
+    let mut ix_data = vec![3];
+    ix_data.extend_from_slice(bytemuck::bytes_of(&swap_params));
+
+    ReturnVal {
+        ix_data,
+        ..get_swap_accounts(mollusk, program_id, "mint", pre_bought_sol)
+    }
+}
+
+/// Set the deadline and limit price of the swap encoded in `ix_data`
+pub fn set_swap_limits(ix_data: &mut [u8], deadline_slot: u64, limit_price_lamports: u64) {
+    let mut swap_params = bytemuck::pod_read_unaligned::<SwapParams>(&ix_data[1..]);
+    swap_params.deadline_slot = deadline_slot;
+    swap_params.limit_price_lamports = limit_price_lamports;
+    ix_data[1..].copy_from_slice(bytemuck::bytes_of(&swap_params));
+}
+
+/// Get the accounts of a swap against the curve of the mint derived from `mint_seed`, the
+/// returned `ix_data` is empty
+///
+/// Arguments:
+/// - `mint_seed`: seed of the curve mint, distinct seeds give distinct curves
+/// - `pre_bought_sol`: lamports the buyer already spent on the curve
+pub fn get_swap_accounts(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    mint_seed: &str,
+    pre_bought_sol: u64,
+) -> ReturnVal {
+    let SystemConfig {
+        system_config: (system_program, system_program_account),
+        token_config: (token_program, token_program_account),
+        associated_program_config: _,
+    } = get_program_configs();
+
+    let rent = &mollusk.sysvars.rent;
+
+    let buyer = find_deterministic_pubkey("buyer");
+    let buyer_account = Account::new(100 * LAMPORTS_PER_SOL, 0, &system_program);
+
+    let creator = find_deterministic_pubkey("creator");
+    let mint_a = find_deterministic_pubkey("wsol");
+    let mint_b = find_deterministic_pubkey(mint_seed);
+
+    let curve_seeds: &[&[u8]] = &[BondingCurve::SEED_PREFIX, mint_b.as_ref()];
+    let (curve_pda, _) = Pubkey::find_program_address(curve_seeds, program_id);
+
+    let sol_escrow_seeds: &[&[u8]] = &[BondingCurve::SOL_ESCROW_SEED_PREFIX, mint_b.as_ref()];
+    let (sol_escrow_pda, _) = Pubkey::find_program_address(sol_escrow_seeds, program_id);
+
+    let global_seeds: &[&[u8]] = &[GlobalConfig::GLOBAL_PEFIX];
+    let (global_config, _) = Pubkey::find_program_address(global_seeds, program_id);
+
+    let mut global_account = Account::new(
+        rent.minimum_balance(GlobalConfig::SIZE),
+        GlobalConfig::SIZE,
+        program_id,
+    );
+    let global_field = get_global_config();
+    global_account.data = bytemuck::bytes_of(&global_field).to_vec();
+
+    let mut curve = get_curve(program_id, &mint_b, &creator);
+    let mut buyer_tokens = 0;
+
+    if pre_bought_sol > 0 {
+        buyer_tokens = curve
+            .apply_buy(pre_bought_sol, global_field.mint_decimals, 0)
+            .expect("pre-buy should succeed")
+            .token_amount;
+    }
+
+    let mut curve_account = Account::new(
+        rent.minimum_balance(BondingCurve::CURVE_SIZE),
+        BondingCurve::CURVE_SIZE,
+        program_id,
+    );
+    curve_account.data = bytemuck::bytes_of(&curve).to_vec();
+
+    let mint_config = Mint {
+        decimals: global_field.mint_decimals,
+        freeze_authority: COption::Some(to_spl_pubkey(&curve_pda)),
+        is_initialized: true,
+        mint_authority: COption::None,
+        supply: global_field.token_total_supply,
+    };
+    let (_, mint_b_account) = get_mint_accounts(None, mollusk, mint_config);
+    let mint_a_account = Account::new(0, 0, &system_program);
+
+    let buyer_ata_config =
+        get_ata_config(buyer_tokens, to_spl_pubkey(&mint_b), to_spl_pubkey(&buyer));
+    // The max wallet cap is only checked against the buyer's ATA
+    let buyer_mint_ata = get_associated_token_address_with_program_id(
+        &to_associated_pubkey(&buyer),
+        &to_associated_pubkey(&mint_b),
+        &to_associated_pubkey(&token_program),
+    );
+    let (buyer_mint_ata, buyer_mint_ata_account) =
+        get_ata_accounts(Some(buyer_mint_ata.to_bytes()), mollusk, buyer_ata_config);
+
+    let mut curve_ata_config = get_ata_config(
+        curve.real_token_reserves,
+        to_spl_pubkey(&mint_b),
+        to_spl_pubkey(&curve_pda),
+    );
+    curve_ata_config.state = AccountState::Frozen;
+    let (curve_mint_ata, curve_mint_ata_account) = get_ata_accounts(
+        Some(*find_deterministic_pubkey(&format!("{mint_seed}_curve_ata")).as_array()),
+        mollusk,
+        curve_ata_config,
+    );
+
+    let sol_escrow_account = Account::new(
+        rent.minimum_balance(0) + curve.real_sol_reserves,
+        0,
+        &system_program,
+    );
+
+    let fee_receiver = Pubkey::new_from_array(global_field.fee_receiver);
+    let fee_receiver_account = Account::new(rent.minimum_balance(0), 0, &system_program);
+
+    ReturnVal {
+        account_infos: vec![
+            (buyer, buyer_account),
+            (buyer_mint_ata, buyer_mint_ata_account),
+            (mint_a, mint_a_account),
+            (mint_b, mint_b_account),
+            (global_config, global_account),
+            (curve_pda, curve_account),
+            (sol_escrow_pda, sol_escrow_account),
+            (curve_mint_ata, curve_mint_ata_account),
+            (fee_receiver, fee_receiver_account),
+            (system_program, system_program_account),
+            (token_program, token_program_account),
+        ],
+        account_meta: vec![
+            AccountMeta::new(buyer, true),
+            AccountMeta::new(buyer_mint_ata, false),
+            AccountMeta::new_readonly(mint_a, false),
+            AccountMeta::new_readonly(mint_b, false),
+            AccountMeta::new_readonly(global_config, false),
+            AccountMeta::new(curve_pda, false),
+            AccountMeta::new(sol_escrow_pda, false),
+            AccountMeta::new(curve_mint_ata, false),
+            AccountMeta::new(fee_receiver, false),
+            AccountMeta::new_readonly(system_program, false),
+            AccountMeta::new_readonly(token_program, false),
+        ],
+        ix_data: vec![],
+    }
+}
+
+/// Program id of the test transfer hook in `tests/programs/transfer_hook`
+pub const TRANSFER_HOOK_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("FVxHGR7ShqYoAoPxdjfwXB9YXS9qqArMHHir1h58k4hX");
+
+/// Get the accounts for a swap like `get_swap_configs`, on a mint hooked to the test transfer
+/// hook program
+///
+/// The hook program, the validation account of the mint and the transfer counter it lists are
+/// appended to the accounts, in that order.
+pub fn get_transfer_hook_swap_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    base_in: u8,
+    exact_in_amount: u64,
+    min_out_amount: u64,
+    pre_bought_sol: u64,
+) -> ReturnVal {
+    let ReturnVal {
+        mut account_infos,
+        mut account_meta,
+        ix_data,
+    } = get_swap_configs(
+        mollusk,
+        program_id,
+        base_in,
+        exact_in_amount,
+        min_out_amount,
+        pre_bought_sol,
+    );
+
+    let rent = &mollusk.sysvars.rent;
+    let (mint_b, _) = account_infos[3];
+
+    let mint = Token2022Mint::unpack(&account_infos[3].1.data).unwrap();
+    account_infos[3].1 =
+        get_token_2022_account::<Token2022Mint>(rent, ExtensionType::TransferHook, |data| {
+            let mut state =
+                StateWithExtensionsMut::<Token2022Mint>::unpack_uninitialized(data).unwrap();
+            state
+                .init_extension::<TransferHook>(true)
+                .unwrap()
+                .program_id = Some(to_spl_pubkey(&TRANSFER_HOOK_PROGRAM_ID))
+                .try_into()
+                .unwrap();
+            state.base = mint;
+            state.pack_base();
+            state.init_account_type().unwrap();
+        });
+
+    for index in [1, 7] {
+        let token_account = Token2022Account::unpack(&account_infos[index].1.data).unwrap();
+        account_infos[index].1 = get_token_2022_account::<Token2022Account>(
+            rent,
+            ExtensionType::TransferHookAccount,
+            |data| {
+                let mut state =
+                    StateWithExtensionsMut::<Token2022Account>::unpack_uninitialized(data).unwrap();
+                state.init_extension::<TransferHookAccount>(true).unwrap();
+                state.base = token_account;
+                state.pack_base();
+                state.init_account_type().unwrap();
+            },
+        );
+    }
+
+    let counter = find_deterministic_pubkey("transfer_hook_counter");
+    let counter_account = Account::new(rent.minimum_balance(8), 8, &TRANSFER_HOOK_PROGRAM_ID);
+
+    let validation = Pubkey::new_from_array(
+        get_extra_account_metas_address(
+            &to_spl_pubkey(&mint_b),
+            &to_spl_pubkey(&TRANSFER_HOOK_PROGRAM_ID),
+        )
+        .to_bytes(),
+    );
+    let extra_metas =
+        [ExtraAccountMeta::new_with_pubkey(&to_spl_pubkey(&counter), false, true).unwrap()];
+    let validation_len = ExtraAccountMetaList::size_of(extra_metas.len()).unwrap();
+    let mut validation_account = Account::new(
+        rent.minimum_balance(validation_len),
+        validation_len,
+        &TRANSFER_HOOK_PROGRAM_ID,
+    );
+    ExtraAccountMetaList::init::<ExecuteInstruction>(&mut validation_account.data, &extra_metas)
+        .unwrap();
+
+    account_infos.extend([
+        (
+            TRANSFER_HOOK_PROGRAM_ID,
+            mollusk_svm::program::create_program_account_loader_v3(&TRANSFER_HOOK_PROGRAM_ID),
+        ),
+        (validation, validation_account),
+        (counter, counter_account),
+    ]);
+    account_meta.extend([
+        AccountMeta::new_readonly(TRANSFER_HOOK_PROGRAM_ID, false),
+        AccountMeta::new_readonly(validation, false),
+        AccountMeta::new(counter, false),
+    ]);
+
+    ReturnVal {
+        account_infos,
+        account_meta,
+        ix_data,
+    }
+}
+
+/// Creates a Token-2022 account of state `S` sized for `extension`, with its data written by `pack`
+fn get_token_2022_account<S: BaseState + Pack>(
+    rent: &Rent,
+    extension: ExtensionType,
+    pack: impl FnOnce(&mut [u8]),
+) -> Account {
+    let len = ExtensionType::try_calculate_account_len::<S>(&[extension]).unwrap();
+
+    let mut account = Account::new(
+        rent.minimum_balance(len),
+        len,
+        &Pubkey::new_from_array(*spl_token_2022::ID.as_array()),
+    );
+    pack(&mut account.data);
+
+    account
+}
+
+/// Slot the allowlist of `get_allowlist_swap_configs` closes at, after `SWAP_SLOT`
+pub const ALLOWLIST_END_SLOT: u64 = SWAP_SLOT + 100;
+
+/// Get the accounts for a buy of `exact_in_amount` lamports during the allowlist phase of the
+/// curve, the buyer being allowlisted with a `max_buy_lamports` limit
+///
+/// The allowlist holds the buyer and one other wallet, the buyer's proof is appended to the
+/// instruction data. A limited entry inserts the buyer's empty claim PDA after the swap accounts.
+pub fn get_allowlist_swap_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    exact_in_amount: u64,
+    max_buy_lamports: u64,
+) -> ReturnVal {
+    let ReturnVal {
+        mut account_infos,
+        mut account_meta,
+        mut ix_data,
+    } = get_swap_configs(mollusk, program_id, 0, exact_in_amount, 1, 0);
+
+    let (buyer, _) = account_infos[0];
+    let (mint_b, _) = account_infos[3];
+
+    let other_leaf = allowlist_leaf(find_deterministic_pubkey("other_buyer").as_array(), 0);
+    let buyer_leaf = allowlist_leaf(buyer.as_array(), max_buy_lamports);
+
+    let mut curve = *bytemuck::from_bytes::<BondingCurve>(&account_infos[5].1.data);
+    curve.allowlist = Allowlist {
+        root: allowlist_node(&buyer_leaf, &other_leaf),
+        end_slot: ALLOWLIST_END_SLOT,
+    };
+    account_infos[5].1.data = bytemuck::bytes_of(&curve).to_vec();
+
+    ix_data.extend_from_slice(&max_buy_lamports.to_le_bytes());
+    ix_data.extend_from_slice(&other_leaf);
+
+    if max_buy_lamports > 0 {
+        let claim_seeds: &[&[u8]] = &[AllowlistClaim::SEED_PREFIX, mint_b.as_ref(), buyer.as_ref()];
+        let (claim_pda, _) = Pubkey::find_program_address(claim_seeds, program_id);
+
+        account_infos.push((claim_pda, Account::default()));
+        account_meta.push(AccountMeta::new(claim_pda, false));
+    }
+
+    ReturnVal {
+        account_infos,
+        account_meta,
+        ix_data,
+    }
+}
+
+/// Sell cooldown of the config in `get_cooldown_swap_configs`
+pub const SELL_COOLDOWN_SLOTS: u32 = 10;
+
+/// Get the accounts for a swap like `get_swap_configs` under a config with a sell cooldown, the
+/// buyer having pre-bought 1 SOL worth of tokens
+///
+/// The buyer's cooldown PDA is inserted after the swap accounts, recording a buy at
+/// `last_buy_slot`, or empty when it's `None`.
+pub fn get_cooldown_swap_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    base_in: u8,
+    exact_in_amount: u64,
+    last_buy_slot: Option<u64>,
+) -> ReturnVal {
+    let ReturnVal {
+        mut account_infos,
+        mut account_meta,
+        ix_data,
+    } = get_swap_configs(
+        mollusk,
+        program_id,
+        base_in,
+        exact_in_amount,
+        1,
+        LAMPORTS_PER_SOL,
+    );
+
+    let (buyer, _) = account_infos[0];
+    let (mint_b, _) = account_infos[3];
+
+    let mut config = *bytemuck::from_bytes::<GlobalConfig>(&account_infos[4].1.data);
+    config.sell_cooldown_slots = SELL_COOLDOWN_SLOTS;
+    account_infos[4].1.data = bytemuck::bytes_of(&config).to_vec();
+
+    let cooldown_seeds: &[&[u8]] = &[TradeCooldown::SEED_PREFIX, mint_b.as_ref(), buyer.as_ref()];
+    let (cooldown_pda, bump) = Pubkey::find_program_address(cooldown_seeds, program_id);
+
+    let cooldown_account = match last_buy_slot {
+        Some(last_buy_slot) => {
+            let cooldown = TradeCooldown {
+                discriminator: TradeCooldown::DISCRIMINATOR,
+                version: TradeCooldown::VERSION,
+                bump,
+                _padding: [0; 6],
+                mint: mint_b.to_bytes(),
+                wallet: buyer.to_bytes(),
+                last_buy_slot,
+            };
+
+            let mut account = Account::new(
+                mollusk.sysvars.rent.minimum_balance(TradeCooldown::SIZE),
+                TradeCooldown::SIZE,
+                program_id,
+            );
+            account.data = bytemuck::bytes_of(&cooldown).to_vec();
+            account
+        }
+        None => Account::default(),
+    };
+
+    account_infos.push((cooldown_pda, cooldown_account));
+    account_meta.push(AccountMeta::new(cooldown_pda, false));
+
+    ReturnVal {
+        account_infos,
+        account_meta,
+        ix_data,
+    }
+}
@@ -0,0 +1,307 @@
+use crate::helpers::{
+    find_deterministic_pubkey, get_ata_accounts, get_ata_config, get_mint_accounts,
+    get_mint_config, get_program_configs, get_treasury_pubkey,
+    ix_configs::swap_configs::get_global_config, to_spl_pubkey, ReturnVal, SystemConfig,
+};
+use amm::{
+    instructions::{
+        create_treasury::TreasuryRecipientsParams, withdraw_treasury::WithdrawTreasuryParams,
+    },
+    states::{
+        global_config::GlobalConfig,
+        treasury::{Treasury, TreasuryRecipient, TreasuryTokenRecord, MAX_TREASURY_RECIPIENTS},
+    },
+    Discriminator,
+};
+use mollusk_svm::Mollusk;
+use solana_sdk::{
+    account::Account, message::AccountMeta, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+};
+
+/// Fees accrued in the test treasury on top of its rent exemption
+pub const TREASURY_FEES: u64 = 3 * LAMPORTS_PER_SOL;
+
+/// Tokens held by the token account of the test treasury
+pub const TREASURY_TOKENS: u64 = 1_000_000_000;
+
+/// The two recipients of the test treasury, splitting withdrawals 70/30
+pub fn get_treasury_recipients() -> [TreasuryRecipient; 2] {
+    [
+        TreasuryRecipient {
+            wallet: find_deterministic_pubkey("team").to_bytes(),
+            weight_bps: 7_000,
+            _padding: [0; 6],
+        },
+        TreasuryRecipient {
+            wallet: find_deterministic_pubkey("grants").to_bytes(),
+            weight_bps: 3_000,
+            _padding: [0; 6],
+        },
+    ]
+}
+
+pub fn get_treasury(program_id: &Pubkey) -> Treasury {
+    let (_, bump) = Pubkey::find_program_address(&[Treasury::SEED_PREFIX], program_id);
+
+    let mut recipients = [TreasuryRecipient::default(); MAX_TREASURY_RECIPIENTS];
+    recipients[..2].copy_from_slice(&get_treasury_recipients());
+
+    Treasury {
+        discriminator: Treasury::DISCRIMINATOR,
+        version: Treasury::VERSION,
+        bump,
+        recipient_count: 2,
+        _padding: [0; 5],
+        total_withdrawn_lamports: 0,
+        recipients,
+    }
+}
+
+fn get_global_account(mollusk: &Mollusk, program_id: &Pubkey) -> (Pubkey, Account) {
+    let (global_config, _) =
+        Pubkey::find_program_address(&[GlobalConfig::GLOBAL_PEFIX], program_id);
+    let mut global_account = Account::new(
+        mollusk.sysvars.rent.minimum_balance(GlobalConfig::SIZE),
+        GlobalConfig::SIZE,
+        program_id,
+    );
+    global_account.data = bytemuck::bytes_of(&get_global_config()).to_vec();
+
+    (global_config, global_account)
+}
+
+/// Get the accounts for the admin creating the test treasury, `prefunded` lamports already sit at
+/// the treasury address
+pub fn get_create_treasury_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    prefunded: u64,
+) -> ReturnVal {
+    let SystemConfig {
+        system_config: (system_program, system_program_account),
+        token_config: _,
+        associated_program_config: _,
+    } = get_program_configs();
+
+    let admin = Pubkey::new_from_array(get_global_config().admin);
+    let (global_config, global_account) = get_global_account(mollusk, program_id);
+    let treasury = get_treasury_pubkey();
+
+    let mut recipients = [TreasuryRecipient::default(); MAX_TREASURY_RECIPIENTS];
+    recipients[..2].copy_from_slice(&get_treasury_recipients());
+
+    let params = TreasuryRecipientsParams {
+        recipient_count: 2,
+        padding: [0; 7],
+        recipients,
+    };
+
+    // instruction discriminator = 15
+    let mut ix_data = vec![15];
+    ix_data.extend_from_slice(bytemuck::bytes_of(&params));
+
+    ReturnVal {
+        account_infos: vec![
+            (
+                admin,
+                Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program),
+            ),
+            (global_config, global_account),
+            (treasury, Account::new(prefunded, 0, &system_program)),
+            (system_program, system_program_account),
+        ],
+        account_meta: vec![
+            AccountMeta::new(admin, true),
+            AccountMeta::new_readonly(global_config, false),
+            AccountMeta::new(treasury, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+        ix_data,
+    }
+}
+
+/// Get the accounts for the admin withdrawing `amount` lamports from a treasury holding
+/// `TREASURY_FEES`
+pub fn get_withdraw_treasury_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    amount: u64,
+) -> ReturnVal {
+    let rent = &mollusk.sysvars.rent;
+
+    let admin = Pubkey::new_from_array(get_global_config().admin);
+    let (global_config, global_account) = get_global_account(mollusk, program_id);
+    let treasury = get_treasury_pubkey();
+
+    let mut treasury_account = Account::new(
+        rent.minimum_balance(Treasury::SIZE) + TREASURY_FEES,
+        Treasury::SIZE,
+        program_id,
+    );
+    treasury_account.data = bytemuck::bytes_of(&get_treasury(program_id)).to_vec();
+
+    let params = WithdrawTreasuryParams {
+        asset: WithdrawTreasuryParams::SOL,
+        padding: [0; 7],
+        amount,
+    };
+
+    // instruction discriminator = 17
+    let mut ix_data = vec![17];
+    ix_data.extend_from_slice(bytemuck::bytes_of(&params));
+
+    let mut account_infos = vec![
+        (admin, Account::new(LAMPORTS_PER_SOL, 0, &Pubkey::default())),
+        (global_config, global_account),
+        (treasury, treasury_account),
+    ];
+    let mut account_meta = vec![
+        AccountMeta::new_readonly(admin, true),
+        AccountMeta::new_readonly(global_config, false),
+        AccountMeta::new(treasury, false),
+    ];
+
+    for recipient in get_treasury_recipients() {
+        let wallet = Pubkey::new_from_array(recipient.wallet);
+
+        account_infos.push((
+            wallet,
+            Account::new(LAMPORTS_PER_SOL, 0, &Pubkey::default()),
+        ));
+        account_meta.push(AccountMeta::new(wallet, false));
+    }
+
+    ReturnVal {
+        account_infos,
+        account_meta,
+        ix_data,
+    }
+}
+
+/// Token record of the test treasury mint with `total_withdrawn` tokens already withdrawn
+pub fn get_treasury_token_record(program_id: &Pubkey, total_withdrawn: u64) -> TreasuryTokenRecord {
+    let mint = find_deterministic_pubkey("treasury_mint");
+    let (_, bump) = Pubkey::find_program_address(
+        &[TreasuryTokenRecord::SEED_PREFIX, mint.as_ref()],
+        program_id,
+    );
+
+    TreasuryTokenRecord {
+        discriminator: TreasuryTokenRecord::DISCRIMINATOR,
+        version: TreasuryTokenRecord::VERSION,
+        bump,
+        _padding: [0; 6],
+        mint: mint.to_bytes(),
+        total_withdrawn,
+    }
+}
+
+/// Get the accounts for the admin withdrawing `amount` tokens from a treasury token account
+/// holding `TREASURY_TOKENS`, `withdrawn_before` tokens are already recorded for the mint, none
+/// leaves the token record uncreated
+pub fn get_withdraw_treasury_token_configs(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    amount: u64,
+    withdrawn_before: Option<u64>,
+) -> ReturnVal {
+    let SystemConfig {
+        system_config: (system_program, system_program_account),
+        token_config: (token_program, token_program_account),
+        associated_program_config: _,
+    } = get_program_configs();
+
+    let rent = &mollusk.sysvars.rent;
+
+    let ReturnVal {
+        mut account_infos,
+        mut account_meta,
+        ix_data: _,
+    } = get_withdraw_treasury_configs(mollusk, program_id, 0);
+    account_infos.truncate(3);
+    account_meta.truncate(3);
+
+    // The admin pays for the token record
+    account_meta[0] = AccountMeta::new(account_meta[0].pubkey, true);
+
+    let treasury = get_treasury_pubkey();
+    let (mint, mint_account) = get_mint_accounts(
+        Some(*find_deterministic_pubkey("treasury_mint").as_array()),
+        mollusk,
+        get_mint_config(TREASURY_TOKENS),
+    );
+    let (treasury_token_account, treasury_token_data) = get_ata_accounts(
+        Some(*find_deterministic_pubkey("treasury_ata").as_array()),
+        mollusk,
+        get_ata_config(
+            TREASURY_TOKENS,
+            to_spl_pubkey(&mint),
+            to_spl_pubkey(&treasury),
+        ),
+    );
+
+    let (record, _) = Pubkey::find_program_address(
+        &[TreasuryTokenRecord::SEED_PREFIX, mint.as_ref()],
+        program_id,
+    );
+    let record_account = match withdrawn_before {
+        Some(total_withdrawn) => {
+            let mut record_account = Account::new(
+                rent.minimum_balance(TreasuryTokenRecord::SIZE),
+                TreasuryTokenRecord::SIZE,
+                program_id,
+            );
+            record_account.data =
+                bytemuck::bytes_of(&get_treasury_token_record(program_id, total_withdrawn))
+                    .to_vec();
+            record_account
+        }
+        None => Account::new(0, 0, &system_program),
+    };
+
+    account_infos.extend([
+        (mint, mint_account),
+        (treasury_token_account, treasury_token_data),
+        (token_program, token_program_account),
+        (record, record_account),
+        (system_program, system_program_account),
+    ]);
+    account_meta.extend([
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new(treasury_token_account, false),
+        AccountMeta::new_readonly(token_program, false),
+        AccountMeta::new(record, false),
+        AccountMeta::new_readonly(system_program, false),
+    ]);
+
+    for (index, recipient) in get_treasury_recipients().into_iter().enumerate() {
+        let (token_account, token_account_data) = get_ata_accounts(
+            Some(*find_deterministic_pubkey(&format!("recipient_ata_{index}")).as_array()),
+            mollusk,
+            get_ata_config(
+                0,
+                to_spl_pubkey(&mint),
+                to_spl_pubkey(&Pubkey::new_from_array(recipient.wallet)),
+            ),
+        );
+
+        account_infos.push((token_account, token_account_data));
+        account_meta.push(AccountMeta::new(token_account, false));
+    }
+
+    let params = WithdrawTreasuryParams {
+        asset: WithdrawTreasuryParams::TOKEN,
+        padding: [0; 7],
+        amount,
+    };
+
+    // instruction discriminator = 17
+    let mut ix_data = vec![17];
+    ix_data.extend_from_slice(bytemuck::bytes_of(&params));
+
+    ReturnVal {
+        account_infos,
+        account_meta,
+        ix_data,
+    }
+}
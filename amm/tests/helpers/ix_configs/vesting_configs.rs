@@ -0,0 +1,131 @@
+use crate::helpers::{
+    find_deterministic_pubkey, get_ata_accounts, get_ata_config, get_mint_accounts,
+    get_program_configs,
+    ix_configs::swap_configs::{get_curve, get_global_config},
+    to_spl_pubkey, ReturnVal, SystemConfig,
+};
+use amm::{
+    states::{bonding_curve::BondingCurve, vesting::Vesting},
+    Discriminator,
+};
+use mollusk_svm::Mollusk;
+use solana_sdk::{account::Account, message::AccountMeta, pubkey::Pubkey};
+use spl_token::{solana_program::program_option::COption, state::Mint};
+
+pub const VESTING_START_SLOT: u64 = 0;
+pub const VESTING_CLIFF_SLOTS: u64 = 100;
+pub const VESTING_DURATION_SLOTS: u64 = 1_000;
+
+fn get_mint_account(mollusk: &Mollusk, curve_pda: &Pubkey) -> Account {
+    let global_field = get_global_config();
+
+    let mint_config = Mint {
+        decimals: global_field.mint_decimals,
+        freeze_authority: COption::Some(to_spl_pubkey(curve_pda)),
+        is_initialized: true,
+        mint_authority: COption::None,
+        supply: global_field.token_total_supply,
+    };
+
+    get_mint_accounts(None, mollusk, mint_config).1
+}
+
+/// Returns the vesting of the "mint" curve's allocation to the creator, nothing claimed yet
+pub fn get_vesting(program_id: &Pubkey) -> Vesting {
+    let mint = find_deterministic_pubkey("mint");
+    let creator = find_deterministic_pubkey("creator");
+    let (_, bump) =
+        Pubkey::find_program_address(&[Vesting::SEED_PREFIX, mint.as_ref()], program_id);
+    let global_field = get_global_config();
+
+    Vesting {
+        discriminator: Vesting::DISCRIMINATOR,
+        version: Vesting::VERSION,
+        bump,
+        _padding: [0u8; 6],
+        mint: *mint.as_array(),
+        beneficiary: *creator.as_array(),
+        total_amount: global_field.token_total_supply - global_field.initial_real_token_reserves,
+        claimed_amount: 0,
+        start_slot: VESTING_START_SLOT,
+        cliff_slots: VESTING_CLIFF_SLOTS,
+        duration_slots: VESTING_DURATION_SLOTS,
+    }
+}
+
+/// Get the accounts for claiming the vested allocation of the "mint" curve to the creator
+pub fn get_claim_vested_configs(mollusk: &Mollusk, program_id: &Pubkey) -> ReturnVal {
+    let SystemConfig {
+        system_config: _,
+        token_config: (token_program, token_program_account),
+        associated_program_config: _,
+    } = get_program_configs();
+
+    let rent = &mollusk.sysvars.rent;
+
+    let creator = find_deterministic_pubkey("creator");
+    let mint = find_deterministic_pubkey("mint");
+
+    let (curve_pda, _) =
+        Pubkey::find_program_address(&[BondingCurve::SEED_PREFIX, mint.as_ref()], program_id);
+    let mint_account = get_mint_account(mollusk, &curve_pda);
+
+    let curve = get_curve(program_id, &mint, &creator);
+    let mut curve_account = Account::new(
+        rent.minimum_balance(BondingCurve::CURVE_SIZE),
+        BondingCurve::CURVE_SIZE,
+        program_id,
+    );
+    curve_account.data = bytemuck::bytes_of(&curve).to_vec();
+
+    let vesting = get_vesting(program_id);
+    let (vesting_pda, _) =
+        Pubkey::find_program_address(&[Vesting::SEED_PREFIX, mint.as_ref()], program_id);
+    let mut vesting_account = Account::new(
+        rent.minimum_balance(Vesting::SIZE),
+        Vesting::SIZE,
+        program_id,
+    );
+    vesting_account.data = bytemuck::bytes_of(&vesting).to_vec();
+
+    let vault_config = get_ata_config(
+        vesting.total_amount,
+        to_spl_pubkey(&mint),
+        to_spl_pubkey(&vesting_pda),
+    );
+    let (vesting_vault, vesting_vault_account) = get_ata_accounts(
+        Some(*find_deterministic_pubkey("vesting_vault").as_array()),
+        mollusk,
+        vault_config,
+    );
+
+    let creator_ata_config = get_ata_config(0, to_spl_pubkey(&mint), to_spl_pubkey(&creator));
+    let (creator_mint_ata, creator_mint_ata_account) = get_ata_accounts(
+        Some(*find_deterministic_pubkey("creator_ata").as_array()),
+        mollusk,
+        creator_ata_config,
+    );
+
+    // instruction discriminator = 8
+    let ix_data = vec![8];
+
+    ReturnVal {
+        account_infos: vec![
+            (vesting_pda, vesting_account),
+            (mint, mint_account),
+            (curve_pda, curve_account),
+            (vesting_vault, vesting_vault_account),
+            (creator_mint_ata, creator_mint_ata_account),
+            (token_program, token_program_account),
+        ],
+        account_meta: vec![
+            AccountMeta::new(vesting_pda, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(curve_pda, false),
+            AccountMeta::new(vesting_vault, false),
+            AccountMeta::new(creator_mint_ata, false),
+            AccountMeta::new_readonly(token_program, false),
+        ],
+        ix_data,
+    }
+}
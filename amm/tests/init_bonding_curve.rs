@@ -3,21 +3,40 @@ mod helpers;
 #[cfg(test)]
 pub mod init_curve_tests {
     use super::*;
-    use amm::{states::bonding_curve::BondingCurve, ID};
+    use amm::{
+        states::{
+            allowlist::Allowlist,
+            bonding_curve::{BondingCurve, CurveStatus},
+            crowdfund::Crowdfund,
+        },
+        transfer_fee::{TransferFee, MINT_WITH_TRANSFER_FEE_LEN},
+        AmmError, Discriminator, ID,
+    };
     use helpers::{
         get_ata_accounts, get_ata_config, get_mint_accounts, get_mollusk,
-        ix_configs::init_bonding_curve_configs::get_init_bonding_curve_configs, to_spl_pubkey,
-        ReturnVal,
+        ix_configs::{
+            init_bonding_curve_configs::{
+                get_creation_fee_configs, get_dev_buy_configs, get_init_bonding_curve_configs,
+                get_transfer_fee_configs, get_transfer_hook_configs,
+            },
+            swap_configs::{get_curve, TRANSFER_HOOK_PROGRAM_ID},
+            vesting_configs::get_vesting,
+        },
+        to_spl_pubkey, ReturnVal,
     };
     use mollusk_svm::result::Check;
     use pinocchio_token_2022::state::TokenAccount as PTokenAccount;
-    use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+    use solana_sdk::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
     use spl_associated_token_account::solana_program::program_pack::Pack;
     use spl_token::{
-        solana_program::program_option::COption,
+        solana_program::{program_option::COption, pubkey::Pubkey as sPubkey},
         state::{Account as ATA, Mint},
     };
-    use spl_token_2022::ID as token_2022;
+    use spl_token_2022::{
+        extension::{transfer_hook::TransferHook, BaseStateWithExtensions, StateWithExtensions},
+        state::Mint as Token2022Mint,
+        ID as token_2022,
+    };
 
     #[test]
     pub fn test_init_curve_runs_sucessfully() {
@@ -35,6 +54,8 @@ pub mod init_curve_tests {
         let bonding_curve_account = account_infos[2].0;
         let mint_account = account_infos[3].0;
         let curve_mint_ata = account_infos[4].0;
+        let curve_sol_escrow = account_infos[5].0;
+        let system_program = account_infos[6].0;
 
         let curve_seeds: &[&[u8]] = &[BondingCurve::SEED_PREFIX, mint_account.as_ref()];
         let (_, curve_bump) = Pubkey::find_program_address(curve_seeds, &program_id);
@@ -45,13 +66,19 @@ pub mod init_curve_tests {
             virtual_sol_reserves: 30000000000,
             virtual_token_reserves: 1_073_000_000_000_000,
             real_sol_reserves: 0,
-            _padding: [0u8; 6],
+            discriminator: BondingCurve::DISCRIMINATOR,
+            version: BondingCurve::VERSION,
+            vault_mode: BondingCurve::FROZEN_VAULT,
+            _padding: [0u8; 2],
+            max_wallet_bps: 0,
             bump: curve_bump,
             mint: *mint_account.as_array(),
-            complete: 0,
+            status: CurveStatus::Active as u8,
             creator: *account_infos[0].0.as_array(),
             real_token_reserves: 793_100_000_000_000,
             starting_slot: mollusk.sysvars.clock.slot,
+            allowlist: Allowlist::default(),
+            crowdfund: Crowdfund::default(),
         };
 
         let expected_data_bytes = bytemuck::bytes_of(&expected_curve_data);
@@ -94,6 +121,11 @@ pub mod init_curve_tests {
                 .owner(&token_program)
                 .data(&expected_mint_account.data)
                 .build(),
+            Check::account(&curve_sol_escrow)
+                .space(0)
+                .lamports(mollusk.sysvars.rent.minimum_balance(0))
+                .owner(&system_program)
+                .build(),
             // Yet to implement
             // Check::account(&curve_mint_ata)
             //     .space(PTokenAccount::BASE_LEN)
@@ -104,4 +136,286 @@ pub mod init_curve_tests {
 
         let _ = mollusk.process_and_validate_instruction(&ix, &account_infos, &checks);
     }
+
+    #[test]
+    pub fn test_init_curve_fails_if_any_account_exists() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+        let ReturnVal {
+            mut account_infos,
+            account_meta,
+            ix_data,
+        } = get_init_bonding_curve_configs(&mollusk, &program_id);
+
+        // Only the sol escrow already holds data, the other new accounts are still empty
+        account_infos[5].1.data = vec![0u8; 8];
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let _ = mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::AccountAlreadyInitialized)],
+        );
+    }
+
+    #[test]
+    pub fn test_init_curve_with_dev_buy() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+        let dev_buy_lamports = 1_000_000_000;
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_dev_buy_configs(&mollusk, &program_id, dev_buy_lamports, 100);
+
+        let creator = account_infos[0].0;
+        let mint = account_infos[3].0;
+        let creator_mint_ata = account_infos[12].0;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let result = mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::success(), Check::all_rent_exempt()],
+        );
+
+        // The config's 1% creator rate applies instead of the 99% launch fee
+        let fee_lamports = dev_buy_lamports / 100;
+        let buy_result = get_curve(&program_id, &mint, &creator)
+            .apply_buy(dev_buy_lamports - fee_lamports, 6, 0)
+            .unwrap();
+
+        let creator_ata =
+            ATA::unpack(&result.get_account(&creator_mint_ata).unwrap().data).unwrap();
+
+        assert_eq!(creator_ata.amount, buy_result.token_amount);
+        assert_eq!(creator_ata.owner, to_spl_pubkey(&creator));
+    }
+
+    #[test]
+    pub fn test_init_curve_with_transfer_fee() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_transfer_fee_configs(&mollusk, &program_id, 250, 1_000_000);
+
+        let mint = account_infos[3].0;
+        let token_program = account_infos[7].0;
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let result = mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&mint)
+                    .space(MINT_WITH_TRANSFER_FEE_LEN)
+                    .owner(&token_program)
+                    .build(),
+            ],
+        );
+
+        let mint_data = &result.get_account(&mint).unwrap().data;
+
+        assert_eq!(
+            TransferFee::from_mint_data(mint_data),
+            Ok(TransferFee {
+                basis_points: 250,
+                maximum_fee: 1_000_000,
+            })
+        );
+    }
+
+    #[test]
+    pub fn test_init_curve_with_transfer_hook() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_transfer_hook_configs(&mollusk, &program_id, &TRANSFER_HOOK_PROGRAM_ID);
+
+        let mint = account_infos[3].0;
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let result =
+            mollusk.process_and_validate_instruction(&ix, &account_infos, &[Check::success()]);
+
+        let mint_data = &result.get_account(&mint).unwrap().data;
+        let mint_state = StateWithExtensions::<Token2022Mint>::unpack(mint_data).unwrap();
+        let transfer_hook = mint_state.get_extension::<TransferHook>().unwrap();
+
+        assert_eq!(
+            Option::<sPubkey>::from(transfer_hook.program_id),
+            Some(to_spl_pubkey(&TRANSFER_HOOK_PROGRAM_ID))
+        );
+        assert_eq!(Option::<sPubkey>::from(transfer_hook.authority), None);
+    }
+
+    #[test]
+    pub fn test_init_curve_fails_for_transfer_hook_not_allowed() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_transfer_hook_configs(&mollusk, &program_id, &Pubkey::new_unique());
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::TransferHookNotAllowed as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    pub fn test_init_curve_fails_above_transfer_fee_cap() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        // The config caps the transfer fee of a single transfer at 10_000_000
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_transfer_fee_configs(&mollusk, &program_id, 250, 10_000_001);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    pub fn test_init_curve_fails_above_max_transfer_fee() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        // The config caps the transfer fee at 500 bps
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_transfer_fee_configs(&mollusk, &program_id, 501, 1_000_000);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    pub fn test_init_curve_charges_creation_fee() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+        let creation_fee_lamports = 20_000_000;
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_creation_fee_configs(&mollusk, &program_id, creation_fee_lamports);
+
+        let (fee_receiver, fee_receiver_account) = &account_infos[9];
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(fee_receiver)
+                    .lamports(fee_receiver_account.lamports + creation_fee_lamports)
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    pub fn test_init_curve_fails_for_other_fee_receiver() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            mut account_infos,
+            mut account_meta,
+            ix_data,
+        } = get_creation_fee_configs(&mollusk, &program_id, 20_000_000);
+
+        let other = Pubkey::new_unique();
+        account_infos[9].0 = other;
+        account_meta[9].pubkey = other;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::IncorrectProgramId)],
+        );
+    }
+
+    #[test]
+    pub fn test_init_curve_vests_allocation() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_init_bonding_curve_configs(&mollusk, &program_id);
+
+        let curve_mint_ata = account_infos[4].0;
+        let vesting_pda = account_infos[10].0;
+        let vesting_vault = account_infos[11].0;
+
+        // The config's schedule and beneficiary apply, starting at the launch
+        let mut expected_vesting = get_vesting(&program_id);
+        expected_vesting.start_slot = mollusk.sysvars.clock.slot;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let result = mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::all_rent_exempt(),
+                Check::account(&vesting_pda)
+                    .owner(&program_id)
+                    .data(bytemuck::bytes_of(&expected_vesting))
+                    .build(),
+            ],
+        );
+
+        let vault = ATA::unpack(&result.get_account(&vesting_vault).unwrap().data).unwrap();
+        let curve_ata = ATA::unpack(&result.get_account(&curve_mint_ata).unwrap().data).unwrap();
+
+        assert_eq!(vault.amount, expected_vesting.total_amount);
+        assert_eq!(vault.owner, to_spl_pubkey(&vesting_pda));
+        assert_eq!(curve_ata.amount, 793_100_000_000_000);
+    }
 }
@@ -4,11 +4,19 @@ pub mod helpers;
 pub mod init_global_tests {
     use super::*;
     use amm::{
-        states::global_config::{GlobalConfig, GlobalSettingsInput},
-        ID,
+        states::{
+            global_config::{GlobalConfig, GlobalSettingsInput},
+            vesting::Vesting,
+        },
+        Discriminator, ID,
     };
     use helpers::{
-        get_mollusk, ix_configs::init_global_configs::get_init_global_configs, ReturnVal,
+        get_mollusk, get_treasury_pubkey,
+        ix_configs::{
+            init_global_configs::get_init_global_configs,
+            vesting_configs::{VESTING_CLIFF_SLOTS, VESTING_DURATION_SLOTS},
+        },
+        ReturnVal,
     };
     use mollusk_svm::result::Check;
     use solana_sdk::{
@@ -28,16 +36,30 @@ pub mod init_global_tests {
         } = get_init_global_configs(&program_id);
 
         let ix_args = GlobalConfig {
+            discriminator: GlobalConfig::DISCRIMINATOR,
+            version: GlobalConfig::VERSION,
             mint_decimals: 6,
-            _padding: [0; 6],
+            _padding: [0; 1],
+            max_transfer_fee_bps: 500,
+            creator_fee_bps: 0,
             inittialized: 1,
             admin: Pubkey::new_from_array([0x2; 32]).to_bytes(),
-            fee_receiver: Pubkey::new_from_array([0x1; 32]).to_bytes(),
+            fee_receiver: get_treasury_pubkey().to_bytes(),
 
             initial_real_token_reserves: 793_100_000_000_000,
             initial_virtual_token_reserves: 1_073_000_000_000_000,
             initial_virtual_sol_reserves: 30000000000,
             token_total_supply: 1_000_000_000_000_000,
+            creation_fee_lamports: 0,
+            max_price_impact_bps: 0,
+            _padding_end: [0; 2],
+            sell_cooldown_slots: 0,
+            max_transfer_fee: 10_000_000,
+            vesting_cliff_slots: VESTING_CLIFF_SLOTS,
+            vesting_duration_slots: VESTING_DURATION_SLOTS,
+            vesting_beneficiary: Vesting::CREATOR_BENEFICIARY,
+            _padding_vesting: [0; 7],
+            allowed_transfer_hooks: [[0; 32]; GlobalConfig::MAX_TRANSFER_HOOKS],
         };
 
         let global_config_account = &account_meta[1].pubkey.clone();
@@ -166,4 +188,32 @@ pub mod init_global_tests {
             )],
         );
     }
+
+    #[test]
+    pub fn test_fails_if_vesting_cliff_exceeds_duration() {
+        let program_id = Pubkey::new_from_array(ID);
+
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_meta,
+            account_infos,
+            mut ix_data,
+        } = get_init_global_configs(&program_id);
+
+        let mut settings = bytemuck::pod_read_unaligned::<GlobalSettingsInput>(&ix_data[1..]);
+        settings.vesting_cliff_slots = settings.vesting_duration_slots + 1;
+        ix_data.truncate(1);
+        ix_data.extend_from_slice(bytemuck::bytes_of(&settings));
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(
+                solana_sdk::program_error::ProgramError::InvalidInstructionData,
+            )],
+        );
+    }
 }
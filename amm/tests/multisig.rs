@@ -0,0 +1,320 @@
+mod helpers;
+
+#[cfg(test)]
+pub mod multisig_tests {
+    use super::*;
+    use amm::{
+        states::{
+            global_config::GlobalConfig,
+            multisig::{Multisig, Proposal},
+        },
+        AmmError, ID,
+    };
+    use helpers::{
+        get_mollusk,
+        ix_configs::multisig_configs::{
+            get_approve_proposal_configs, get_create_multisig_configs, get_create_proposal_configs,
+            get_execute_proposal_configs, get_multisig, get_new_global_settings,
+            get_update_global_configs, get_update_global_proposal,
+        },
+        ReturnVal,
+    };
+    use mollusk_svm::result::Check;
+    use solana_sdk::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
+
+    #[test]
+    pub fn test_update_global_works() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+        let admin = Pubkey::new_from_array([0x2; 32]);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_update_global_configs(&mollusk, &program_id, &admin);
+
+        let global_config = account_infos[1].0;
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let result =
+            mollusk.process_and_validate_instruction(&ix, &account_infos, &[Check::success()]);
+
+        let settings = get_new_global_settings();
+        let global_data = *bytemuck::from_bytes::<GlobalConfig>(
+            &result.get_account(&global_config).unwrap().data,
+        );
+
+        assert_eq!(global_data.creator_fee_bps, settings.creator_fee_bps);
+        assert_eq!(
+            global_data.max_transfer_fee_bps,
+            settings.max_transfer_fee_bps
+        );
+    }
+
+    #[test]
+    pub fn test_update_global_fails_for_non_admin() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+        let admin = Pubkey::new_from_array([0x2; 32]);
+
+        let ReturnVal {
+            mut account_infos,
+            mut account_meta,
+            ix_data,
+        } = get_update_global_configs(&mollusk, &program_id, &admin);
+
+        let impostor = Pubkey::new_unique();
+        account_infos[0].0 = impostor;
+        account_meta[0].pubkey = impostor;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::IncorrectAuthority)],
+        );
+    }
+
+    #[test]
+    pub fn test_create_multisig_works() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_create_multisig_configs(&program_id);
+
+        let (multisig, _, expected_multisig) = get_multisig(&program_id);
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&multisig)
+                    .space(Multisig::SIZE)
+                    .data(bytemuck::bytes_of(&expected_multisig))
+                    .owner(&program_id)
+                    .rent_exempt()
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    pub fn test_create_multisig_fails_above_signer_count() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            mut ix_data,
+        } = get_create_multisig_configs(&program_id);
+
+        // threshold of 4 out of 3 signers
+        ix_data[1] = 4;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    pub fn test_create_proposal_works() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_create_proposal_configs(&mollusk, &program_id);
+
+        let (multisig, _, mut expected_multisig) = get_multisig(&program_id);
+        let (proposal, expected_proposal) = get_update_global_proposal(&program_id, 0);
+        expected_multisig.proposal_count = 1;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&proposal)
+                    .space(Proposal::SIZE)
+                    .data(bytemuck::bytes_of(&expected_proposal))
+                    .owner(&program_id)
+                    .build(),
+                Check::account(&multisig)
+                    .data(bytemuck::bytes_of(&expected_multisig))
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    pub fn test_approve_proposal_works() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_approve_proposal_configs(&mollusk, &program_id, 0b001);
+
+        let (proposal, expected_proposal) = get_update_global_proposal(&program_id, 0b011);
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&proposal)
+                    .data(bytemuck::bytes_of(&expected_proposal))
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    pub fn test_approve_proposal_fails_twice() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_approve_proposal_configs(&mollusk, &program_id, 0b010);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::AlreadyApproved as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    pub fn test_approve_proposal_fails_for_non_signer() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            mut account_infos,
+            mut account_meta,
+            ix_data,
+        } = get_approve_proposal_configs(&mollusk, &program_id, 0);
+
+        let outsider = Pubkey::new_unique();
+        account_infos[0].0 = outsider;
+        account_meta[0].pubkey = outsider;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::NotMultisigSigner as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    pub fn test_execute_proposal_updates_global() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_execute_proposal_configs(&mollusk, &program_id, 0b101);
+
+        let (proposal, _) = get_update_global_proposal(&program_id, 0b101);
+        let (_, vault, _) = get_multisig(&program_id);
+        let global_config = account_infos[4].0;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let result =
+            mollusk.process_and_validate_instruction(&ix, &account_infos, &[Check::success()]);
+
+        let global_data = *bytemuck::from_bytes::<GlobalConfig>(
+            &result.get_account(&global_config).unwrap().data,
+        );
+        let proposal_data =
+            *bytemuck::from_bytes::<Proposal>(&result.get_account(&proposal).unwrap().data);
+
+        assert_eq!(global_data.admin, vault.to_bytes());
+        assert_eq!(
+            global_data.creator_fee_bps,
+            get_new_global_settings().creator_fee_bps
+        );
+        assert_eq!(proposal_data.executed, 1);
+    }
+
+    #[test]
+    pub fn test_execute_proposal_fails_below_threshold() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_execute_proposal_configs(&mollusk, &program_id, 0b100);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::ThresholdNotMet as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    pub fn test_execute_proposal_fails_with_other_accounts() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            mut account_infos,
+            mut account_meta,
+            ix_data,
+        } = get_execute_proposal_configs(&mollusk, &program_id, 0b011);
+
+        // the proposal was approved against the global config, not an arbitrary account
+        let other = Pubkey::new_unique();
+        account_infos[4].0 = other;
+        account_meta[5].pubkey = other;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::InvalidArgument)],
+        );
+    }
+}
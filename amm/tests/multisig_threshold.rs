@@ -0,0 +1,54 @@
+#[cfg(test)]
+pub mod multisig_threshold_tests {
+    use amm::states::multisig::{Multisig, Proposal, MAX_MULTISIG_SIGNERS};
+    use bytemuck::Zeroable;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_signer_set_validation() {
+        let signers = [[0x1; 32], [0x2; 32], [0x3; 32]];
+
+        assert!(Multisig::validate_signers(&signers, 1).is_ok());
+        assert!(Multisig::validate_signers(&signers, 3).is_ok());
+        assert!(Multisig::validate_signers(&signers, 0).is_err());
+        assert!(Multisig::validate_signers(&signers, 4).is_err());
+        assert!(Multisig::validate_signers(&[], 0).is_err());
+        assert!(Multisig::validate_signers(&[[0x1; 32], [0x1; 32]], 1).is_err());
+        assert!(Multisig::validate_signers(&[[0x1; 32], [0; 32]], 1).is_err());
+        assert!(Multisig::validate_signers(&[[0x1; 32]; MAX_MULTISIG_SIGNERS + 1], 1).is_err());
+    }
+
+    #[test]
+    fn test_proposal_executes_once() {
+        let mut proposal = Proposal::zeroed();
+
+        proposal.approve(0).unwrap();
+        proposal.approve(2).unwrap();
+
+        assert!(proposal.approve(2).is_err());
+        assert!(proposal.execute(2).is_ok());
+        assert!(proposal.execute(2).is_err());
+        // Approvals on an executed proposal change nothing
+        assert!(proposal.approve(1).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_execution_needs_threshold_distinct_approvals(
+            approvers in prop::collection::vec(0..MAX_MULTISIG_SIGNERS, 0..20),
+            threshold in 1..=MAX_MULTISIG_SIGNERS as u8,
+        ) {
+            let mut proposal = Proposal::zeroed();
+            let mut distinct = 0u32;
+
+            for signer_index in approvers {
+                if proposal.approve(signer_index).is_ok() {
+                    distinct += 1;
+                }
+            }
+
+            prop_assert_eq!(proposal.approval_count(), distinct);
+            prop_assert_eq!(proposal.execute(threshold).is_ok(), distinct >= threshold as u32);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Transfer hook used by the amm tests, counts the transfers of the mints it hooks
+//!
+//! Build it next to the amm program before running the tests:
+//! `cargo build-sbf --manifest-path tests/programs/transfer_hook/Cargo.toml --sbf-out-dir target/deploy`
+//!
+//! `Execute` expects the validation account of the mint to list a single extra account, the
+//! counter, owned by this program and at least 8 bytes long.
+#![no_std]
+use pinocchio::{
+    account_info::AccountInfo, no_allocator, nostd_panic_handler, program_entrypoint,
+    program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+use pinocchio_pubkey::declare_id;
+
+program_entrypoint!(process_instruction);
+nostd_panic_handler!();
+no_allocator!();
+
+declare_id!("FVxHGR7ShqYoAoPxdjfwXB9YXS9qqArMHHir1h58k4hX");
+
+/// `spl-transfer-hook-interface:execute` discriminator
+pub const EXECUTE_DISCRIMINATOR: [u8; 8] = [105, 37, 101, 197, 75, 251, 102, 26];
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let Some((discriminator, _amount)) = instruction_data.split_first_chunk::<8>() else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    if *discriminator != EXECUTE_DISCRIMINATOR {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let [_source, _mint, _destination, _authority, _validation, counter, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !counter.is_owned_by(program_id) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let mut data = counter.try_borrow_mut_data()?;
+    let count = data
+        .first_chunk_mut::<8>()
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+    *count = (u64::from_le_bytes(*count) + 1).to_le_bytes();
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+mod helpers;
+
+#[cfg(test)]
+pub mod quote_tests {
+    use super::*;
+    use amm::{
+        instructions::quote::SwapQuote,
+        states::{
+            bonding_curve::{BondingCurve, CurveStatus},
+            global_config::GlobalConfig,
+        },
+        AmmError, ID,
+    };
+    use helpers::{
+        get_mollusk,
+        ix_configs::{
+            quote_configs::get_quote_configs,
+            swap_configs::{get_curve, SWAP_SLOT},
+        },
+        ReturnVal,
+    };
+    use mollusk_svm::result::Check;
+    use solana_sdk::{
+        instruction::Instruction, native_token::LAMPORTS_PER_SOL, program_error::ProgramError,
+        pubkey::Pubkey,
+    };
+
+    #[test]
+    pub fn test_quote_buy_matches_curve_math() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_quote_configs(&mollusk, &program_id, 0, LAMPORTS_PER_SOL);
+
+        let curve_data = *bytemuck::from_bytes::<BondingCurve>(&account_infos[0].1.data);
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let result =
+            mollusk.process_and_validate_instruction(&ix, &account_infos, &[Check::success()]);
+
+        let quote = bytemuck::pod_read_unaligned::<SwapQuote>(&result.return_data);
+
+        // 1% fee once the launch phases are over
+        let fee_lamports = LAMPORTS_PER_SOL / 100;
+        let mut curve = get_curve(&program_id, &account_infos[1].0, &Pubkey::default());
+        let buy_result = curve
+            .apply_buy(LAMPORTS_PER_SOL - fee_lamports, 6, 0)
+            .unwrap();
+
+        assert_eq!(quote.fee_lamports, fee_lamports);
+        assert_eq!(quote.amount_out, buy_result.token_amount);
+        assert_eq!(quote.amount_in, buy_result.sol_amount + fee_lamports);
+        assert_eq!(
+            quote.price_impact_bps,
+            curve_data
+                .price_impact_bps(buy_result.sol_amount, buy_result.token_amount, true)
+                .unwrap()
+        );
+        assert_eq!(quote.completes_curve, 0);
+    }
+
+    #[test]
+    pub fn test_quote_flags_completing_buy() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_quote_configs(&mollusk, &program_id, 0, 1_000 * LAMPORTS_PER_SOL);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let result =
+            mollusk.process_and_validate_instruction(&ix, &account_infos, &[Check::success()]);
+
+        let quote = bytemuck::pod_read_unaligned::<SwapQuote>(&result.return_data);
+
+        assert_eq!(quote.completes_curve, 1);
+        assert!(quote.amount_in < 1_000 * LAMPORTS_PER_SOL);
+    }
+
+    #[test]
+    pub fn test_quote_fails_above_max_price_impact() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            mut account_infos,
+            account_meta,
+            ix_data,
+        } = get_quote_configs(&mollusk, &program_id, 0, 10 * LAMPORTS_PER_SOL);
+
+        // 10 SOL against 30 virtual SOL moves the price far more than 1%
+        let mut config = *bytemuck::from_bytes::<GlobalConfig>(&account_infos[2].1.data);
+        config.max_price_impact_bps = 100;
+        account_infos[2].1.data = bytemuck::bytes_of(&config).to_vec();
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::PriceImpactExceeded as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    pub fn test_quote_fails_before_start() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            mut account_infos,
+            account_meta,
+            ix_data,
+        } = get_quote_configs(&mollusk, &program_id, 0, LAMPORTS_PER_SOL);
+
+        let mut curve = *bytemuck::from_bytes::<BondingCurve>(&account_infos[0].1.data);
+        curve.status = CurveStatus::Scheduled as u8;
+        curve.starting_slot = SWAP_SLOT;
+        account_infos[0].1.data = bytemuck::bytes_of(&curve).to_vec();
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::CurveNotStarted as u32,
+            ))],
+        );
+    }
+}
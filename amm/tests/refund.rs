@@ -0,0 +1,111 @@
+mod helpers;
+
+#[cfg(test)]
+pub mod refund_tests {
+    use super::*;
+    use amm::{
+        states::bonding_curve::{BondingCurve, CurveStatus},
+        AmmError, ID,
+    };
+    use helpers::{
+        get_mollusk,
+        ix_configs::{
+            refund_configs::{get_refund_configs, set_crowdfund},
+            swap_configs::{get_swap_configs, SWAP_SLOT},
+        },
+        ReturnVal,
+    };
+    use mollusk_svm::result::Check;
+    use solana_sdk::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
+    use spl_token::{solana_program::program_pack::Pack, state::Account as ATA};
+
+    #[test]
+    pub fn test_refund_returns_share_of_reserves() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        // Refunding half the holdings pays out half the reserves
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_refund_configs(&mollusk, &program_id, 5_000);
+
+        let (holder, holder_account) = &account_infos[0];
+        let holder_tokens = ATA::unpack(&account_infos[1].1.data).unwrap().amount;
+        let curve_pda = account_infos[3].0;
+
+        let mut curve = *bytemuck::from_bytes::<BondingCurve>(&account_infos[3].1.data);
+        let raised = curve.real_sol_reserves;
+
+        curve.fail_crowdfund(SWAP_SLOT).unwrap();
+        let refund = curve.apply_refund(holder_tokens / 2).unwrap();
+        assert!(refund.abs_diff(raised / 2) <= 1);
+        assert_eq!(curve.status, CurveStatus::Failed as u8);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(holder)
+                    .lamports(holder_account.lamports + refund)
+                    .build(),
+                Check::account(&curve_pda)
+                    .data(bytemuck::bytes_of(&curve))
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    pub fn test_refund_fails_before_deadline() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT - 1);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_refund_configs(&mollusk, &program_id, 10_000);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::CrowdfundNotFailed as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    pub fn test_swap_fails_after_missed_deadline() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            mut account_infos,
+            account_meta,
+            ix_data,
+        } = get_swap_configs(&mollusk, &program_id, 0, 1_000_000_000, 0, 1_000_000_000);
+
+        set_crowdfund(&mut account_infos);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::CrowdfundFailed as u32,
+            ))],
+        );
+    }
+}
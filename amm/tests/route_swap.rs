@@ -0,0 +1,116 @@
+mod helpers;
+
+#[cfg(test)]
+pub mod route_swap_tests {
+    use super::*;
+    use amm::{states::cooldown::TradeCooldown, AmmError, ID};
+    use helpers::{
+        get_mollusk,
+        ix_configs::{
+            route_swap_configs::{get_cooldown_route_swap_configs, get_route_swap_configs},
+            swap_configs::{SELL_COOLDOWN_SLOTS, SWAP_SLOT},
+        },
+        ReturnVal,
+    };
+    use mollusk_svm::result::Check;
+    use solana_sdk::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
+
+    #[test]
+    pub fn test_route_swap_works() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_route_swap_configs(&mollusk, &program_id, 1_000_000_000, 1);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::success(), Check::all_rent_exempt()],
+        );
+    }
+
+    #[test]
+    pub fn test_route_swap_fails_on_slippage() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_route_swap_configs(&mollusk, &program_id, 1_000_000_000, u64::MAX);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::SlippageExceeded as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    pub fn test_route_swap_records_buy_leg_cooldown() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_cooldown_route_swap_configs(
+            &mollusk,
+            &program_id,
+            1_000_000_000,
+            SWAP_SLOT - SELL_COOLDOWN_SLOTS as u64,
+        );
+
+        let buy_cooldown = account_meta[account_meta.len() - 1].pubkey;
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&buy_cooldown)
+                    .space(TradeCooldown::SIZE)
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    pub fn test_route_swap_fails_during_sell_leg_cooldown() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_cooldown_route_swap_configs(&mollusk, &program_id, 1_000_000_000, SWAP_SLOT - 1);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::SellCooldownActive as u32,
+            ))],
+        );
+    }
+}
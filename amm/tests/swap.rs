@@ -0,0 +1,597 @@
+mod helpers;
+
+#[cfg(test)]
+pub mod swap_tests {
+    use super::*;
+    use amm::{
+        instructions::swap::{SwapAccounts, SwapParams, SwapResult},
+        states::{
+            allowlist::AllowlistClaim,
+            bonding_curve::{BondingCurve, CurveStatus},
+            cooldown::TradeCooldown,
+            global_config::GlobalConfig,
+        },
+        AmmError, ID,
+    };
+    use helpers::{
+        get_mollusk,
+        ix_configs::swap_configs::{
+            get_allowlist_swap_configs, get_cooldown_swap_configs, get_swap_configs,
+            get_transfer_hook_swap_configs, set_swap_limits, SELL_COOLDOWN_SLOTS, SWAP_SLOT,
+            TRANSFER_HOOK_PROGRAM_ID,
+        },
+        ReturnVal,
+    };
+    use mollusk_svm::result::Check;
+    use solana_sdk::{
+        instruction::Instruction, native_token::LAMPORTS_PER_SOL, program_error::ProgramError,
+        pubkey::Pubkey,
+    };
+    use spl_token::{
+        solana_program::program_pack::Pack,
+        state::{Account as ATA, AccountState},
+    };
+
+    #[test]
+    pub fn test_buy_works() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_swap_configs(&mollusk, &program_id, 0, LAMPORTS_PER_SOL, 1, 0);
+
+        let mut curve = *bytemuck::from_bytes::<BondingCurve>(&account_infos[5].1.data);
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let result = mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::success(), Check::all_rent_exempt()],
+        );
+
+        // 1% fee once the launch phases are over
+        let fee_lamports = LAMPORTS_PER_SOL / 100;
+        let buy_result = curve
+            .apply_buy(LAMPORTS_PER_SOL - fee_lamports, 6, 0)
+            .unwrap();
+
+        assert_eq!(
+            bytemuck::pod_read_unaligned::<SwapResult>(&result.return_data),
+            SwapResult {
+                amount_in: buy_result.sol_amount + fee_lamports,
+                amount_out: buy_result.token_amount,
+                fee_lamports,
+                virtual_sol_reserves: curve.virtual_sol_reserves,
+                virtual_token_reserves: curve.virtual_token_reserves,
+                real_sol_reserves: curve.real_sol_reserves,
+                real_token_reserves: curve.real_token_reserves,
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_sell_works() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_swap_configs(&mollusk, &program_id, 1, 1_000_000_000, 1, LAMPORTS_PER_SOL);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::success(), Check::all_rent_exempt()],
+        );
+    }
+
+    #[test]
+    pub fn test_buy_works_with_unfrozen_vault() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            mut account_infos,
+            account_meta,
+            ix_data,
+        } = get_swap_configs(&mollusk, &program_id, 0, LAMPORTS_PER_SOL, 1, 0);
+
+        let mut curve = *bytemuck::from_bytes::<BondingCurve>(&account_infos[5].1.data);
+        curve.vault_mode = BondingCurve::UNFROZEN_VAULT;
+        account_infos[5].1.data = bytemuck::bytes_of(&curve).to_vec();
+
+        let curve_mint_ata = account_infos[7].0;
+        let mut curve_ata = ATA::unpack(&account_infos[7].1.data).unwrap();
+        curve_ata.state = AccountState::Initialized;
+        // Tokens sent to the thawed vault from outside don't break the invariant
+        curve_ata.amount += 1_000;
+        ATA::pack(curve_ata, &mut account_infos[7].1.data).unwrap();
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let result = mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::success(), Check::all_rent_exempt()],
+        );
+
+        let curve_ata = ATA::unpack(&result.get_account(&curve_mint_ata).unwrap().data).unwrap();
+        assert_eq!(curve_ata.state, AccountState::Initialized);
+    }
+
+    #[test]
+    pub fn test_buy_fails_on_slippage() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_swap_configs(&mollusk, &program_id, 0, LAMPORTS_PER_SOL, u64::MAX, 0);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::SlippageExceeded as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    pub fn test_buy_fails_after_deadline() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            mut ix_data,
+        } = get_swap_configs(&mollusk, &program_id, 0, LAMPORTS_PER_SOL, 1, 0);
+
+        set_swap_limits(&mut ix_data, SWAP_SLOT - 1, 0);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::DeadlineExceeded as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    pub fn test_buy_fails_above_limit_price() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            mut ix_data,
+        } = get_swap_configs(&mollusk, &program_id, 0, LAMPORTS_PER_SOL, 1, 0);
+
+        // A whole token costs about 28 lamports at the initial reserves
+        set_swap_limits(&mut ix_data, 0, 1);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::PriceLimitExceeded as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    pub fn test_buy_works_with_legacy_params() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            mut ix_data,
+        } = get_swap_configs(&mollusk, &program_id, 0, LAMPORTS_PER_SOL, 1, 0);
+
+        // Clients built before the deadline and the limit price send the first 24 bytes only
+        ix_data.truncate(1 + SwapParams::LEGACY_SIZE);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::success(), Check::all_rent_exempt()],
+        );
+    }
+
+    #[test]
+    pub fn test_buy_fails_when_paused() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            mut account_infos,
+            account_meta,
+            ix_data,
+        } = get_swap_configs(&mollusk, &program_id, 0, LAMPORTS_PER_SOL, 1, 0);
+
+        let mut curve = *bytemuck::from_bytes::<BondingCurve>(&account_infos[5].1.data);
+        curve.status = CurveStatus::Paused as u8;
+        account_infos[5].1.data = bytemuck::bytes_of(&curve).to_vec();
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::CurvePaused as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    pub fn test_buy_fails_above_max_wallet() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            mut account_infos,
+            account_meta,
+            ix_data,
+        } = get_swap_configs(&mollusk, &program_id, 0, LAMPORTS_PER_SOL, 1, 0);
+
+        // 0.01% of the supply, far less than a 1 SOL buy gets
+        let mut curve = *bytemuck::from_bytes::<BondingCurve>(&account_infos[5].1.data);
+        curve.max_wallet_bps = 1;
+        account_infos[5].1.data = bytemuck::bytes_of(&curve).to_vec();
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::MaxWalletExceeded as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    pub fn test_max_wallet_requires_buyer_ata() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            mut account_infos,
+            mut account_meta,
+            ix_data,
+        } = get_swap_configs(&mollusk, &program_id, 0, LAMPORTS_PER_SOL, 1, 0);
+
+        // A fresh token account of the buyer would dodge the cap on what the ATA already holds
+        let mut curve = *bytemuck::from_bytes::<BondingCurve>(&account_infos[5].1.data);
+        curve.max_wallet_bps = 10_000;
+        account_infos[5].1.data = bytemuck::bytes_of(&curve).to_vec();
+
+        let other_account = Pubkey::new_unique();
+        account_infos[1].0 = other_account;
+        account_meta[1].pubkey = other_account;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::IncorrectProgramId)],
+        );
+    }
+
+    #[test]
+    pub fn test_buy_fails_above_max_price_impact() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            mut account_infos,
+            account_meta,
+            ix_data,
+        } = get_swap_configs(&mollusk, &program_id, 0, 10 * LAMPORTS_PER_SOL, 1, 0);
+
+        // 10 SOL against 30 virtual SOL moves the price far more than 1%
+        let mut config = *bytemuck::from_bytes::<GlobalConfig>(&account_infos[4].1.data);
+        config.max_price_impact_bps = 100;
+        account_infos[4].1.data = bytemuck::bytes_of(&config).to_vec();
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::PriceImpactExceeded as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    pub fn test_buy_records_cooldown() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_cooldown_swap_configs(&mollusk, &program_id, 0, LAMPORTS_PER_SOL, None);
+
+        let cooldown_pda = account_infos[SwapAccounts::LEN].0;
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let result = mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&cooldown_pda)
+                    .space(TradeCooldown::SIZE)
+                    .owner(&program_id)
+                    .build(),
+            ],
+        );
+
+        let cooldown =
+            bytemuck::from_bytes::<TradeCooldown>(&result.get_account(&cooldown_pda).unwrap().data);
+        assert_eq!(cooldown.last_buy_slot, SWAP_SLOT);
+    }
+
+    #[test]
+    pub fn test_sell_fails_during_cooldown() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_cooldown_swap_configs(&mollusk, &program_id, 1, 1_000_000_000, Some(SWAP_SLOT));
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::SellCooldownActive as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    pub fn test_sell_works_after_cooldown() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let last_buy_slot = SWAP_SLOT - SELL_COOLDOWN_SLOTS as u64;
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_cooldown_swap_configs(&mollusk, &program_id, 1, 1_000_000_000, Some(last_buy_slot));
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(&ix, &account_infos, &[Check::success()]);
+    }
+
+    #[test]
+    pub fn test_allowlisted_buy_records_claim() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_allowlist_swap_configs(
+            &mollusk,
+            &program_id,
+            LAMPORTS_PER_SOL,
+            2 * LAMPORTS_PER_SOL,
+        );
+
+        let claim_pda = account_infos[SwapAccounts::LEN].0;
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let result = mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&claim_pda)
+                    .space(AllowlistClaim::SIZE)
+                    .owner(&program_id)
+                    .build(),
+            ],
+        );
+
+        let claim =
+            bytemuck::from_bytes::<AllowlistClaim>(&result.get_account(&claim_pda).unwrap().data);
+        assert_eq!(claim.bought_lamports, LAMPORTS_PER_SOL);
+    }
+
+    #[test]
+    pub fn test_buy_fails_without_allowlist_proof() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            mut ix_data,
+        } = get_allowlist_swap_configs(&mollusk, &program_id, LAMPORTS_PER_SOL, 0);
+
+        ix_data.truncate(1 + SwapParams::SIZE);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::NotAllowlisted as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    pub fn test_buy_fails_above_allowlist_limit() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_allowlist_swap_configs(
+            &mollusk,
+            &program_id,
+            LAMPORTS_PER_SOL,
+            LAMPORTS_PER_SOL / 2,
+        );
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::AllowlistLimitExceeded as u32,
+            ))],
+        );
+    }
+
+    /// Loads the test transfer hook built from `tests/programs/transfer_hook`
+    fn add_transfer_hook_program(mollusk: &mut mollusk_svm::Mollusk) {
+        mollusk.add_program(
+            &TRANSFER_HOOK_PROGRAM_ID,
+            "target/deploy/transfer_hook",
+            &mollusk_svm::program::loader_keys::LOADER_V3,
+        );
+    }
+
+    #[test]
+    pub fn test_buy_works_with_transfer_hook() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        add_transfer_hook_program(&mut mollusk);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_transfer_hook_swap_configs(&mollusk, &program_id, 0, LAMPORTS_PER_SOL, 1, 0);
+
+        let counter = account_infos[13].0;
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&counter).data(&1u64.to_le_bytes()).build(),
+            ],
+        );
+    }
+
+    #[test]
+    pub fn test_sell_works_with_transfer_hook() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        add_transfer_hook_program(&mut mollusk);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_transfer_hook_swap_configs(
+            &mollusk,
+            &program_id,
+            1,
+            1_000_000_000,
+            1,
+            LAMPORTS_PER_SOL,
+        );
+
+        let counter = account_infos[13].0;
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&counter).data(&1u64.to_le_bytes()).build(),
+            ],
+        );
+    }
+
+    #[test]
+    pub fn test_buy_fails_without_transfer_hook_accounts() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        add_transfer_hook_program(&mut mollusk);
+        mollusk.warp_to_slot(SWAP_SLOT);
+
+        let ReturnVal {
+            mut account_infos,
+            mut account_meta,
+            ix_data,
+        } = get_transfer_hook_swap_configs(&mollusk, &program_id, 0, LAMPORTS_PER_SOL, 1, 0);
+
+        account_infos.truncate(SwapAccounts::LEN);
+        account_meta.truncate(SwapAccounts::LEN);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let result = mollusk.process_instruction(&ix, &account_infos);
+
+        assert!(result.program_result.is_err());
+    }
+}
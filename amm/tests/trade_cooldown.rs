@@ -0,0 +1,43 @@
+#[cfg(test)]
+pub mod trade_cooldown_tests {
+    use amm::{states::cooldown::TradeCooldown, Discriminator};
+    use proptest::prelude::*;
+
+    /// Cooldown PDA of a wallet that last bought at `last_buy_slot`
+    fn get_cooldown(last_buy_slot: u64) -> TradeCooldown {
+        TradeCooldown {
+            discriminator: TradeCooldown::DISCRIMINATOR,
+            version: TradeCooldown::VERSION,
+            bump: 255,
+            _padding: [0u8; 6],
+            mint: [0x1; 32],
+            wallet: [0x2; 32],
+            last_buy_slot,
+        }
+    }
+
+    #[test]
+    fn test_same_slot_sell_waits_for_cooldown() {
+        let cooldown = get_cooldown(1_000);
+
+        assert!(!cooldown.is_over(1_000, 1));
+        assert!(cooldown.is_over(1_001, 1));
+        assert!(cooldown.is_over(1_000, 0));
+    }
+
+    proptest! {
+        #[test]
+        fn test_cooldown_is_over_after_cooldown_slots(
+            last_buy_slot in 0..u64::MAX / 2,
+            cooldown_slots in 0..=u32::MAX,
+            elapsed in 0..=u32::MAX as u64 * 2,
+        ) {
+            let cooldown = get_cooldown(last_buy_slot);
+
+            prop_assert_eq!(
+                cooldown.is_over(last_buy_slot + elapsed, cooldown_slots),
+                elapsed >= cooldown_slots as u64
+            );
+        }
+    }
+}
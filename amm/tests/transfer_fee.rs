@@ -0,0 +1,91 @@
+#[cfg(test)]
+pub mod transfer_fee_tests {
+    use amm::transfer_fee::{TransferFee, MINT_WITH_TRANSFER_FEE_LEN};
+    use proptest::prelude::*;
+    use spl_token::solana_program::program_pack::Pack;
+    use spl_token_2022::{
+        extension::{
+            transfer_fee::{self, TransferFeeConfig},
+            BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+        },
+        state::Mint,
+    };
+
+    fn get_spl_fee(basis_points: u16, maximum_fee: u64) -> transfer_fee::TransferFee {
+        transfer_fee::TransferFee {
+            epoch: 0.into(),
+            maximum_fee: maximum_fee.into(),
+            transfer_fee_basis_points: basis_points.into(),
+        }
+    }
+
+    /// Packs a mint with the `TransferFeeConfig` extension the way Token-2022 does
+    fn get_mint_data(basis_points: u16, maximum_fee: u64) -> Vec<u8> {
+        let len =
+            ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::TransferFeeConfig])
+                .unwrap();
+        let mut data = vec![0u8; len];
+
+        let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        let extension = mint.init_extension::<TransferFeeConfig>(true).unwrap();
+        extension.older_transfer_fee = get_spl_fee(0, 0);
+        extension.newer_transfer_fee = get_spl_fee(basis_points, maximum_fee);
+
+        mint.base = Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..Mint::default()
+        };
+        mint.pack_base();
+        mint.init_account_type().unwrap();
+
+        data
+    }
+
+    #[test]
+    fn test_mint_len_matches_token_2022() {
+        let len =
+            ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::TransferFeeConfig])
+                .unwrap();
+
+        assert_eq!(MINT_WITH_TRANSFER_FEE_LEN, len);
+    }
+
+    #[test]
+    fn test_mint_without_extension_has_no_fee() {
+        assert_eq!(
+            TransferFee::from_mint_data(&[0u8; Mint::LEN]),
+            Ok(TransferFee::default())
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_fee_matches_token_2022(
+            basis_points in 0..=10_000u16,
+            maximum_fee in any::<u64>(),
+            amount in any::<u64>(),
+        ) {
+            let fee = TransferFee { basis_points, maximum_fee };
+
+            prop_assert_eq!(
+                fee.calculate(amount),
+                get_spl_fee(basis_points, maximum_fee).calculate_fee(amount)
+            );
+            prop_assert!(fee.net_amount(amount).unwrap() <= amount);
+        }
+
+        #[test]
+        fn test_reads_the_newer_fee_of_a_packed_mint(
+            basis_points in 0..=10_000u16,
+            maximum_fee in any::<u64>(),
+        ) {
+            let data = get_mint_data(basis_points, maximum_fee);
+
+            prop_assert_eq!(
+                TransferFee::from_mint_data(&data),
+                Ok(TransferFee { basis_points, maximum_fee })
+            );
+        }
+    }
+}
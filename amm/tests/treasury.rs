@@ -0,0 +1,262 @@
+mod helpers;
+
+#[cfg(test)]
+pub mod treasury_tests {
+    use super::*;
+    use amm::{
+        states::treasury::{Treasury, TreasuryTokenRecord},
+        ID,
+    };
+    use helpers::{
+        get_mollusk, get_treasury_pubkey,
+        ix_configs::treasury_configs::{
+            get_create_treasury_configs, get_treasury, get_treasury_token_record,
+            get_withdraw_treasury_configs, get_withdraw_treasury_token_configs, TREASURY_FEES,
+            TREASURY_TOKENS,
+        },
+        ReturnVal,
+    };
+    use mollusk_svm::result::Check;
+    use solana_sdk::{
+        instruction::Instruction, native_token::LAMPORTS_PER_SOL, program_error::ProgramError,
+        pubkey::Pubkey,
+    };
+    use spl_token::{solana_program::program_pack::Pack, state::Account as ATA};
+
+    #[test]
+    pub fn test_create_treasury_works() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_create_treasury_configs(&mollusk, &program_id, 0);
+
+        let treasury = get_treasury_pubkey();
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&treasury)
+                    .space(Treasury::SIZE)
+                    .data(bytemuck::bytes_of(&get_treasury(&program_id)))
+                    .owner(&program_id)
+                    .rent_exempt()
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    pub fn test_create_treasury_keeps_fees_sent_before() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+        let rent_exempt = mollusk.sysvars.rent.minimum_balance(Treasury::SIZE);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_create_treasury_configs(&mollusk, &program_id, rent_exempt + LAMPORTS_PER_SOL);
+
+        let treasury = get_treasury_pubkey();
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&treasury)
+                    .lamports(rent_exempt + LAMPORTS_PER_SOL)
+                    .data(bytemuck::bytes_of(&get_treasury(&program_id)))
+                    .owner(&program_id)
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    pub fn test_withdraw_treasury_splits_by_weight() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_withdraw_treasury_configs(&mollusk, &program_id, 0);
+
+        let treasury = get_treasury_pubkey();
+        let (team, grants) = (account_infos[3].0, account_infos[4].0);
+
+        let mut expected_treasury = get_treasury(&program_id);
+        expected_treasury.total_withdrawn_lamports = TREASURY_FEES;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&treasury)
+                    .lamports(mollusk.sysvars.rent.minimum_balance(Treasury::SIZE))
+                    .data(bytemuck::bytes_of(&expected_treasury))
+                    .build(),
+                Check::account(&team)
+                    .lamports(LAMPORTS_PER_SOL + TREASURY_FEES * 7 / 10)
+                    .build(),
+                Check::account(&grants)
+                    .lamports(LAMPORTS_PER_SOL + TREASURY_FEES * 3 / 10)
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    pub fn test_withdraw_treasury_keeps_rent_exemption() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_withdraw_treasury_configs(&mollusk, &program_id, TREASURY_FEES + 1);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::InsufficientFunds)],
+        );
+    }
+
+    #[test]
+    pub fn test_withdraw_treasury_fails_for_non_admin() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            mut account_infos,
+            mut account_meta,
+            ix_data,
+        } = get_withdraw_treasury_configs(&mollusk, &program_id, 0);
+
+        let impostor = Pubkey::new_unique();
+        account_infos[0].0 = impostor;
+        account_meta[0].pubkey = impostor;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::IncorrectAuthority)],
+        );
+    }
+
+    #[test]
+    pub fn test_withdraw_treasury_fails_for_other_recipients() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            mut account_infos,
+            mut account_meta,
+            ix_data,
+        } = get_withdraw_treasury_configs(&mollusk, &program_id, 0);
+
+        let other = Pubkey::new_unique();
+        account_infos[4].0 = other;
+        account_meta[4].pubkey = other;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::InvalidArgument)],
+        );
+    }
+
+    #[test]
+    pub fn test_withdraw_treasury_tokens_records_the_mint() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_withdraw_treasury_token_configs(&mollusk, &program_id, 0, None);
+
+        let record = account_infos[6].0;
+        let (team_ata, grants_ata) = (account_infos[8].0, account_infos[9].0);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let result = mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&record)
+                    .space(TreasuryTokenRecord::SIZE)
+                    .data(bytemuck::bytes_of(&get_treasury_token_record(
+                        &program_id,
+                        TREASURY_TOKENS,
+                    )))
+                    .owner(&program_id)
+                    .rent_exempt()
+                    .build(),
+            ],
+        );
+
+        let team_tokens = ATA::unpack(&result.get_account(&team_ata).unwrap().data)
+            .unwrap()
+            .amount;
+        let grants_tokens = ATA::unpack(&result.get_account(&grants_ata).unwrap().data)
+            .unwrap()
+            .amount;
+
+        assert_eq!(team_tokens, TREASURY_TOKENS * 7 / 10);
+        assert_eq!(grants_tokens, TREASURY_TOKENS * 3 / 10);
+    }
+
+    #[test]
+    pub fn test_withdraw_treasury_tokens_adds_to_the_record() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_withdraw_treasury_token_configs(&mollusk, &program_id, 1_000, Some(5_000));
+
+        let record = account_infos[6].0;
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[
+                Check::success(),
+                Check::account(&record)
+                    .data(bytemuck::bytes_of(&get_treasury_token_record(
+                        &program_id,
+                        6_000,
+                    )))
+                    .build(),
+            ],
+        );
+    }
+}
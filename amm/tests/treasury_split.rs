@@ -0,0 +1,66 @@
+#[cfg(test)]
+pub mod treasury_split_tests {
+    use amm::states::treasury::{Treasury, TreasuryRecipient, MAX_TREASURY_RECIPIENTS};
+    use bytemuck::Zeroable;
+    use proptest::prelude::*;
+
+    fn get_recipient(seed: u8, weight_bps: u16) -> TreasuryRecipient {
+        TreasuryRecipient {
+            wallet: [seed; 32],
+            weight_bps,
+            _padding: [0; 6],
+        }
+    }
+
+    #[test]
+    fn test_recipient_weights_add_up_to_10_000() {
+        assert!(Treasury::validate_recipients(&[get_recipient(1, 10_000)]).is_ok());
+        assert!(
+            Treasury::validate_recipients(&[get_recipient(1, 7_000), get_recipient(2, 3_000)])
+                .is_ok()
+        );
+        assert!(Treasury::validate_recipients(&[]).is_err());
+        assert!(Treasury::validate_recipients(&[get_recipient(1, 9_999)]).is_err());
+        assert!(
+            Treasury::validate_recipients(&[get_recipient(1, 10_000), get_recipient(2, 0)])
+                .is_err()
+        );
+        assert!(Treasury::validate_recipients(&[get_recipient(0, 10_000)]).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_split_pays_out_the_whole_amount(
+            cuts in prop::collection::btree_set(1u16..10_000, 0..MAX_TREASURY_RECIPIENTS),
+            amount in any::<u64>(),
+        ) {
+            // Cutting 10_000 bps at distinct points gives non-zero weights adding up to 10_000
+            let mut bounds: Vec<u16> = cuts.into_iter().collect();
+            bounds.insert(0, 0);
+            bounds.push(10_000);
+            let weights: Vec<u16> = bounds.windows(2).map(|pair| pair[1] - pair[0]).collect();
+            let last = weights.len() - 1;
+
+            let recipients: Vec<_> = weights
+                .iter()
+                .enumerate()
+                .map(|(index, weight)| get_recipient(index as u8 + 1, *weight))
+                .collect();
+
+            let mut treasury = Treasury::zeroed();
+            treasury.set_recipients(&recipients).unwrap();
+
+            let shares = treasury.split(amount);
+            let paid: u128 = shares.iter().map(|share| *share as u128).sum();
+
+            prop_assert_eq!(paid, amount as u128);
+
+            for (share, recipient) in shares[..last].iter().zip(&recipients) {
+                prop_assert_eq!(
+                    *share as u128,
+                    amount as u128 * recipient.weight_bps as u128 / 10_000
+                );
+            }
+        }
+    }
+}
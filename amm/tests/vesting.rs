@@ -0,0 +1,104 @@
+mod helpers;
+
+#[cfg(test)]
+pub mod vesting_tests {
+    use super::*;
+    use amm::{
+        states::{bonding_curve::BondingCurve, crowdfund::Crowdfund},
+        AmmError, ID,
+    };
+    use helpers::{
+        get_mollusk,
+        ix_configs::vesting_configs::{
+            get_claim_vested_configs, get_vesting, VESTING_DURATION_SLOTS, VESTING_START_SLOT,
+        },
+        ReturnVal,
+    };
+    use mollusk_svm::result::Check;
+    use solana_sdk::{
+        instruction::Instruction, native_token::LAMPORTS_PER_SOL, program_error::ProgramError,
+        pubkey::Pubkey,
+    };
+    use spl_token::{solana_program::program_pack::Pack, state::Account as ATA};
+
+    #[test]
+    pub fn test_claim_vested_releases_linearly() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        let halfway = VESTING_START_SLOT + VESTING_DURATION_SLOTS / 2;
+        mollusk.warp_to_slot(halfway);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_claim_vested_configs(&mollusk, &program_id);
+
+        let creator_mint_ata = account_infos[4].0;
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let result =
+            mollusk.process_and_validate_instruction(&ix, &account_infos, &[Check::success()]);
+
+        let creator_ata =
+            ATA::unpack(&result.get_account(&creator_mint_ata).unwrap().data).unwrap();
+
+        assert_eq!(
+            creator_ata.amount,
+            get_vesting(&program_id).total_amount / 2
+        );
+    }
+
+    #[test]
+    pub fn test_claim_vested_fails_before_cliff() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = get_claim_vested_configs(&mollusk, &program_id);
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::NothingToClaim as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    pub fn test_claim_vested_fails_before_crowdfund_settles() {
+        let program_id = Pubkey::new_from_array(ID);
+        let mut mollusk = get_mollusk(&program_id);
+        mollusk.warp_to_slot(VESTING_START_SLOT + VESTING_DURATION_SLOTS);
+
+        let ReturnVal {
+            mut account_infos,
+            account_meta,
+            ix_data,
+        } = get_claim_vested_configs(&mollusk, &program_id);
+
+        // The crowdfund runs past the end of the schedule and can still fail
+        let mut curve = *bytemuck::from_bytes::<BondingCurve>(&account_infos[2].1.data);
+        curve.crowdfund = Crowdfund {
+            target_sol: 10 * LAMPORTS_PER_SOL,
+            deadline_slot: 2 * VESTING_DURATION_SLOTS,
+        };
+        account_infos[2].1.data = bytemuck::bytes_of(&curve).to_vec();
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::Custom(
+                AmmError::CrowdfundNotSettled as u32,
+            ))],
+        );
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+pub mod vesting_schedule_tests {
+    use amm::{states::vesting::Vesting, Discriminator};
+    use proptest::prelude::*;
+
+    fn get_vesting(total_amount: u64, cliff_slots: u64, duration_slots: u64) -> Vesting {
+        Vesting {
+            discriminator: Vesting::DISCRIMINATOR,
+            version: Vesting::VERSION,
+            bump: 255,
+            _padding: [0u8; 6],
+            mint: [0x1; 32],
+            beneficiary: [0x2; 32],
+            total_amount,
+            claimed_amount: 0,
+            start_slot: 1_000,
+            cliff_slots,
+            duration_slots,
+        }
+    }
+
+    fn schedule_strategy() -> impl Strategy<Value = Vesting> {
+        (0..=u64::MAX / 2, 1..=10_000_000u64)
+            .prop_flat_map(|(total, duration)| (Just(total), 0..=duration, Just(duration)))
+            .prop_map(|(total, cliff, duration)| get_vesting(total, cliff, duration))
+    }
+
+    proptest! {
+        #[test]
+        fn test_nothing_unlocks_before_cliff(vesting in schedule_strategy(), offset in 0..=10_000_000u64) {
+            prop_assume!(offset < vesting.cliff_slots);
+
+            prop_assert_eq!(vesting.unlocked_amount(vesting.start_slot + offset), Some(0));
+        }
+
+        #[test]
+        fn test_everything_unlocks_after_duration(vesting in schedule_strategy(), extra in 0..=1_000u64) {
+            let slot = vesting.start_slot + vesting.duration_slots + extra;
+
+            prop_assert_eq!(vesting.unlocked_amount(slot), Some(vesting.total_amount));
+        }
+
+        #[test]
+        fn test_unlocked_grows_and_stays_bounded(
+            vesting in schedule_strategy(),
+            first in 0..=20_000_000u64,
+            step in 0..=20_000_000u64,
+        ) {
+            let earlier = vesting.unlocked_amount(vesting.start_slot + first).unwrap();
+            let later = vesting.unlocked_amount(vesting.start_slot + first + step).unwrap();
+
+            prop_assert!(earlier <= later);
+            prop_assert!(later <= vesting.total_amount);
+        }
+
+        #[test]
+        fn test_claims_never_exceed_total(
+            vesting in schedule_strategy(),
+            claim_slots in prop::collection::vec(0..=20_000_000u64, 1..20),
+        ) {
+            let mut vesting = vesting;
+            let mut claim_slots = claim_slots;
+            claim_slots.sort();
+
+            for offset in claim_slots {
+                let claimable = vesting.claimable_amount(vesting.start_slot + offset).unwrap();
+                vesting.claimed_amount += claimable;
+            }
+
+            prop_assert!(vesting.claimed_amount <= vesting.total_amount);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Compute unit benchmarks for every escrow instruction
+//!
+//! Needs the program built with `cargo build-sbf` first, then run `cargo bench --bench compute_units`.
+//!
+//! The consumed CUs are checked against `benches/compute_units.baseline`, see
+//! `program_utils::bench` for the report and the environment variables.
+#[allow(dead_code)]
+#[path = "../tests/helpers/mod.rs"]
+mod helpers;
+
+use escrow::ID;
+use helpers::{
+    close::get_close_configs, create::get_create_config, get_mollusk, structs::ReturnVal,
+    withdraw::withdraw_configs,
+};
+use program_utils::bench::check_compute_units;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use std::process::exit;
+
+fn main() {
+    let program_id = Pubkey::new_from_array(ID);
+    let mollusk = get_mollusk(&program_id);
+
+    let cases = [
+        ("create_escrow", get_create_config(10, 10, &mollusk)),
+        ("process_withdraw", withdraw_configs(&mollusk)),
+        ("process_close", get_close_configs(&mollusk, &program_id)),
+    ];
+
+    let mut results = Vec::with_capacity(cases.len());
+
+    for (name, config) in cases {
+        let ReturnVal {
+            account_infos,
+            account_meta,
+            ix_data,
+        } = config;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+        let result = mollusk.process_instruction(&ix, &account_infos);
+
+        if result.program_result.is_err() {
+            eprintln!("{name} failed: {:?}", result.program_result);
+            exit(1);
+        }
+
+        results.push((name, result.compute_units_consumed));
+    }
+
+    check_compute_units(&results);
+}
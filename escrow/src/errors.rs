@@ -1,7 +1,4 @@
-use {
-    pinocchio::program_error::{ProgramError, ToStr},
-    thiserror_no_std::Error,
-};
+use {pinocchio::program_error::ToStr, thiserror_no_std::Error};
 
 #[derive(Clone, Debug, Eq, PartialEq, Error)]
 pub enum EscrowErrors {
@@ -13,13 +10,11 @@ pub enum EscrowErrors {
     InvalidEscrow,
     #[error("Invalid token amount")]
     InvalidBalance,
+    #[error("Unsupported account layout version")]
+    UnsupportedLayoutVersion,
 }
 
-impl From<EscrowErrors> for ProgramError {
-    fn from(e: EscrowErrors) -> Self {
-        ProgramError::Custom(e as u32)
-    }
-}
+program_utils::impl_program_error!(EscrowErrors);
 
 impl ToStr for EscrowErrors {
     fn to_str<E>(&self) -> &'static str {
@@ -28,6 +23,9 @@ impl ToStr for EscrowErrors {
             EscrowErrors::InvalidMint => "Validation Error: Invalid Mint",
             EscrowErrors::InvalidEscrow => "Validation Error: Invalid escrow provided",
             EscrowErrors::InvalidBalance => "Validation Error: Invalid Token amount",
+            EscrowErrors::UnsupportedLayoutVersion => {
+                "Load Error: Account layout version not supported"
+            }
         }
     }
 }
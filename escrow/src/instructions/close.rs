@@ -1,114 +1,134 @@
-use crate::{errors::EscrowErrors, helper::require, states::EscrowPda};
+use crate::{
+    errors::EscrowErrors,
+    states::EscrowPda,
+    token::{mint_decimals, require_token_program, token_account},
+};
 use {
     pinocchio::{
         account_info::AccountInfo,
         instruction::{Seed, Signer},
         log::sol_log,
         program_error::ProgramError,
-        pubkey::{pubkey_eq, Pubkey},
+        pubkey::pubkey_eq,
         ProgramResult,
     },
-    pinocchio_token::{
-        instructions::{CloseAccount, Transfer},
-        state::TokenAccount,
+    pinocchio_token_2022::instructions::CloseAccount,
+    program_utils::{
+        close_account, require, require_owner, require_signer, require_writable,
+        token::TransferChecked,
     },
 };
 
-pub fn process_close(program_id: Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-    sol_log("Escrow Instruction: Close");
-    let escrow_data = validate(&program_id, accounts)?;
-
-    if let [creator, creator_mint_ata, escrow, escrow_vault, _token_program] = accounts {
-        let bump = [escrow_data.bump];
-        let seed = [
-            Seed::from(EscrowPda::ESCROW_PREFIX.as_bytes()),
-            Seed::from(escrow_data.creator.as_ref()),
-            Seed::from(escrow_data.mint_a.as_ref()),
-            Seed::from(&bump),
-        ];
-
-        let seeds = Signer::from(&seed);
-        sol_log("Transferring tokens back");
-        Transfer {
-            amount: escrow_data.amount,
-            authority: escrow,
-            from: escrow_vault,
-            to: creator_mint_ata,
-        }
-        .invoke_signed(&[seeds.clone()])?;
-
-        sol_log("Closing account");
-        CloseAccount {
-            account: escrow_vault,
-            authority: escrow,
-            destination: creator,
-        }
-        .invoke_signed(&[seeds])?;
-
-        // Close the escrow account
-        unsafe {
-            *creator.borrow_mut_lamports_unchecked() += *escrow.borrow_lamports_unchecked();
-            *escrow.borrow_mut_lamports_unchecked() = 0
-        };
-
-        Ok(())
-    } else {
-        Err(ProgramError::NotEnoughAccountKeys)
-    }
+/// Accounts of `Close`
+///
+/// - `creator`: signer that created the escrow, receives the deposit back
+/// - `mint_a`: mint of the deposit
+/// - `escrow`: initialized escrow PDA, its data is loaded into `escrow_data`
+/// - `escrow_vault`: `mint_a` token account holding the deposit
+/// - `token_program`: SPL Token or Token-2022, the program of `mint_a`
+/// - `transfer_hook_accounts`: remaining accounts, forwarded to the refund for the transfer hook of
+///   `mint_a`
+pub struct CloseEscrowAccounts<'a> {
+    pub creator: &'a AccountInfo,
+    pub creator_mint_ata: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub escrow: &'a AccountInfo,
+    pub escrow_vault: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub transfer_hook_accounts: &'a [AccountInfo],
+    pub escrow_data: EscrowPda,
 }
 
-pub fn validate(program_id: &Pubkey, accounts: &[AccountInfo]) -> Result<EscrowPda, ProgramError> {
-    if let [creator, creator_mint_ata, escrow, escrow_vault, _] = accounts {
-        require(creator.is_signer(), ProgramError::MissingRequiredSignature)?;
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseEscrowAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [creator, creator_mint_ata, mint_a, escrow, escrow_vault, token_program, transfer_hook_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
 
+        require_signer(creator)?;
+        require_writable(escrow)?;
+        require_owner(escrow, &crate::ID)?;
         require(!escrow.data_is_empty(), ProgramError::UninitializedAccount)?;
+        require_token_program(token_program)?;
+
         let escrow_data = EscrowPda::load(escrow)?;
+        escrow_data.check_id(escrow, &crate::ID)?;
 
         require(
             pubkey_eq(&escrow_data.creator, creator.key()),
             ProgramError::IllegalOwner,
         )?;
-
         require(
-            TokenAccount::from_account_info(creator_mint_ata)
-                .map_err(|_| ProgramError::InvalidAccountData)?
-                .mint()
-                == &escrow_data.mint_a,
+            pubkey_eq(mint_a.key(), &escrow_data.mint_a),
             EscrowErrors::InvalidMint.into(),
         )?;
 
         require(
-            TokenAccount::from_account_info(escrow_vault)
-                .map_err(|_| ProgramError::InvalidAccountData)?
-                .mint()
-                == &escrow_data.mint_a,
+            token_account(creator_mint_ata, token_program)?.mint() == &escrow_data.mint_a,
             EscrowErrors::InvalidMint.into(),
         )?;
 
-        let expected_escrow = pinocchio::pubkey::create_program_address(
-            &[
-                EscrowPda::ESCROW_PREFIX.as_bytes(),
-                escrow_data.creator.as_ref(),
-                escrow_data.mint_a.as_ref(),
-                &[escrow_data.bump],
-            ],
-            program_id,
-        )?;
-
         require(
-            pubkey_eq(&expected_escrow, escrow.key()),
-            EscrowErrors::InvalidEscrow.into(),
-        )?;
-
-        require(
-            pubkey_eq(&escrow_data.creator, creator.key()),
-            ProgramError::InvalidAccountOwner,
+            token_account(escrow_vault, token_program)?.mint() == &escrow_data.mint_a,
+            EscrowErrors::InvalidMint.into(),
         )?;
 
         sol_log("Validation successful");
 
-        Ok(escrow_data)
-    } else {
-        Err(ProgramError::NotEnoughAccountKeys)
+        Ok(Self {
+            creator,
+            creator_mint_ata,
+            mint_a,
+            escrow,
+            escrow_vault,
+            token_program,
+            transfer_hook_accounts,
+            escrow_data,
+        })
     }
 }
+
+pub fn process_close(accounts: &[AccountInfo]) -> ProgramResult {
+    sol_log("Escrow Instruction: Close");
+    let ctx = CloseEscrowAccounts::try_from(accounts)?;
+    let escrow_data = &ctx.escrow_data;
+
+    let bump = [escrow_data.bump];
+    let seed = [
+        Seed::from(EscrowPda::ESCROW_PREFIX.as_bytes()),
+        Seed::from(escrow_data.creator.as_ref()),
+        Seed::from(escrow_data.mint_a.as_ref()),
+        Seed::from(&bump),
+    ];
+
+    let seeds = Signer::from(&seed);
+    sol_log("Transferring tokens back");
+    TransferChecked {
+        token_program: ctx.token_program.key(),
+        from: ctx.escrow_vault,
+        mint: ctx.mint_a,
+        to: ctx.creator_mint_ata,
+        authority: ctx.escrow,
+        amount: escrow_data.amount,
+        decimals: mint_decimals(ctx.mint_a, ctx.token_program)?,
+        hook_accounts: ctx.transfer_hook_accounts,
+    }
+    .invoke_signed(core::slice::from_ref(&seeds))?;
+
+    sol_log("Closing account");
+    CloseAccount {
+        account: ctx.escrow_vault,
+        authority: ctx.escrow,
+        destination: ctx.creator,
+        token_program: ctx.token_program.key(),
+    }
+    .invoke_signed(&[seeds])?;
+
+    close_account(ctx.escrow, ctx.creator)?;
+
+    Ok(())
+}
@@ -1,95 +1,135 @@
 use crate::{
-    helper::require,
     states::{CreateEscrow, EscrowPda},
+    token::{mint_decimals, require_token_program, token_account},
 };
 use {
     pinocchio::{
         account_info::AccountInfo,
+        instruction::{Seed, Signer},
         log::sol_log,
         program_error::ProgramError,
-        program_error::ProgramError::NotEnoughAccountKeys,
-        pubkey::{pubkey_eq, try_find_program_address, Pubkey},
+        pubkey::Pubkey,
         sysvars::{rent::Rent, Sysvar},
         ProgramResult,
     },
     pinocchio_system::instructions::CreateAccount,
-    pinocchio_token::{instructions::Transfer, state::TokenAccount},
+    program_utils::{
+        require, require_empty, require_pda, require_signer, require_writable,
+        token::TransferChecked,
+    },
 };
 
-pub fn create_escrow(
-    program_id: Pubkey,
-    accounts: &[AccountInfo],
-    data: CreateEscrow,
-) -> ProgramResult {
-    sol_log("Escrow Instruction: CreateEscrow");
+/// Accounts of `CreateEscrow`
+///
+/// - `creator`: signer depositing `mint_a` tokens from `creator_mint_ata`
+/// - `escrow_pda`: empty escrow PDA of the creator and `mint_a`
+/// - `vault`: `mint_a` token account receiving the deposit
+/// - `token_program`: SPL Token or Token-2022, the program of `mint_a`
+/// - `transfer_hook_accounts`: remaining accounts, forwarded to the deposit for the transfer hook of
+///   `mint_a`
+pub struct CreateEscrowAccounts<'a> {
+    pub creator: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub mint_b: &'a AccountInfo,
+    pub creator_mint_ata: &'a AccountInfo,
+    pub escrow_pda: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub transfer_hook_accounts: &'a [AccountInfo],
+    pub bump: u8,
+}
 
-    validate(accounts)?;
+impl<'a> TryFrom<&'a [AccountInfo]> for CreateEscrowAccounts<'a> {
+    type Error = ProgramError;
 
-    if let [creator, mint_a, mint_b, creator_mint_ata, escrow_pda, vault, _system_program, _token_program] =
-        accounts
-    {
-        let escrow_seeds = EscrowPda::get_signer_seeds(creator.key(), mint_a.key());
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [creator, mint_a, mint_b, creator_mint_ata, escrow_pda, vault, system_program, token_program, transfer_hook_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        require_signer(creator)?;
+        require_writable(escrow_pda)?;
 
-        let (expected_pda, bump) = try_find_program_address(&escrow_seeds, &program_id).unwrap();
+        require_empty(escrow_pda)?;
+        require_token_program(token_program)?;
+
+        let escrow_seeds = EscrowPda::get_signer_seeds(creator.key(), mint_a.key());
+        let bump = require_pda(escrow_pda, &escrow_seeds, &crate::ID)?;
 
         require(
-            pubkey_eq(&expected_pda, escrow_pda.key()),
-            ProgramError::IncorrectProgramId,
+            token_account(vault, token_program)?.mint() == mint_a.key(),
+            ProgramError::InvalidAccountOwner,
         )?;
 
-        sol_log("Creating PDA");
-        CreateAccount {
-            from: creator,
-            lamports: (Rent::get()?).minimum_balance(EscrowPda::ESCROW_SIZE),
-            owner: &program_id,
-            space: EscrowPda::ESCROW_SIZE as u64,
-            to: escrow_pda,
-        }
-        .invoke()?;
-
-        sol_log("pda created");
-        let mut escrow_data = EscrowPda::load(escrow_pda)?;
-        sol_log("Initializing pda");
-        escrow_data.init(
-            creator.key(),
-            mint_a.key(),
-            mint_b.key(),
-            data.send_amount,
-            data.recv_amount,
+        Ok(Self {
+            creator,
+            mint_a,
+            mint_b,
+            creator_mint_ata,
+            escrow_pda,
+            vault,
+            system_program,
+            token_program,
+            transfer_hook_accounts,
             bump,
-        );
+        })
+    }
+}
 
-        Transfer {
-            amount: data.send_amount,
-            authority: creator,
-            from: creator_mint_ata,
-            to: vault,
-        }
-        .invoke()?;
-    } else {
-        return Err(NotEnoughAccountKeys);
-    };
+pub fn create_escrow(
+    program_id: Pubkey,
+    accounts: &[AccountInfo],
+    data: CreateEscrow,
+) -> ProgramResult {
+    sol_log("Escrow Instruction: CreateEscrow");
 
-    Ok(())
-}
+    let ctx = CreateEscrowAccounts::try_from(accounts)?;
+    sol_log("Validation complete");
 
-pub fn validate(accounts: &[AccountInfo]) -> ProgramResult {
-    if let [creator, mint_a, _, _creator_mint_ata, escrow_pda, vault, _, _] = accounts {
-        require(creator.is_signer(), ProgramError::MissingRequiredSignature)?;
+    sol_log("Creating PDA");
+    let bump = [ctx.bump];
+    let seed = [
+        Seed::from(EscrowPda::ESCROW_PREFIX.as_bytes()),
+        Seed::from(ctx.creator.key().as_ref()),
+        Seed::from(ctx.mint_a.key().as_ref()),
+        Seed::from(&bump),
+    ];
 
-        require(
-            TokenAccount::from_account_info(vault).unwrap().mint() == mint_a.key(),
-            ProgramError::InvalidAccountOwner,
-        )?;
+    CreateAccount {
+        from: ctx.creator,
+        lamports: (Rent::get()?).minimum_balance(EscrowPda::ESCROW_SIZE),
+        owner: &program_id,
+        space: EscrowPda::ESCROW_SIZE as u64,
+        to: ctx.escrow_pda,
+    }
+    .invoke_signed(&[Signer::from(&seed)])?;
 
-        require(
-            escrow_pda.data_is_empty(),
-            ProgramError::AccountAlreadyInitialized,
-        )?;
-        sol_log("Validation complete");
-    } else {
-        return Err(NotEnoughAccountKeys);
-    };
+    sol_log("pda created");
+    sol_log("Initializing pda");
+    EscrowPda::new(
+        ctx.creator.key(),
+        ctx.mint_a.key(),
+        ctx.mint_b.key(),
+        data.send_amount,
+        data.recv_amount,
+        ctx.bump,
+    )
+    .save(ctx.escrow_pda)?;
+
+    TransferChecked {
+        token_program: ctx.token_program.key(),
+        from: ctx.creator_mint_ata,
+        mint: ctx.mint_a,
+        to: ctx.vault,
+        authority: ctx.creator,
+        amount: data.send_amount,
+        decimals: mint_decimals(ctx.mint_a, ctx.token_program)?,
+        hook_accounts: ctx.transfer_hook_accounts,
+    }
+    .invoke()?;
 
     Ok(())
 }
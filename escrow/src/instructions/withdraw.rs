@@ -1,138 +1,180 @@
-use crate::{errors::EscrowErrors, helper::require, states::EscrowPda};
+use crate::{
+    errors::EscrowErrors,
+    states::EscrowPda,
+    token::{mint_decimals, require_token_program, token_account},
+};
 use {
     pinocchio::{
         account_info::AccountInfo,
         instruction::{Seed, Signer},
         log::sol_log,
         program_error::ProgramError,
-        pubkey::{pubkey_eq, Pubkey},
+        pubkey::pubkey_eq,
         ProgramResult,
     },
-    pinocchio_token::{
-        instructions::{CloseAccount, Transfer},
-        state::TokenAccount,
+    pinocchio_token_2022::instructions::CloseAccount,
+    program_utils::{
+        close_account, require, require_owner, require_signer, require_writable,
+        token::TransferChecked,
     },
 };
 
-pub fn process_withdraw(program_id: Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-    sol_log("Escrow Instruction: Withdraw");
-    let escrow_account = validate(&program_id, accounts)?;
-
-    if let [taker, taker_mint_b_ata, taker_mint_a_ata, maker_b_ata, _, _, escrow, escrow_vault, _token_program] =
-        accounts
-    {
-        let bump = [escrow_account.bump];
-        let seed = [
-            Seed::from(EscrowPda::ESCROW_PREFIX.as_bytes()),
-            Seed::from(escrow_account.creator.as_ref()),
-            Seed::from(escrow_account.mint_a.as_ref()),
-            Seed::from(&bump),
-        ];
-        let seeds = Signer::from(&seed);
-        Transfer {
-            amount: escrow_account.amount,
-            authority: escrow,
-            from: escrow_vault,
-            to: taker_mint_a_ata,
-        }
-        .invoke_signed(&[seeds.clone()])?;
-
-        Transfer {
-            amount: escrow_account.receive,
-            authority: taker,
-            from: taker_mint_b_ata,
-            to: maker_b_ata,
-        }
-        .invoke()?;
-
-        CloseAccount {
-            account: escrow_vault,
-            authority: escrow,
-            destination: taker,
-        }
-        .invoke_signed(&[seeds])?;
-
-        // Close the escrow account
-        unsafe {
-            *taker.borrow_mut_lamports_unchecked() += *escrow.borrow_lamports_unchecked();
-            *escrow.borrow_mut_lamports_unchecked() = 0
-        };
-
-        Ok(())
-    } else {
-        Err(ProgramError::NotEnoughAccountKeys) // Never gonna reach here
-    }
+/// Accounts of `Withdraw`
+///
+/// - `taker`: signer paying `receive` of `mint_b` to `maker_b_ata` in exchange for the vault
+/// - `maker_b_ata`: the escrow creator's `mint_b` token account
+/// - `escrow`: initialized escrow PDA, its data is loaded into `escrow_data`
+/// - `escrow_vault`: `mint_a` token account holding the deposit
+/// - `token_program`: SPL Token or Token-2022, the program of both mints
+/// - `transfer_hook_accounts`: remaining accounts, forwarded to both transfers for the transfer
+///   hooks of `mint_a` and `mint_b`
+pub struct WithdrawAccounts<'a> {
+    pub taker: &'a AccountInfo,
+    pub taker_mint_b_ata: &'a AccountInfo,
+    pub taker_mint_a_ata: &'a AccountInfo,
+    pub maker_b_ata: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub mint_b: &'a AccountInfo,
+    pub escrow: &'a AccountInfo,
+    pub escrow_vault: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub transfer_hook_accounts: &'a [AccountInfo],
+    pub escrow_data: EscrowPda,
 }
 
-fn validate(program_id: &Pubkey, accounts: &[AccountInfo]) -> Result<EscrowPda, ProgramError> {
-    if let [taker, taker_mint_b_ata, taker_mint_a_ata, _, mint_a, mint_b, escrow, escrow_vault, _] =
-        accounts
-    {
-        let taker_b_info = TokenAccount::from_account_info(taker_mint_b_ata)
-            .map_err(|_| ProgramError::InvalidAccountData)?;
-
-        let taker_a_info = TokenAccount::from_account_info(taker_mint_a_ata)
-            .map_err(|_| ProgramError::InvalidAccountData)?;
+impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawAccounts<'a> {
+    type Error = ProgramError;
 
-        let vault_info = TokenAccount::from_account_info(escrow_vault)
-            .map_err(|_| ProgramError::InvalidAccountData)?;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [taker, taker_mint_b_ata, taker_mint_a_ata, maker_b_ata, mint_a, mint_b, escrow, escrow_vault, token_program, transfer_hook_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
 
-        require(taker.is_signer(), ProgramError::MissingRequiredSignature)?;
+        require_signer(taker)?;
+        require_writable(escrow)?;
+        require_owner(escrow, &crate::ID)?;
         require(!escrow.data_is_empty(), ProgramError::UninitializedAccount)?;
+        require_token_program(token_program)?;
 
-        let escrow_account = EscrowPda::load(escrow)?;
-        require(
-            taker_a_info.is_initialized(),
-            ProgramError::UninitializedAccount,
-        )?;
+        let escrow_data = EscrowPda::load(escrow)?;
+        escrow_data.check_id(escrow, &crate::ID)?;
 
         require(
-            pubkey_eq(taker_a_info.mint(), &escrow_account.mint_a),
+            pubkey_eq(mint_a.key(), &escrow_data.mint_a),
             EscrowErrors::InvalidMint.into(),
         )?;
         require(
-            pubkey_eq(mint_a.key(), &escrow_account.mint_a),
-            EscrowErrors::InvalidMint.into(),
-        )?;
-        require(
-            pubkey_eq(mint_b.key(), &escrow_account.mint_b),
+            pubkey_eq(mint_b.key(), &escrow_data.mint_b),
             EscrowErrors::InvalidMint.into(),
         )?;
 
-        require(
-            pubkey_eq(taker_b_info.mint(), &escrow_account.mint_b),
-            EscrowErrors::InvalidMint.into(),
-        )?;
+        {
+            let taker_b_info = token_account(taker_mint_b_ata, token_program)?;
+            let taker_a_info = token_account(taker_mint_a_ata, token_program)?;
+            let vault_info = token_account(escrow_vault, token_program)?;
+            let maker_b_info = token_account(maker_b_ata, token_program)?;
+
+            require(
+                taker_a_info.is_initialized(),
+                ProgramError::UninitializedAccount,
+            )?;
+            require(
+                pubkey_eq(taker_a_info.mint(), &escrow_data.mint_a),
+                EscrowErrors::InvalidMint.into(),
+            )?;
+            require(
+                pubkey_eq(taker_b_info.mint(), &escrow_data.mint_b),
+                EscrowErrors::InvalidMint.into(),
+            )?;
+
+            // The payment has to reach the creator, not an account the taker picked
+            require(
+                pubkey_eq(maker_b_info.mint(), &escrow_data.mint_b),
+                EscrowErrors::InvalidMint.into(),
+            )?;
+            require(
+                pubkey_eq(maker_b_info.owner(), &escrow_data.creator),
+                ProgramError::IncorrectAuthority,
+            )?;
+
+            // The taker pays `receive` out of their mint_b account
+            require(
+                taker_b_info.amount() >= escrow_data.receive,
+                EscrowErrors::InvalidBalance.into(),
+            )?;
+
+            require(
+                vault_info.is_initialized(),
+                ProgramError::UninitializedAccount,
+            )?;
+        }
 
-        require(
-            taker_a_info.amount() >= escrow_account.receive,
-            EscrowErrors::InvalidBalance.into(),
-        )?;
+        sol_log("Validation done");
 
-        require(
-            vault_info.is_initialized(),
-            ProgramError::UninitializedAccount,
-        )?;
+        Ok(Self {
+            taker,
+            taker_mint_b_ata,
+            taker_mint_a_ata,
+            maker_b_ata,
+            mint_a,
+            mint_b,
+            escrow,
+            escrow_vault,
+            token_program,
+            transfer_hook_accounts,
+            escrow_data,
+        })
+    }
+}
 
-        let expected_escrow = pinocchio::pubkey::create_program_address(
-            &[
-                EscrowPda::ESCROW_PREFIX.as_bytes(),
-                escrow_account.creator.as_ref(),
-                escrow_account.mint_a.as_ref(),
-                &[escrow_account.bump],
-            ],
-            program_id,
-        )?;
+pub fn process_withdraw(accounts: &[AccountInfo]) -> ProgramResult {
+    sol_log("Escrow Instruction: Withdraw");
+    let ctx = WithdrawAccounts::try_from(accounts)?;
+    let escrow_account = &ctx.escrow_data;
+
+    let bump = [escrow_account.bump];
+    let seed = [
+        Seed::from(EscrowPda::ESCROW_PREFIX.as_bytes()),
+        Seed::from(escrow_account.creator.as_ref()),
+        Seed::from(escrow_account.mint_a.as_ref()),
+        Seed::from(&bump),
+    ];
+    let seeds = Signer::from(&seed);
+    TransferChecked {
+        token_program: ctx.token_program.key(),
+        from: ctx.escrow_vault,
+        mint: ctx.mint_a,
+        to: ctx.taker_mint_a_ata,
+        authority: ctx.escrow,
+        amount: escrow_account.amount,
+        decimals: mint_decimals(ctx.mint_a, ctx.token_program)?,
+        hook_accounts: ctx.transfer_hook_accounts,
+    }
+    .invoke_signed(core::slice::from_ref(&seeds))?;
+
+    TransferChecked {
+        token_program: ctx.token_program.key(),
+        from: ctx.taker_mint_b_ata,
+        mint: ctx.mint_b,
+        to: ctx.maker_b_ata,
+        authority: ctx.taker,
+        amount: escrow_account.receive,
+        decimals: mint_decimals(ctx.mint_b, ctx.token_program)?,
+        hook_accounts: ctx.transfer_hook_accounts,
+    }
+    .invoke()?;
 
-        require(
-            pubkey_eq(&expected_escrow, escrow.key()),
-            EscrowErrors::InvalidEscrow.into(),
-        )?;
+    CloseAccount {
+        account: ctx.escrow_vault,
+        authority: ctx.escrow,
+        destination: ctx.taker,
+        token_program: ctx.token_program.key(),
+    }
+    .invoke_signed(&[seeds])?;
 
-        sol_log("Validation done");
+    close_account(ctx.escrow, ctx.taker)?;
 
-        Ok(escrow_account)
-    } else {
-        Err(ProgramError::NotEnoughAccountKeys)
-    }
+    Ok(())
 }
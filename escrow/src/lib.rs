@@ -2,10 +2,10 @@
 use pinocchio::{entrypoint, nostd_panic_handler};
 mod constants;
 mod errors;
-mod helper;
-mod instructions;
+pub mod instructions;
 pub mod processor;
 pub mod states;
+mod token;
 
 use processor::process_instruction;
 
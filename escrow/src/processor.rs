@@ -25,8 +25,8 @@ pub fn process_instruction(
 
     match ix {
         EscrowInstructions::CreateEscrow(data) => create_escrow(*program_id, accounts, data)?,
-        EscrowInstructions::Withdraw => process_withdraw(*program_id, accounts)?,
-        EscrowInstructions::Close => process_close(*program_id, accounts)?,
+        EscrowInstructions::Withdraw => process_withdraw(accounts)?,
+        EscrowInstructions::Close => process_close(accounts)?,
     }
     Ok(())
 }
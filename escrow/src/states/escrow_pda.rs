@@ -1,6 +1,9 @@
 use crate::errors::EscrowErrors;
 use borsh::{BorshDeserialize, BorshSerialize};
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+use program_utils::{check_header, require_pda_with_bump, Discriminator};
 
 /// `EscrowPda` represents the state of an escrow account in the program.
 ///
@@ -10,6 +13,8 @@ use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::
 /// account on-chain.
 ///
 /// Fields:
+/// - `discriminator`: Type tag telling escrow accounts apart from any other account.
+/// - `version`: Layout version of the account data.
 /// - `creator`: The public key of the user who created the escrow.
 /// - `amount`: The amount of `mint_a` tokens the creator is depositing into escrow.
 /// - `mint_a`: The token mint address for the asset being offered by the creator.
@@ -20,6 +25,8 @@ use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::
 /// and initialization.
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct EscrowPda {
+    pub discriminator: [u8; 8],
+    pub version: u8,
     pub creator: Pubkey,
     pub amount: u64,
     pub mint_a: Pubkey,
@@ -27,8 +34,18 @@ pub struct EscrowPda {
     pub receive: u64,
     pub bump: u8,
 }
+impl Discriminator for EscrowPda {
+    // First 8 bytes of sha256("account:EscrowPda")
+    const DISCRIMINATOR: [u8; 8] = [0xa8, 0xd9, 0x1e, 0x0e, 0xf8, 0xc5, 0x76, 0x80];
+    const VERSION: u8 = 1;
+    // Borsh layout, there's no padding so it's smaller than the in memory struct
+    const LEN: usize = Self::ESCROW_SIZE;
+    const UNSUPPORTED_VERSION: ProgramError =
+        ProgramError::Custom(EscrowErrors::UnsupportedLayoutVersion as u32);
+}
+
 impl EscrowPda {
-    pub const ESCROW_SIZE: usize = 32 * 3 + 8 * 2 + 1;
+    pub const ESCROW_SIZE: usize = 8 + 1 + 32 * 3 + 8 * 2 + 1;
 
     pub const ESCROW_PREFIX: &str = "escrow";
 
@@ -41,11 +58,25 @@ impl EscrowPda {
         ]
     }
 
+    /// Checks the account is the escrow PDA derived from the stored creator, mint and bump
+    #[inline(always)]
+    pub fn check_id(&self, escrow_account: &AccountInfo, program_id: &Pubkey) -> ProgramResult {
+        require_pda_with_bump(
+            escrow_account,
+            &Self::get_signer_seeds(&self.creator, &self.mint_a),
+            self.bump,
+            program_id,
+        )
+        .map_err(|_| EscrowErrors::InvalidEscrow.into())
+    }
+
     #[inline(always)]
     pub fn load(escrow_account: &AccountInfo) -> Result<Self, ProgramError> {
         if escrow_account.can_borrow_mut_data().is_ok() {
             let data = unsafe { escrow_account.borrow_data_unchecked() };
 
+            check_header::<Self>(data)?;
+
             let escrow_data =
                 EscrowPda::try_from_slice(data).map_err(|_| ProgramError::InvalidAccountData)?;
 
@@ -56,20 +87,34 @@ impl EscrowPda {
     }
 
     #[inline(always)]
-    pub fn init(
-        &mut self,
+    pub fn new(
         creator: &Pubkey,
         mint_a: &Pubkey,
         mint_b: &Pubkey,
         amount: u64,
         recieve: u64,
         bump: u8,
-    ) {
-        self.creator = *creator;
-        self.amount = amount;
-        self.mint_a = *mint_a;
-        self.mint_b = *mint_b;
-        self.receive = recieve;
-        self.bump = bump;
+    ) -> Self {
+        Self {
+            discriminator: Self::DISCRIMINATOR,
+            version: Self::VERSION,
+            creator: *creator,
+            amount,
+            mint_a: *mint_a,
+            mint_b: *mint_b,
+            receive: recieve,
+            bump,
+        }
+    }
+
+    /// Serializes the escrow into the account data
+    #[inline(always)]
+    pub fn save(&self, escrow_account: &AccountInfo) -> Result<(), ProgramError> {
+        let mut data = escrow_account
+            .try_borrow_mut_data()
+            .map_err(|_| ProgramError::from(EscrowErrors::BorrowInvalid))?;
+
+        self.serialize(&mut &mut data[..])
+            .map_err(|_| ProgramError::AccountDataTooSmall)
     }
 }
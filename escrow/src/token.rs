@@ -0,0 +1,50 @@
+//! Token accounts and mints of either token program
+//!
+//! An escrow can hold tokens of SPL Token or Token-2022 mints. Token-2022 accounts and mints keep
+//! the SPL Token layout and append their extensions after it, so both are read with the SPL Token
+//! state once the owner is checked against the token program of the instruction.
+
+use {
+    pinocchio::{
+        account_info::{AccountInfo, Ref},
+        program_error::ProgramError,
+        pubkey::pubkey_eq,
+        ProgramResult,
+    },
+    pinocchio_token::state::{Mint, TokenAccount},
+    program_utils::require,
+};
+
+pub fn require_token_program(token_program: &AccountInfo) -> ProgramResult {
+    require(
+        pubkey_eq(token_program.key(), &pinocchio_token::ID)
+            || pubkey_eq(token_program.key(), &pinocchio_token_2022::ID),
+        ProgramError::IncorrectProgramId,
+    )
+}
+
+/// `TokenAccount::from_account_info` accepting accounts of `token_program` with extensions
+pub fn token_account<'a>(
+    account: &'a AccountInfo,
+    token_program: &AccountInfo,
+) -> Result<Ref<'a, TokenAccount>, ProgramError> {
+    require(
+        account.is_owned_by(token_program.key()) && account.data_len() >= TokenAccount::LEN,
+        ProgramError::InvalidAccountData,
+    )?;
+
+    Ok(Ref::map(account.try_borrow_data()?, |data| unsafe {
+        TokenAccount::from_bytes_unchecked(data)
+    }))
+}
+
+/// Decimals of a mint of `token_program`, `TransferChecked` needs them
+pub fn mint_decimals(mint: &AccountInfo, token_program: &AccountInfo) -> Result<u8, ProgramError> {
+    require(
+        mint.is_owned_by(token_program.key()) && mint.data_len() >= Mint::LEN,
+        ProgramError::InvalidAccountData,
+    )?;
+
+    let data = mint.try_borrow_data()?;
+    Ok(unsafe { Mint::from_bytes_unchecked(&data) }.decimals())
+}
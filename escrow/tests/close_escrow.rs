@@ -2,7 +2,9 @@ mod helpers;
 
 #[cfg(test)]
 pub mod close_escrow_tests {
-    use crate::helpers::{close::get_close_configs, get_mollusk, structs::ReturnVal};
+    use crate::helpers::{
+        close::get_close_configs, get_mollusk, into_token_2022, structs::ReturnVal,
+    };
     use mollusk_svm::result::Check;
     use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
 
@@ -23,4 +25,22 @@ pub mod close_escrow_tests {
 
         let _ = mollusk.process_and_validate_instruction(&ix, &account_infos, &checks);
     }
+
+    #[test]
+    pub fn test_close_token_2022() {
+        let program_id = Pubkey::new_from_array(escrow::ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            account_meta,
+            account_infos,
+            ix_data,
+        } = into_token_2022(get_close_configs(&mollusk, &program_id));
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        let checks = [Check::success()];
+
+        let _ = mollusk.process_and_validate_instruction(&ix, &account_infos, &checks);
+    }
 }
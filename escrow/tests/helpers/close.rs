@@ -8,6 +8,7 @@ use super::{
 use borsh::BorshSerialize;
 use escrow::{processor::EscrowInstructions, states::EscrowPda};
 use mollusk_svm::Mollusk;
+use program_utils::Discriminator;
 use solana_sdk::{account::Account, message::AccountMeta, pubkey::Pubkey};
 use spl_token::solana_program::pubkey::Pubkey as sPubkey;
 
@@ -29,7 +30,7 @@ pub fn get_close_configs(mollusk: &Mollusk, program_id: &Pubkey) -> ReturnVal {
 
     let mint_a_config = get_mint_config(100_000 * 10u64.pow(6));
 
-    let (mint_a, _) = get_mint_accounts(Some([0x02; 32]), mollusk, mint_a_config);
+    let (mint_a, mint_a_account) = get_mint_accounts(Some([0x02; 32]), mollusk, mint_a_config);
     let mint_a_config = get_mint_config(100_000 * 10u64.pow(6));
 
     let (mint_b, _) = get_mint_accounts(Some([0x03; 32]), mollusk, mint_a_config);
@@ -58,6 +59,8 @@ pub fn get_close_configs(mollusk: &Mollusk, program_id: &Pubkey) -> ReturnVal {
     );
 
     let escrow_data = EscrowPda {
+        discriminator: EscrowPda::DISCRIMINATOR,
+        version: EscrowPda::VERSION,
         creator: *creator.as_array(),
         amount: 20_000u64.checked_mul(10u64.pow(6)).unwrap(), // taker a ata will gain
         bump,
@@ -83,6 +86,7 @@ pub fn get_close_configs(mollusk: &Mollusk, program_id: &Pubkey) -> ReturnVal {
         account_infos: vec![
             (creator, creator_account),
             (creator_a_ata, creator_a_ata_account),
+            (mint_a, mint_a_account),
             (escrow_pda, escrow_pda_account),
             (vault_a_ata, vault_a_ata_account),
             (token_program, token_program_account),
@@ -90,6 +94,7 @@ pub fn get_close_configs(mollusk: &Mollusk, program_id: &Pubkey) -> ReturnVal {
         account_meta: vec![
             AccountMeta::new(creator, true),
             AccountMeta::new(creator_a_ata, false),
+            AccountMeta::new_readonly(mint_a, false),
             AccountMeta::new(escrow_pda, true),
             AccountMeta::new(vault_a_ata, false),
             AccountMeta::new(token_program, false),
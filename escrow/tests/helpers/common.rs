@@ -1,4 +1,4 @@
-use super::structs::SystemConfig;
+use super::structs::{ReturnVal, SystemConfig};
 use borsh::BorshSerialize;
 use escrow::processor::EscrowInstructions;
 use mollusk_svm::Mollusk;
@@ -20,6 +20,11 @@ pub fn get_mollusk(program_id: &Pubkey) -> Mollusk {
         "tests/program_bytes/spl_token",
         &mollusk_svm::program::loader_keys::LOADER_V3,
     );
+    mollusk.add_program(
+        &Pubkey::new_from_array(pinocchio_token_2022::ID),
+        "tests/program_bytes/token_2022",
+        &mollusk_svm::program::loader_keys::LOADER_V3,
+    );
 
     mollusk
 }
@@ -134,3 +139,47 @@ pub fn get_ata_config(amount: u64, mint: sPubkey, owner: sPubkey) -> ATA {
 }
 
 pub const LAMPORTS_PER_SOL: u64 = 10u64.pow(9);
+
+/// Moves the mints and token accounts of an instruction config to Token-2022
+///
+/// Token-2022 reads accounts without extensions with the SPL Token layout, only their owner and the
+/// token program passed to the instruction change.
+pub fn into_token_2022(config: ReturnVal) -> ReturnVal {
+    let spl_token = Pubkey::new_from_array(*token_program.as_array());
+    let token_2022 = Pubkey::new_from_array(pinocchio_token_2022::ID);
+
+    let account_infos = config
+        .account_infos
+        .into_iter()
+        .map(|(key, mut account)| {
+            if key == spl_token {
+                (
+                    token_2022,
+                    mollusk_svm::program::create_program_account_loader_v3(&token_2022),
+                )
+            } else {
+                if account.owner == spl_token {
+                    account.owner = token_2022;
+                }
+                (key, account)
+            }
+        })
+        .collect();
+
+    let account_meta = config
+        .account_meta
+        .into_iter()
+        .map(|mut meta| {
+            if meta.pubkey == spl_token {
+                meta.pubkey = token_2022;
+            }
+            meta
+        })
+        .collect();
+
+    ReturnVal {
+        account_meta,
+        account_infos,
+        ix_data: config.ix_data,
+    }
+}
@@ -139,7 +139,7 @@ pub fn get_create_config(send: u64, recv: u64, mollusk: &Mollusk) -> ReturnVal {
             AccountMeta::new(mint_a, false),
             AccountMeta::new(mint_b, false),
             AccountMeta::new(creator_mint_ata, false),
-            AccountMeta::new(escrow_pda, true),
+            AccountMeta::new(escrow_pda, false),
             AccountMeta::new(escrow_mint_ata, false),
             AccountMeta::new(system_program, false),
             AccountMeta::new(token_program, false),
@@ -8,6 +8,7 @@ use super::{
 use borsh::BorshSerialize;
 use escrow::{processor::EscrowInstructions, states::EscrowPda};
 use mollusk_svm::Mollusk;
+use program_utils::Discriminator;
 use solana_sdk::{account::Account, message::AccountMeta, pubkey::Pubkey};
 use spl_token::solana_program::pubkey::Pubkey as sPubkey;
 
@@ -81,6 +82,8 @@ pub fn withdraw_configs(mollusk: &Mollusk) -> ReturnVal {
     );
 
     let escrow_data = EscrowPda {
+        discriminator: EscrowPda::DISCRIMINATOR,
+        version: EscrowPda::VERSION,
         creator: *creator.as_array(),
         amount: 20_000u64.checked_mul(10u64.pow(6)).unwrap(), // taker a ata will gain
         bump: escrow_bump,
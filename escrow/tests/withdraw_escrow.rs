@@ -2,9 +2,13 @@ mod helpers;
 
 #[cfg(test)]
 pub mod tests {
-    use crate::helpers::{get_mollusk, structs::ReturnVal, withdraw::withdraw_configs};
+    use crate::helpers::{
+        get_ata_accounts, get_ata_config, get_mollusk, structs::ReturnVal,
+        withdraw::withdraw_configs,
+    };
     use mollusk_svm::result::Check;
-    use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+    use solana_sdk::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
+    use spl_token::solana_program::pubkey::Pubkey as sPubkey;
 
     #[test]
     pub fn test_withdraw() {
@@ -23,4 +27,35 @@ pub mod tests {
 
         let _ = mollusk.process_and_validate_instruction(&ix, &account_infos, &checks);
     }
+
+    #[test]
+    pub fn test_withdraw_fails_for_foreign_maker_ata() {
+        let program_id = Pubkey::new_from_array(escrow::ID);
+        let mollusk = get_mollusk(&program_id);
+
+        let ReturnVal {
+            mut account_infos,
+            account_meta,
+            ix_data,
+        } = withdraw_configs(&mollusk);
+
+        // A mint_b account of the taker in place of the creator's
+        let (taker, _) = account_infos[0];
+        let (mint_b, _) = account_infos[5];
+        let foreign_ata_config = get_ata_config(
+            0,
+            sPubkey::new_from_array(*mint_b.as_array()),
+            sPubkey::new_from_array(*taker.as_array()),
+        );
+        let (_, foreign_ata_account) = get_ata_accounts(None, &mollusk, foreign_ata_config);
+        account_infos[3].1 = foreign_ata_account;
+
+        let ix = Instruction::new_with_bytes(program_id, &ix_data, account_meta);
+
+        mollusk.process_and_validate_instruction(
+            &ix,
+            &account_infos,
+            &[Check::err(ProgramError::IncorrectAuthority)],
+        );
+    }
 }
@@ -0,0 +1,80 @@
+use crate::{require, require_owner};
+use bytemuck::Pod;
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+/// Offset of the layout version byte in every program account
+pub const VERSION_OFFSET: usize = 8;
+
+/// Program accounts start with an 8 byte type tag followed by a layout version byte
+pub trait Discriminator: Sized {
+    const DISCRIMINATOR: [u8; 8];
+    const VERSION: u8;
+    /// Size of the account data
+    const LEN: usize = core::mem::size_of::<Self>();
+    /// Error returned when the account was written with another layout version
+    const UNSUPPORTED_VERSION: ProgramError = ProgramError::InvalidAccountData;
+}
+
+/// Checks the account data has the size, discriminator and layout version of `T`
+pub fn check_header<T: Discriminator>(data: &[u8]) -> Result<(), ProgramError> {
+    require(
+        data.len() == T::LEN && data[..VERSION_OFFSET] == T::DISCRIMINATOR,
+        ProgramError::InvalidAccountData,
+    )?;
+
+    require(data[VERSION_OFFSET] == T::VERSION, T::UNSUPPORTED_VERSION)
+}
+
+/// Casts the data of an account owned by `owner` to `T` after checking its header
+#[allow(clippy::mut_from_ref)]
+pub fn load<'a, T>(account: &'a AccountInfo, owner: &Pubkey) -> Result<&'a mut T, ProgramError>
+where
+    T: Discriminator + Pod,
+{
+    require_owner(account, owner)?;
+
+    let data = unsafe { account.borrow_mut_data_unchecked() };
+
+    check_header::<T>(data)?;
+
+    bytemuck::try_from_bytes_mut::<T>(data).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// Read only version of `load`
+pub fn load_read_only<'a, T>(
+    account: &'a AccountInfo,
+    owner: &Pubkey,
+) -> Result<&'a T, ProgramError>
+where
+    T: Discriminator + Pod,
+{
+    require_owner(account, owner)?;
+
+    let data = unsafe { account.borrow_data_unchecked() };
+
+    check_header::<T>(data)?;
+
+    bytemuck::try_from_bytes::<T>(data).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// Tags a freshly created account with the discriminator and version of `T`
+///
+/// Errors out if the account already carries a discriminator
+#[allow(clippy::mut_from_ref)]
+pub fn init_account<T>(account: &AccountInfo) -> Result<&mut T, ProgramError>
+where
+    T: Discriminator + Pod,
+{
+    let data = unsafe { account.borrow_mut_data_unchecked() };
+
+    require(data.len() == T::LEN, ProgramError::InvalidAccountData)?;
+    require(
+        data[..=VERSION_OFFSET].iter().all(|byte| *byte == 0),
+        ProgramError::AccountAlreadyInitialized,
+    )?;
+
+    data[..VERSION_OFFSET].copy_from_slice(&T::DISCRIMINATOR);
+    data[VERSION_OFFSET] = T::VERSION;
+
+    bytemuck::try_from_bytes_mut::<T>(data).map_err(|_| ProgramError::InvalidAccountData)
+}
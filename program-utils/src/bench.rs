@@ -0,0 +1,103 @@
+//! Compute unit baseline checks shared by the program benches
+//!
+//! Consumed CUs are written to `target/benches/compute_units.md`. The run fails when an
+//! instruction has no entry in `benches/compute_units.baseline` or consumes more than its entry
+//! plus `CU_BENCH_MARGIN_BPS` (500, i.e. 5%, by default). Set `CU_BENCH_UPDATE_BASELINE=1` to
+//! rewrite the baseline with the current numbers.
+extern crate std;
+
+use std::{
+    collections::BTreeMap,
+    eprintln,
+    fmt::Write,
+    format, fs, print,
+    process::exit,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+const BASELINE_PATH: &str = "benches/compute_units.baseline";
+const RESULTS_DIR: &str = "target/benches";
+const RESULTS_PATH: &str = "target/benches/compute_units.md";
+const DEFAULT_MARGIN_BPS: u64 = 500;
+
+/// Reports the consumed CUs of each instruction against the baseline and exits with an error on
+/// a regression or an instruction missing from the baseline
+pub fn check_compute_units(results: &[(&str, u64)]) {
+    let margin_bps = std::env::var("CU_BENCH_MARGIN_BPS")
+        .ok()
+        .map(|margin| {
+            margin
+                .parse()
+                .expect("CU_BENCH_MARGIN_BPS must be a number")
+        })
+        .unwrap_or(DEFAULT_MARGIN_BPS);
+
+    let baseline = read_baseline();
+    let mut report = String::from("| Instruction | CUs | Baseline | Delta |\n|---|---|---|---|\n");
+    let mut regressions = Vec::new();
+
+    for (name, consumed) in results.iter() {
+        match baseline.get(*name) {
+            Some(expected) => {
+                let delta = *consumed as i64 - *expected as i64;
+                let allowed = expected + expected * margin_bps / 10_000;
+
+                writeln!(report, "| {name} | {consumed} | {expected} | {delta:+} |").unwrap();
+
+                if *consumed > allowed {
+                    regressions.push(format!(
+                        "{name}: {consumed} CUs exceeds baseline {expected} by more than {margin_bps} bps"
+                    ));
+                }
+            }
+            None => {
+                writeln!(report, "| {name} | {consumed} | - | new |").unwrap();
+                regressions.push(format!("{name}: missing from {BASELINE_PATH}"));
+            }
+        }
+    }
+
+    print!("{report}");
+    fs::create_dir_all(RESULTS_DIR).expect("Unable to create the results directory");
+    fs::write(RESULTS_PATH, &report).expect("Unable to write the results file");
+
+    if std::env::var("CU_BENCH_UPDATE_BASELINE").is_ok() {
+        write_baseline(results);
+        return;
+    }
+
+    if !regressions.is_empty() {
+        for regression in regressions.iter() {
+            eprintln!("{regression}");
+        }
+        exit(1);
+    }
+}
+
+/// Reads `<instruction> <cus>` lines, ignoring blank lines and `#` comments
+fn read_baseline() -> BTreeMap<String, u64> {
+    let contents = fs::read_to_string(BASELINE_PATH).unwrap_or_default();
+
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (name, cus) = line.split_once(char::is_whitespace)?;
+            Some((name.to_string(), cus.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+fn write_baseline(results: &[(&str, u64)]) {
+    let mut contents = String::from(
+        "# Compute units per instruction, regenerate with CU_BENCH_UPDATE_BASELINE=1\n",
+    );
+
+    for (name, consumed) in results.iter() {
+        writeln!(contents, "{name} {consumed}").unwrap();
+    }
+
+    fs::write(BASELINE_PATH, contents).expect("Unable to write the baseline file");
+}
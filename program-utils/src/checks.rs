@@ -0,0 +1,32 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+/// Errors out if the condition isn't true
+pub fn require(condition: bool, err: ProgramError) -> Result<(), ProgramError> {
+    if !condition {
+        return Err(err);
+    }
+    Ok(())
+}
+
+pub fn require_signer(account: &AccountInfo) -> Result<(), ProgramError> {
+    require(account.is_signer(), ProgramError::MissingRequiredSignature)
+}
+
+pub fn require_writable(account: &AccountInfo) -> Result<(), ProgramError> {
+    require(account.is_writable(), ProgramError::Immutable)
+}
+
+pub fn require_owner(account: &AccountInfo, owner: &Pubkey) -> Result<(), ProgramError> {
+    require(
+        account.is_owned_by(owner),
+        ProgramError::InvalidAccountOwner,
+    )
+}
+
+/// Errors out if the account already holds data, used for accounts created by the instruction
+pub fn require_empty(account: &AccountInfo) -> Result<(), ProgramError> {
+    require(
+        account.data_is_empty(),
+        ProgramError::AccountAlreadyInitialized,
+    )
+}
@@ -0,0 +1,23 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+/// Moves `lamports` between two accounts, `from` has to be owned by the executing program
+pub fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    let mut from_lamports = from.try_borrow_mut_lamports()?;
+    let mut to_lamports = to.try_borrow_mut_lamports()?;
+
+    *from_lamports = from_lamports
+        .checked_sub(lamports)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    *to_lamports = to_lamports
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+/// Sends every lamport of a program owned account to `destination` and closes it
+pub fn close_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    transfer_lamports(account, destination, account.lamports())?;
+
+    account.close()
+}
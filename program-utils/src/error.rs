@@ -0,0 +1,15 @@
+/// Implements `From<$error> for ProgramError`, the variant's discriminant becomes the custom code
+///
+/// ```ignore
+/// program_utils::impl_program_error!(AmmError);
+/// ```
+#[macro_export]
+macro_rules! impl_program_error {
+    ($error:ty) => {
+        impl From<$error> for pinocchio::program_error::ProgramError {
+            fn from(e: $error) -> Self {
+                pinocchio::program_error::ProgramError::Custom(e as u32)
+            }
+        }
+    };
+}
@@ -0,0 +1,18 @@
+//! Helpers shared by the AMM and escrow programs
+#![no_std]
+
+pub mod account;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod checks;
+pub mod close;
+pub mod error;
+pub mod math;
+pub mod pda;
+pub mod token;
+
+pub use account::*;
+pub use checks::*;
+pub use close::*;
+pub use math::*;
+pub use pda::*;
@@ -0,0 +1,36 @@
+use pinocchio::program_error::ProgramError;
+
+pub fn checked_add(a: u64, b: u64) -> Result<u64, ProgramError> {
+    a.checked_add(b).ok_or(ProgramError::ArithmeticOverflow)
+}
+
+pub fn checked_sub(a: u64, b: u64) -> Result<u64, ProgramError> {
+    a.checked_sub(b).ok_or(ProgramError::ArithmeticOverflow)
+}
+
+pub fn checked_mul(a: u64, b: u64) -> Result<u64, ProgramError> {
+    a.checked_mul(b).ok_or(ProgramError::ArithmeticOverflow)
+}
+
+pub fn checked_div(a: u64, b: u64) -> Result<u64, ProgramError> {
+    a.checked_div(b).ok_or(ProgramError::ArithmeticOverflow)
+}
+
+/// `value * numerator / denominator` without overflowing the intermediate product
+pub fn mul_div(value: u64, numerator: u64, denominator: u64) -> Option<u64> {
+    (value as u128)
+        .checked_mul(numerator as u128)?
+        .checked_div(denominator as u128)?
+        .try_into()
+        .ok()
+}
+
+pub fn bps_mul(bps: u64, value: u64, divisor: u64) -> Option<u64> {
+    bps_mul_raw(bps, value, divisor)?.try_into().ok()
+}
+
+pub fn bps_mul_raw(bps: u64, value: u64, divisor: u64) -> Option<u128> {
+    (value as u128)
+        .checked_mul(bps as u128)?
+        .checked_div(divisor as u128)
+}
@@ -0,0 +1,47 @@
+use crate::require;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{create_program_address, find_program_address, pubkey_eq, Pubkey},
+};
+
+/// Checks the account is the canonical PDA for `seeds` and returns its bump
+pub fn require_pda(
+    account: &AccountInfo,
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (expected, bump) = find_program_address(seeds, program_id);
+
+    require(
+        pubkey_eq(account.key(), &expected),
+        ProgramError::IncorrectProgramId,
+    )?;
+
+    Ok(bump)
+}
+
+/// Checks the account is the PDA for `seeds` and a bump stored on chain
+///
+/// Cheaper than `require_pda` since the bump doesn't have to be searched for
+pub fn require_pda_with_bump(
+    account: &AccountInfo,
+    seeds: &[&[u8]],
+    bump: u8,
+    program_id: &Pubkey,
+) -> Result<(), ProgramError> {
+    let bump = [bump];
+    let mut seeds_with_bump: [&[u8]; 17] = [&[]; 17];
+
+    require(seeds.len() < 17, ProgramError::MaxSeedLengthExceeded)?;
+
+    seeds_with_bump[..seeds.len()].copy_from_slice(seeds);
+    seeds_with_bump[seeds.len()] = &bump;
+
+    let expected = create_program_address(&seeds_with_bump[..=seeds.len()], program_id)?;
+
+    require(
+        pubkey_eq(account.key(), &expected),
+        ProgramError::IncorrectProgramId,
+    )
+}
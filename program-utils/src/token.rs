@@ -0,0 +1,96 @@
+//! `TransferChecked` for mints of either token program
+//!
+//! Token-2022 invokes the hook program of a mint on every transfer and looks for the accounts the
+//! hook needs among the remaining accounts of the transfer. Programs moving tokens take those
+//! accounts, already resolved by the client from the mint's extra account metas, after their own
+//! accounts and forward them untouched.
+
+use crate::require;
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::slice_invoke_signed,
+    instruction::{AccountMeta, Instruction, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+const TRANSFER_CHECKED: u8 = 12;
+
+/// Most accounts a transfer forwards to the hook: the extra accounts, the validation account and
+/// the hook program
+pub const MAX_TRANSFER_HOOK_ACCOUNTS: usize = 16;
+
+/// `TransferChecked` forwarding the accounts a transfer hook needs
+///
+/// ### Accounts:
+///   0. `[WRITE]` The source account.
+///   1. `[]` The token mint.
+///   2. `[WRITE]` The destination account.
+///   3. `[SIGNER]` The source account's owner/delegate.
+///   4. ..`hook_accounts`, with the privileges they were passed with.
+pub struct TransferChecked<'a, 'b> {
+    /// SPL Token or Token-2022, only Token-2022 mints can have a hook
+    pub token_program: &'a Pubkey,
+    pub from: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub to: &'a AccountInfo,
+    pub authority: &'a AccountInfo,
+    pub amount: u64,
+    pub decimals: u8,
+    /// At most `MAX_TRANSFER_HOOK_ACCOUNTS`, empty for mints without a hook
+    pub hook_accounts: &'b [AccountInfo],
+}
+
+impl TransferChecked<'_, '_> {
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        require(
+            self.hook_accounts.len() <= MAX_TRANSFER_HOOK_ACCOUNTS,
+            ProgramError::InvalidArgument,
+        )?;
+
+        let accounts_len = 4 + self.hook_accounts.len();
+
+        let account_metas: [AccountMeta; 4 + MAX_TRANSFER_HOOK_ACCOUNTS] =
+            core::array::from_fn(|index| match index {
+                0 => AccountMeta::writable(self.from.key()),
+                1 => AccountMeta::readonly(self.mint.key()),
+                2 => AccountMeta::writable(self.to.key()),
+                3 => AccountMeta::readonly_signer(self.authority.key()),
+                index if index < accounts_len => {
+                    let account = &self.hook_accounts[index - 4];
+                    AccountMeta::new(account.key(), account.is_writable(), account.is_signer())
+                }
+                _ => AccountMeta::readonly(self.mint.key()),
+            });
+        let account_infos: [&AccountInfo; 4 + MAX_TRANSFER_HOOK_ACCOUNTS] =
+            core::array::from_fn(|index| match index {
+                0 => self.from,
+                2 => self.to,
+                3 => self.authority,
+                index if index > 3 && index < accounts_len => &self.hook_accounts[index - 4],
+                _ => self.mint,
+            });
+
+        // Instruction data layout:
+        // -  [0]: instruction discriminator
+        // -  [1..9]: amount (u64)
+        // -  [9]: decimals
+        let mut instruction_data = [0u8; 10];
+        instruction_data[0] = TRANSFER_CHECKED;
+        instruction_data[1..9].copy_from_slice(&self.amount.to_le_bytes());
+        instruction_data[9] = self.decimals;
+
+        let instruction = Instruction {
+            program_id: self.token_program,
+            accounts: &account_metas[..accounts_len],
+            data: &instruction_data,
+        };
+
+        slice_invoke_signed(&instruction, &account_infos[..accounts_len], signers)
+    }
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+pub mod account_tests {
+    use bytemuck::{Pod, Zeroable};
+    use pinocchio::program_error::ProgramError;
+    use program_utils::{check_header, Discriminator, VERSION_OFFSET};
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Pod, Zeroable)]
+    struct Counter {
+        discriminator: [u8; 8],
+        version: u8,
+        _padding: [u8; 7],
+        count: u64,
+    }
+
+    impl Discriminator for Counter {
+        const DISCRIMINATOR: [u8; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+        const VERSION: u8 = 2;
+        const UNSUPPORTED_VERSION: ProgramError = ProgramError::Custom(7);
+    }
+
+    fn counter_bytes() -> Vec<u8> {
+        let counter = Counter {
+            discriminator: Counter::DISCRIMINATOR,
+            version: Counter::VERSION,
+            _padding: [0; 7],
+            count: 42,
+        };
+
+        bytemuck::bytes_of(&counter).to_vec()
+    }
+
+    #[test]
+    pub fn test_check_header_accepts_current_layout() {
+        assert_eq!(check_header::<Counter>(&counter_bytes()), Ok(()));
+    }
+
+    #[test]
+    pub fn test_check_header_rejects_wrong_discriminator_and_size() {
+        let mut data = counter_bytes();
+        data[0] = 0;
+        assert_eq!(
+            check_header::<Counter>(&data),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        let data = counter_bytes();
+        assert_eq!(
+            check_header::<Counter>(&data[..Counter::LEN - 1]),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    pub fn test_check_header_rejects_old_version() {
+        let mut data = counter_bytes();
+        data[VERSION_OFFSET] = 1;
+
+        assert_eq!(check_header::<Counter>(&data), Err(ProgramError::Custom(7)));
+    }
+}
@@ -0,0 +1,29 @@
+#[cfg(test)]
+pub mod math_tests {
+    use pinocchio::program_error::ProgramError;
+    use program_utils::{bps_mul, checked_add, checked_div, checked_sub, mul_div};
+
+    #[test]
+    pub fn test_checked_helpers_error_on_overflow() {
+        assert_eq!(
+            checked_add(u64::MAX, 1),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+        assert_eq!(checked_sub(0, 1), Err(ProgramError::ArithmeticOverflow));
+        assert_eq!(checked_div(1, 0), Err(ProgramError::ArithmeticOverflow));
+        assert_eq!(checked_add(1, 2), Ok(3));
+    }
+
+    #[test]
+    pub fn test_mul_div_uses_wide_intermediate() {
+        assert_eq!(mul_div(u64::MAX, 10, 20), Some(u64::MAX / 2));
+        assert_eq!(mul_div(u64::MAX, 2, 1), None);
+        assert_eq!(mul_div(1, 1, 0), None);
+    }
+
+    #[test]
+    pub fn test_bps_mul() {
+        assert_eq!(bps_mul(100, 1_000_000, 10_000), Some(10_000));
+        assert_eq!(bps_mul(1, 1, 0), None);
+    }
+}